@@ -3,7 +3,7 @@ use crate::compiler::analyzer::resolvers::{class_definition, function_definition
 use crate::compiler::tokenizer::token::{Token, TokenVec};
 use crate::public::compile_time::ast::ast_enum::{ASTNode, ASTVec};
 use crate::public::compile_time::ast::types::{
-    ExpressionNode, ImportNode, ModuleType, VariableNode,
+    ExpressionNode, FormatStringNode, ImportNode, ModuleType, VariableNode,
 };
 use crate::public::compile_time::dividers::Divider;
 use crate::public::compile_time::keywords::Keyword;
@@ -22,11 +22,31 @@ pub fn resolve(tokens: &mut TokenVec) -> Result<ExpressionNode, ()> {
     while let Some(token) = tokens.pop_front() {
         match token {
             Token::Number(num) => params.push(ASTNode::NumberLiteral(num)),
+            Token::Quantity(quantity) => params.push(ASTNode::QuantityLiteral(quantity)),
             Token::String(str) => params.push(ASTNode::StringLiteral(str)),
+            Token::FormatString(template, expression_tokens) => {
+                let mut args = vec![];
+                for mut tokens in expression_tokens {
+                    args.push(resolve(&mut tokens)?);
+                }
+                let node = FormatStringNode { template, args };
+                params.push(ASTNode::FormatString(node.into()));
+            }
             Token::Symbol(sym) => {
                 if sym == Symbols::Equal {
                     return Err(assignment_error("invalid left-hand value")?);
                 }
+
+                // `+` and `-` at the start of expression or
+                // following another symbol are prefix operators.
+                let is_prefix = matches!(params.last(), Some(ASTNode::SymbolLiteral(_)) | None);
+                if is_prefix && sym == Symbols::Plus {
+                    continue;
+                }
+                if is_prefix && sym == Symbols::Minus {
+                    params.push(ASTNode::SymbolLiteral(Symbols::Negate));
+                    continue;
+                }
                 params.push(ASTNode::SymbolLiteral(sym))
             }
 
@@ -118,7 +138,9 @@ pub fn resolve(tokens: &mut TokenVec) -> Result<ExpressionNode, ()> {
             ASTNode::Variable(_)
             | ASTNode::Assignment(_)
             | ASTNode::NumberLiteral(_)
+            | ASTNode::QuantityLiteral(_)
             | ASTNode::StringLiteral(_)
+            | ASTNode::FormatString(_)
             | ASTNode::ArrayLiteral(_)
             | ASTNode::MapLiteral(_)
             | ASTNode::Expression(_)
@@ -131,8 +153,10 @@ pub fn resolve(tokens: &mut TokenVec) -> Result<ExpressionNode, ()> {
             | ASTNode::FunctionDefinition(_)
             | ASTNode::ElementReading(_) => result_stack.push(node),
 
-            ASTNode::SymbolLiteral(_) => {
-                if symbol_stack.len() == 0 {
+            ASTNode::SymbolLiteral(sym) => {
+                // prefix operators have no left-hand operand,
+                // so nothing in the symbol_stack can be reduced.
+                if symbol_stack.len() == 0 || Symbols::is_unary_symbol(sym) {
                     symbol_stack.push(node);
                     continue;
                 }
@@ -140,11 +164,15 @@ pub fn resolve(tokens: &mut TokenVec) -> Result<ExpressionNode, ()> {
                 let mut last_node = symbol_stack.last().unwrap();
                 let mut priority = compare(current_node, last_node)?;
 
-                if priority > 1 {
+                // `^` is right-associative:
+                // the symbol with the same priority is kept in stack.
+                let min_priority = if sym == Symbols::Power { -1 } else { 0 };
+
+                if priority > min_priority {
                     // current priority > last priority
                     symbol_stack.push(node);
                 } else {
-                    while priority <= 0 {
+                    while priority <= min_priority {
                         let poped_node = symbol_stack.pop().unwrap();
                         result_stack.push(poped_node);
 
@@ -1,10 +1,53 @@
 use crate::compiler::tokenizer::token::{Token, TokenVec};
 use crate::public::compile_time::ast::types::LazyExpressionNode;
+use crate::public::compile_time::dividers::Divider;
 use crate::public::compile_time::parens::Paren;
 use crate::public::error::syntax_error;
 
 use super::sequence;
 
+// resolve the params before `=>`, like: `{x, y => x ^ 2 + y}`
+fn params_resolve(sub_tokens: &mut TokenVec) -> Result<Vec<String>, ()> {
+    let mut params = Vec::<String>::new();
+
+    // the arrow should be out of nested lazy-expressions
+    let mut brace_count = 0;
+    let arrow_index = sub_tokens.iter().position(|token| {
+        match token {
+            Token::Paren(Paren::LeftBrace) => brace_count += 1,
+            Token::Paren(Paren::RightBrace) => brace_count -= 1,
+            _ => {}
+        }
+        brace_count == 0 && *token == Token::Divider(Divider::Arrow)
+    });
+    let Some(arrow_index) = arrow_index else {
+        return Ok(params);
+    };
+
+    let mut param_tokens = sub_tokens.drain(..=arrow_index);
+    param_tokens.next_back();
+    while let Some(token) = param_tokens.next() {
+        let Token::Identi(name) = token else {
+            let msg = format!("invalid lazy-expression param: {}", token);
+            return Err(syntax_error(&msg)?);
+        };
+        if params.contains(&name) {
+            let msg = format!("duplicate lazy-expression param '{}'", name);
+            return Err(syntax_error(&msg)?);
+        }
+        params.push(name);
+
+        match param_tokens.next() {
+            Some(Token::Divider(Divider::Comma)) | None => {}
+            Some(token) => {
+                let msg = format!("unexpected token in lazy-expression params: {}", token);
+                return Err(syntax_error(&msg)?);
+            }
+        }
+    }
+    Ok(params)
+}
+
 pub fn resolve(tokens: &mut TokenVec) -> Result<LazyExpressionNode, ()> {
     let mut sub_tokens = TokenVec::new();
     let mut brace_count = 1;
@@ -25,6 +68,10 @@ pub fn resolve(tokens: &mut TokenVec) -> Result<LazyExpressionNode, ()> {
         return Err(syntax_error("unmatched brace")?);
     }
 
+    let params = params_resolve(&mut sub_tokens)?;
     let sub_sequence = sequence::resolve(&mut sub_tokens)?;
-    return Ok(LazyExpressionNode { sub_sequence });
+    return Ok(LazyExpressionNode {
+        params,
+        sub_sequence,
+    });
 }
@@ -3,14 +3,14 @@ use crate::public::{
     error::{internal_error, InternalComponent},
 };
 
-const PRIORITY: [i8; 15] = [
-    2, // Symbols::Plus
-    2, // Symbols::Minus
-    3, // Symbols::Multiply
-    3, // Symbols::Divide
-    3, // Symbols::Mod
-    4, // Symbols::Power
-    5, // Symbols::Not
+const PRIORITY: [i8; 24] = [
+    6, // Symbols::Plus
+    6, // Symbols::Minus
+    7, // Symbols::Multiply
+    7, // Symbols::Divide
+    7, // Symbols::Mod
+    9, // Symbols::Power
+    8, // Symbols::Not
     1, // Symbols::LessThan
     1, // Symbols::MoreThan
     1, // Symbols::LessThanEqual
@@ -19,6 +19,15 @@ const PRIORITY: [i8; 15] = [
     1, // Symbols::NotEqual
     0, // Symbols::AndSign
     0, // Symbols::OrSign
+    7, // Symbols::FloorDivide
+    4, // Symbols::BitAnd
+    2, // Symbols::BitOr
+    3, // Symbols::BitXor
+    8, // Symbols::BitNot
+    5, // Symbols::LeftShift
+    5, // Symbols::RightShift
+    8, // Symbols::Negate
+    0, // Symbols::Convert
 ];
 
 fn get_priority(symbol_node: &ASTNode) -> Result<i8, ()> {
@@ -1,16 +1,80 @@
-pub fn char_converter(ch: char) -> Result<char, ()> {
+use std::str::Chars;
+
+use crate::public::error::syntax_error;
+
+// resolve the escape sequence after the `\`, like:
+// `\n` | `\t` | `\\` | `\x41` | `\u{1F600}`
+pub fn char_converter(chars: &mut Chars, index: &mut usize) -> Result<char, ()> {
+    let Some(ch) = chars.next() else {
+        return Err(syntax_error("unexpected end of escape sequence")?);
+    };
+    *index += 1;
+
     let result: u8 = match ch {
-        '\"' => 34,         // '\"'
-        '\'' => 39,         // '\''
-        '\\' => 92,         // '\\'
-        'a' => 7,           // '\a'
-        'b' => 8,           // '\b'
-        'n' => 10,          // '\n'
-        'r' => 13,          // '\r'
-        's' => 32,          // '\s'
-        't' => 9,           // '\t'
-        'v' => 11,          // '\v'
+        '\"' => 34, // '\"'
+        '\'' => 39, // '\''
+        '\\' => 92, // '\\'
+        '0' => 0,   // '\0'
+        'a' => 7,   // '\a'
+        'b' => 8,   // '\b'
+        'n' => 10,  // '\n'
+        'r' => 13,  // '\r'
+        's' => 32,  // '\s'
+        't' => 9,   // '\t'
+        'v' => 11,  // '\v'
+        'x' => return hex_escape(chars, index),
+        'u' => return unicode_escape(chars, index),
         _ => return Ok(ch), // for other: return itself
     };
     Ok(result as char)
 }
+
+// `\x41`: exactly two hex digits
+fn hex_escape(chars: &mut Chars, index: &mut usize) -> Result<char, ()> {
+    let digits: String = chars.clone().take(2).collect();
+    let is_valid = digits.len() == 2 && digits.chars().all(|ch| ch.is_ascii_hexdigit());
+    if !is_valid {
+        let msg = format!("invalid escape sequence `\\x{}`", digits);
+        return Err(syntax_error(&msg)?);
+    }
+    chars.nth(1);
+    *index += 2;
+
+    let code = u32::from_str_radix(&digits, 16).unwrap();
+    Ok(char::from_u32(code).unwrap())
+}
+
+// `\u{1F600}`: one to six hex digits inside braces
+fn unicode_escape(chars: &mut Chars, index: &mut usize) -> Result<char, ()> {
+    if chars.clone().next() != Some('{') {
+        return Err(syntax_error("expected '{' after `\\u` escape")?);
+    }
+    chars.next();
+    *index += 1;
+
+    let mut digits = String::new();
+    loop {
+        let Some(ch) = chars.next() else {
+            return Err(syntax_error("unclosed `\\u{...}` escape sequence")?);
+        };
+        *index += 1;
+
+        if ch == '}' {
+            break;
+        }
+        digits.push(ch);
+    }
+
+    let is_valid =
+        (1..=6).contains(&digits.len()) && digits.chars().all(|ch| ch.is_ascii_hexdigit());
+    let code_point = is_valid
+        .then(|| u32::from_str_radix(&digits, 16).unwrap())
+        .and_then(char::from_u32);
+    match code_point {
+        Some(ch) => Ok(ch),
+        None => {
+            let msg = format!("invalid unicode escape `\\u{{{}}}`", digits);
+            Err(syntax_error(&msg)?)
+        }
+    }
+}
@@ -8,50 +8,253 @@ use crate::public::compile_time::dividers::Divider;
 use crate::public::compile_time::keywords::Keyword;
 use crate::public::compile_time::parens::Paren;
 use crate::public::error::{assignment_error, syntax_error};
+use crate::public::value::quantity::{CompoundUnit, Quantity};
 use crate::public::value::symbols::Symbols;
-use crate::public::value::{number::Number, value::ValueType};
-use crate::utils::ascii::{ascii_to_num, is_identi_ascii};
+use crate::public::value::{big_int::BigInt, number::Number, value::ValueType};
+use crate::utils::unicode::{is_identi_char, is_identi_continue};
 
 use token::{Token, TokenType, TokenVec};
 
-fn number_resolver(chars: &mut Chars, first_ch: char, index: &mut usize) -> (char, Number) {
+// resolve number literals like:
+// `255` | `1_000_000` | `0.5` | `1.5e-3` | `6.02E23` | `0xFF` | `0b1010` | `0o755` | `2i`
+fn number_resolver(
+    chars: &mut Chars,
+    first_ch: char,
+    index: &mut usize,
+) -> Result<(char, Number), ()> {
     enum State {
         Int,
         Float,
+        Exponent,
+    }
+
+    // radix prefixed integer
+    if first_ch == '0' {
+        let radix = match chars.clone().next() {
+            Some('x' | 'X') => 16,
+            Some('b' | 'B') => 2,
+            Some('o' | 'O') => 8,
+            _ => 10,
+        };
+        if radix != 10 {
+            chars.next();
+            *index += 1;
+            return radix_resolver(chars, radix, index);
+        }
     }
 
     let mut state = State::Int;
-    let mut float_para: f64 = 10.0;
-    let mut value = Number::Int(ascii_to_num(first_ch));
+    let mut literal = String::from(first_ch);
     let mut cached_ch = '\0';
 
     while let Some(ch) = chars.next() {
         *index += 1;
 
-        if ch.is_ascii_digit() {
-            let num_ascii = ascii_to_num(ch);
-            match state {
-                State::Int => {
-                    value = value * Number::Int(10);
-                    value = value + Number::Int(num_ascii);
+        let mut rest = chars.clone();
+        let next_ch = rest.next();
+        let is_next_digit = next_ch.is_some_and(|ch| ch.is_ascii_digit());
+
+        match (ch, &state) {
+            (_, _) if ch.is_ascii_digit() => literal.push(ch),
+            ('_', _) => {
+                // digit separator should be between two digits
+                let is_last_digit = literal.ends_with(|ch: char| ch.is_ascii_digit());
+                if !is_last_digit || !is_next_digit {
+                    let msg = format!("invalid digit separator in number literal `{}_`", literal);
+                    return Err(syntax_error(&msg)?);
+                }
+            }
+            ('.', State::Int) => {
+                state = State::Float;
+                literal.push(ch);
+            }
+            ('e' | 'E', State::Int | State::Float) => {
+                // exponent part: `e5` | `e+5` | `e-5`
+                let is_exponent = match next_ch {
+                    Some('+' | '-') => rest.next().is_some_and(|ch| ch.is_ascii_digit()),
+                    _ => is_next_digit,
+                };
+                if !is_exponent {
+                    cached_ch = ch;
+                    break;
                 }
-                State::Float => {
-                    value = value + Number::Float((num_ascii as f64) / float_para);
-                    float_para *= 10.0;
+                state = State::Exponent;
+                literal.push(ch);
+                if let Some(sign @ ('+' | '-')) = next_ch {
+                    chars.next();
+                    *index += 1;
+                    literal.push(sign);
                 }
             }
-            continue;
+            _ => {
+                cached_ch = ch;
+                break;
+            }
         }
+    }
 
-        if ch == '.' {
-            state = State::Float;
-            value = value.float();
-            continue;
+    let value = match state {
+        State::Int => Number::from_big(BigInt::from_str_radix(&literal, 10).unwrap()),
+        // the standard float parsing is correctly rounded
+        State::Float | State::Exponent => Number::Float(literal.parse::<f64>().unwrap()),
+    };
+
+    // imaginary number literal: `2i` | `1.5i`
+    let is_imaginary = cached_ch == 'i' && !chars.clone().next().is_some_and(is_identi_continue);
+    if is_imaginary {
+        return Ok(('\0', Number::complex(0.0, value.float_value())));
+    }
+    return Ok((cached_ch, value));
+}
+
+fn radix_resolver(chars: &mut Chars, radix: u32, index: &mut usize) -> Result<(char, Number), ()> {
+    let mut literal = String::new();
+    let mut cached_ch = '\0';
+
+    while let Some(ch) = chars.next() {
+        *index += 1;
+
+        if ch.is_ascii_alphanumeric() || ch == '_' {
+            literal.push(ch);
+        } else {
+            cached_ch = ch;
+            break;
         }
-        cached_ch = ch;
-        break;
     }
-    return (cached_ch, value);
+
+    let prefix = match radix {
+        16 => "0x",
+        2 => "0b",
+        _ => "0o",
+    };
+    let is_valid_separator =
+        !literal.starts_with('_') && !literal.ends_with('_') && !literal.contains("__");
+    let digits = literal.replace('_', "");
+
+    if digits.is_empty() || !is_valid_separator {
+        let msg = format!("invalid number literal `{}{}`", prefix, literal);
+        return Err(syntax_error(&msg)?);
+    }
+    if let Some(ch) = digits.chars().find(|ch| !ch.is_digit(radix)) {
+        let msg = format!(
+            "invalid digit '{}' in number literal `{}{}`",
+            ch, prefix, literal
+        );
+        return Err(syntax_error(&msg)?);
+    }
+    let value = BigInt::from_str_radix(&digits, radix).unwrap();
+    return Ok((cached_ch, Number::from_big(value)));
+}
+
+// resolve unit literals like: `km` | `m/s^2` | `kg*m/s^2`,
+// there should be no space inside the unit literal.
+fn unit_resolver(
+    chars: &mut Chars,
+    first_ch: char,
+    index: &mut usize,
+) -> Option<(char, CompoundUnit)> {
+    // unit term like: `m` | `s^2` | `s^-1`
+    fn term_resolver(source: &[char], start: usize) -> Option<(usize, String, i8)> {
+        let mut end = start;
+        while source.get(end).is_some_and(|ch| ch.is_ascii_alphabetic()) {
+            end += 1;
+        }
+        let symbol: String = source[start..end].iter().collect();
+        let is_identi_end = !source
+            .get(end)
+            .is_some_and(|ch| is_identi_continue(*ch) || *ch == '(');
+        if !is_identi_end || !CompoundUnit::is_unit(&symbol) {
+            return None;
+        }
+
+        // optional power part
+        let mut power_end = end + 1;
+        if source.get(end) == Some(&'^') {
+            if source.get(power_end) == Some(&'-') {
+                power_end += 1;
+            }
+            while source.get(power_end).is_some_and(|ch| ch.is_ascii_digit()) {
+                power_end += 1;
+            }
+            let power_str: String = source[end + 1..power_end].iter().collect();
+            if let Ok(power) = power_str.parse::<i8>() {
+                return Some((power_end, symbol, power));
+            }
+        }
+        Some((end, symbol, 1))
+    }
+
+    if first_ch == '\0' {
+        return None;
+    }
+    let source: Vec<char> = std::iter::once(first_ch).chain(chars.clone()).collect();
+
+    // spaces between the number and the unit
+    let mut position = 0;
+    while matches!(source.get(position), Some(' ' | '\t')) {
+        position += 1;
+    }
+
+    let (end, symbol, power) = term_resolver(&source, position)?;
+    let mut unit = CompoundUnit::default();
+    unit.push(&symbol, power);
+    position = end;
+
+    // following terms: `*m` | `/s`
+    loop {
+        let sign = match source.get(position) {
+            Some('*') => 1,
+            Some('/') => -1,
+            _ => break,
+        };
+        let Some((end, symbol, power)) = term_resolver(&source, position + 1) else {
+            break;
+        };
+        unit.push(&symbol, power.saturating_mul(sign));
+        position = end;
+    }
+
+    // the `first_ch` is already taken from `chars`
+    for _ in 1..position {
+        chars.next();
+        *index += 1;
+    }
+    let cached_ch = match chars.next() {
+        Some(ch) => {
+            *index += 1;
+            ch
+        }
+        None => '\0',
+    };
+    Some((cached_ch, unit))
+}
+
+// the token ends an operand, so that a word operator can follow it
+fn is_operand_end(token: Option<&Token>) -> bool {
+    matches!(
+        token,
+        Some(
+            Token::Number(_)
+                | Token::Quantity(_)
+                | Token::String(_)
+                | Token::FormatString(_, _)
+                | Token::Identi(_)
+                | Token::Paren(Paren::RightParen | Paren::RightBracket | Paren::RightBrace)
+        )
+    )
+}
+
+// a unit follows after spaces, like: ` km/h` of `x to km/h`
+fn is_unit_ahead(chars: &Chars, cached_ch: char) -> bool {
+    let mut rest = chars.clone();
+    let mut ch = cached_ch;
+    while ch == ' ' || ch == '\t' {
+        let Some(next_ch) = rest.next() else {
+            return false;
+        };
+        ch = next_ch;
+    }
+    is_identi_char(ch) && unit_resolver(&mut rest, ch, &mut 0).is_some()
 }
 
 fn identi_resolver(chars: &mut Chars, first_ch: char, index: &mut usize) -> (char, String) {
@@ -61,7 +264,7 @@ fn identi_resolver(chars: &mut Chars, first_ch: char, index: &mut usize) -> (cha
     while let Some(ch) = chars.next() {
         *index += 1;
 
-        if is_identi_ascii(ch) || ch.is_ascii_digit() {
+        if is_identi_continue(ch) {
             value.push(ch);
         } else {
             cached_ch = ch;
@@ -71,14 +274,134 @@ fn identi_resolver(chars: &mut Chars, first_ch: char, index: &mut usize) -> (cha
     return (cached_ch, value);
 }
 
+// resolve interpolated string literal like: `f"x = {x:.3}, {{escaped}}"`,
+// it is compiled to the template `"x = {0:.3}, {{escaped}}"` with the expression tokens.
+fn format_string_resolver(chars: &mut Chars, quote: char, index: &mut usize) -> Result<Token, ()> {
+    let mut template = String::new();
+    let mut expressions = Vec::<String>::new();
+
+    loop {
+        let Some(ch) = chars.next() else {
+            return Err(syntax_error("unclosed format string literal")?);
+        };
+        *index += 1;
+
+        match ch {
+            _ if ch == quote => break,
+            '\\' => match char_converter(chars, index)? {
+                brace @ ('{' | '}') => template.extend([brace, brace]),
+                converted => template.push(converted),
+            },
+            '{' | '}' if chars.clone().next() == Some(ch) => {
+                chars.next();
+                *index += 1;
+                template.extend([ch, ch]);
+            }
+            '}' => return Err(syntax_error("single '}' in format string literal")?),
+            '{' => {
+                // the expression ends with the outermost `:` or `}`
+                let mut expression = String::new();
+                let mut spec = None::<String>;
+                let mut depth = 0;
+                let mut quote = None::<char>;
+                loop {
+                    let Some(ch) = chars.next() else {
+                        return Err(syntax_error("unclosed '{' in format string literal")?);
+                    };
+                    *index += 1;
+
+                    if let Some(spec) = &mut spec {
+                        if ch == '}' {
+                            break;
+                        }
+                        spec.push(ch);
+                        continue;
+                    }
+                    match (ch, quote) {
+                        (_, Some(quote_ch)) if ch == quote_ch => quote = None,
+                        (_, Some(_)) => {}
+                        ('\'' | '\"', None) => quote = Some(ch),
+                        ('(' | '[' | '{', None) => depth += 1,
+                        (')' | ']', None) => depth -= 1,
+                        ('}', None) if depth == 0 => break,
+                        ('}', None) => depth -= 1,
+                        (':', None) if depth == 0 => {
+                            spec = Some(String::new());
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    expression.push(ch);
+                }
+
+                if expression.trim().is_empty() {
+                    return Err(syntax_error("empty expression in format string literal")?);
+                }
+                let field = match spec {
+                    Some(spec) => format!("{{{}:{}}}", expressions.len(), spec),
+                    None => format!("{{{}}}", expressions.len()),
+                };
+                template.push_str(&field);
+                expressions.push(expression);
+            }
+            _ => template.push(ch),
+        }
+    }
+
+    let mut expression_tokens = vec![];
+    for expression in expressions {
+        expression_tokens.push(tokenize(&expression)?);
+    }
+    Ok(Token::FormatString(template, expression_tokens))
+}
+
+// resolve string literals like:
+// `"text\n"` | `'text'` | `"""multi-line"""` | `r"raw\text"`,
+// the opening quote is already taken from `chars`.
+fn string_resolver(
+    chars: &mut Chars,
+    quote: char,
+    is_raw: bool,
+    index: &mut usize,
+) -> Result<String, ()> {
+    let mut rest = chars.clone();
+    let is_triple = rest.next() == Some(quote) && rest.next() == Some(quote);
+    if is_triple {
+        chars.nth(1);
+        *index += 2;
+    }
+
+    let mut value = String::new();
+    loop {
+        let Some(ch) = chars.next() else {
+            return Err(syntax_error("unclosed string literal")?);
+        };
+        *index += 1;
+
+        if ch == quote {
+            if !is_triple {
+                break;
+            }
+            let mut rest = chars.clone();
+            if rest.next() == Some(quote) && rest.next() == Some(quote) {
+                chars.nth(1);
+                *index += 2;
+                break;
+            }
+            value.push(ch);
+        } else if ch == '\\' && !is_raw {
+            value.push(char_converter(chars, index)?);
+        } else {
+            value.push(ch);
+        }
+    }
+    Ok(value)
+}
+
 pub fn tokenize(source: &String) -> Result<TokenVec, ()> {
-    // is used for check is number minus OR
-    // check is in annotation state.
+    // is used for check is in annotation state.
     let mut last_type = TokenType::Unknown;
 
-    // use to control if number is minus
-    let mut is_num_minus = false;
-
     let mut tokens = TokenVec::new();
 
     let mut chars = source.chars();
@@ -99,12 +422,14 @@ pub fn tokenize(source: &String) -> Result<TokenVec, ()> {
         // Number
         if ch.is_ascii_digit() {
             last_type = TokenType::Number;
-            let mut value: Number;
-            (cached_ch, value) = number_resolver(&mut chars, ch, &mut index);
+            let value: Number;
+            (cached_ch, value) = number_resolver(&mut chars, ch, &mut index)?;
 
-            if is_num_minus {
-                is_num_minus = false;
-                value = Number::Int(0) - value;
+            // quantity literal: `3 km` | `9.8 m/s^2`
+            if let Some((next_ch, unit)) = unit_resolver(&mut chars, cached_ch, &mut index) {
+                cached_ch = next_ch;
+                tokens.push_back(Token::Quantity(Quantity::new(value, unit)));
+                continue;
             }
 
             let current_token = Token::Number(value);
@@ -112,10 +437,39 @@ pub fn tokenize(source: &String) -> Result<TokenVec, ()> {
             continue;
         }
         // Identifier
-        if is_identi_ascii(ch) {
+        if is_identi_char(ch) {
+            // unit as the conversion target: `to km/h` | `in m/s^2`
+            if tokens.back() == Some(&Token::Symbol(Symbols::Convert)) {
+                if let Some((next_ch, unit)) = unit_resolver(&mut chars, ch, &mut index) {
+                    cached_ch = next_ch;
+                    last_type = TokenType::Number;
+                    let unit_quantity = Quantity::new(Number::Int(1), unit);
+                    tokens.push_back(Token::Quantity(unit_quantity));
+                    continue;
+                }
+            }
+
             let value: String;
             (cached_ch, value) = identi_resolver(&mut chars, ch, &mut index);
 
+            // interpolated string literal: `f"x = {x}"`
+            if value == "f" && matches!(cached_ch, '\'' | '\"') {
+                let quote = cached_ch;
+                cached_ch = '\0';
+                last_type = TokenType::String;
+                tokens.push_back(format_string_resolver(&mut chars, quote, &mut index)?);
+                continue;
+            }
+            // raw string literal: `r"C:\path"`
+            if value == "r" && matches!(cached_ch, '\'' | '\"') {
+                let quote = cached_ch;
+                cached_ch = '\0';
+                last_type = TokenType::String;
+                let value = string_resolver(&mut chars, quote, true, &mut index)?;
+                tokens.push_back(Token::String(value));
+                continue;
+            }
+
             if last_type == TokenType::Annotation {
                 // Type annotation
                 match ValueType::is_valid_type(&value) {
@@ -128,6 +482,17 @@ pub fn tokenize(source: &String) -> Result<TokenVec, ()> {
                         return Err(syntax_error(&msg)?);
                     }
                 }
+            } else if let Some(symbol) = Symbols::from_word(&value).filter(|symbol| match symbol {
+                // contextual word operators, which are identifiers elsewhere, like: `to = 5`
+                Symbols::BitAnd | Symbols::BitOr | Symbols::BitXor => is_operand_end(tokens.back()),
+                Symbols::Convert => {
+                    is_operand_end(tokens.back()) && is_unit_ahead(&chars, cached_ch)
+                }
+                _ => true,
+            }) {
+                // word formed operators: and | or | not | band | bor | bxor | to | in
+                last_type = TokenType::Symbol;
+                tokens.push_back(Token::Symbol(symbol));
             } else {
                 // check is keyword
                 match Keyword::is_keyword(&value) {
@@ -154,29 +519,36 @@ pub fn tokenize(source: &String) -> Result<TokenVec, ()> {
             }
 
             // Computing symbols
-            '+' => {
-                if last_type == TokenType::Symbol || last_type == TokenType::Unknown {
-                    is_num_minus = false;
-                } else {
-                    last_type = TokenType::Symbol;
-                    tokens.push_back(Token::Symbol(Symbols::Plus));
-                }
+            // prefix `+` and `-` are resolved by the analyzer
+            '+' | '-' => {
+                last_type = TokenType::Symbol;
+                tokens.push_back(Token::Symbol(Symbols::from(ch)));
             }
-            '-' => {
-                let last_token = tokens.back();
-                if last_type == TokenType::Unknown
-                    || last_type == TokenType::Symbol
-                    || last_token == Some(&Token::Paren(Paren::LeftParen))
-                    || last_token == Some(&Token::Paren(Paren::LeftBrace))
-                    || last_token == Some(&Token::Paren(Paren::LeftBracket))
-                {
-                    is_num_minus = true;
+            '/' | '<' | '>' => {
+                // doubled symbols: `//` | `<<` | `>>`,
+                // only if the second char follows immediately, like: `1 / / 2` is invalid.
+                let current_symbol = Symbols::from(ch);
+                let doubled_symbol = match current_symbol {
+                    Symbols::Divide => Symbols::FloorDivide,
+                    Symbols::LessThan => Symbols::LeftShift,
+                    Symbols::MoreThan => Symbols::RightShift,
+                    _ => unreachable!(),
+                };
+
+                if chars.clone().next() == Some(ch) {
+                    chars.next();
+                    index += 1;
+                    tokens.push_back(Token::Symbol(doubled_symbol));
+                } else if ch == '>' && tokens.back() == Some(&Token::Symbol(Symbols::Equal)) {
+                    // lazy-expression params arrow: `=>`
+                    tokens.pop_back();
+                    tokens.push_back(Token::Divider(Divider::Arrow));
                 } else {
-                    last_type = TokenType::Symbol;
-                    tokens.push_back(Token::Symbol(Symbols::Minus));
+                    tokens.push_back(Token::Symbol(current_symbol));
                 }
+                last_type = TokenType::Symbol;
             }
-            '*' | '/' | '%' | '^' | '!' | '<' | '>' | '&' | '|' => {
+            '*' | '%' | '^' | '!' | '~' | '&' | '|' => {
                 last_type = TokenType::Symbol;
                 tokens.push_back(Token::Symbol(Symbols::from(ch)));
             }
@@ -191,8 +563,8 @@ pub fn tokenize(source: &String) -> Result<TokenVec, ()> {
                 if let Token::Symbol(last_symbol) = last_token {
                     // if last_symbol
                     if Symbols::is_basic_symbol(last_symbol) {
-                        // if last char is: +  -  *  /  ^  !  >  <  =,
-                        // convert it to  : += -= *= /= ^= != >= <= ==.
+                        // if last char is: +  -  *  /  %  ^  !  >  <  =,
+                        // convert it to  : += -= *= /= %= ^= != >= <= ==.
                         let target_symbol = Symbols::Equal.combine(last_symbol)?;
                         tokens.push_back(Token::Symbol(target_symbol));
                         continue;
@@ -206,27 +578,7 @@ pub fn tokenize(source: &String) -> Result<TokenVec, ()> {
 
             // String literal
             '\'' | '\"' => {
-                // String token resolve
-                let mut value = String::new();
-                let mut is_escape_char = false;
-
-                while let Some(mut ch) = chars.next() {
-                    index += 1;
-
-                    if is_escape_char || (ch != '\'' && ch != '\"') {
-                        // if last char is '\', current is escape character.
-                        if is_escape_char {
-                            is_escape_char = false;
-                            ch = char_converter(ch)?;
-                        } else if ch == '\\' {
-                            is_escape_char = true;
-                            continue;
-                        }
-                        value.push(ch);
-                    } else {
-                        break;
-                    }
-                }
+                let value = string_resolver(&mut chars, ch, false, &mut index)?;
                 tokens.push_back(Token::String(value));
                 last_type = TokenType::String;
                 continue;
@@ -5,6 +5,7 @@ use crate::public::compile_time::dividers::Divider;
 use crate::public::compile_time::keywords::Keyword;
 use crate::public::compile_time::parens::Paren;
 use crate::public::value::number::Number;
+use crate::public::value::quantity::Quantity;
 use crate::public::value::symbols::Symbols;
 use crate::public::value::value::ValueType;
 
@@ -27,6 +28,7 @@ pub enum TokenType {
 #[derive(PartialEq, Clone)]
 pub enum Token {
     Number(Number),
+    Quantity(Quantity),
     String(String),
     Symbol(Symbols),
     Paren(Paren),
@@ -35,6 +37,9 @@ pub enum Token {
 
     Divider(Divider),
     Annotation(ValueType),
+
+    // interpolated string: template and tokens of the expressions
+    FormatString(String, Vec<TokenVec>),
 }
 pub type TokenVec = VecDeque<Token>;
 
@@ -42,6 +47,7 @@ impl fmt::Display for Token {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let token_content_display = match self {
             Self::Number(num) => format!("Number: {}", num),
+            Self::Quantity(quantity) => format!("Quantity: {}", quantity),
             Self::String(str) => format!("String: {}", str),
             Self::Symbol(sym) => format!("Symbol: {}", sym),
             Self::Paren(par) => format!("Paren: {:#?}", par),
@@ -49,6 +55,7 @@ impl fmt::Display for Token {
             Self::Keyword(key) => format!("Keywords: {}", key),
             Self::Divider(div) => format!("Divider: {:?}", div),
             Self::Annotation(type__) => format!("Annotation: {}", type__),
+            Self::FormatString(template, _) => format!("FormatString: {}", template),
         };
         write!(f, "Token({})", token_content_display)
     }
@@ -1,2 +1,2 @@
 pub mod computer;
-mod resolvers;
+pub mod resolvers;
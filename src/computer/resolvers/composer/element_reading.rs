@@ -37,10 +37,10 @@ fn middle_ware(
             arr_callback(arr_ref, index)
         }
         (Value::String(str), Value::Number(num)) => {
-            // string
+            // string, indexed by characters
             let str_ref = str.borrow_mut();
             let index = num.int_value() as usize;
-            check_outof_range(index, str_ref.len())?;
+            check_outof_range(index, str_ref.chars().count())?;
             str_callback(str_ref, index)
         }
         (Value::Map(map), Value::String(key)) => {
@@ -1,8 +1,15 @@
-use crate::public::compile_time::ast::ast_enum::ASTNode;
+use std::collections::HashMap;
+
+use crate::public::compile_time::ast::ast_enum::{ASTNode, ASTVec};
 use crate::public::compile_time::ast::types::{ExpressionNode, ModuleType};
 use crate::public::error::{internal_error, syntax_error, type_error, InternalComponent};
 use crate::public::run_time::scope::Scope;
+use crate::public::std::format_values;
+use crate::public::std::modules::matrix::MatrixModule;
+use crate::public::std::modules::poly::PolyModule;
+use crate::public::std::modules::time::TimeModule;
 use crate::public::value::into_rc_refcell;
+use crate::public::value::number::Number;
 use crate::public::value::symbols::Symbols;
 use crate::public::value::value::{Value, ValueType};
 
@@ -10,23 +17,103 @@ use super::operate::operate;
 use super::{array_literal, assignment, composer::compose, function_definition, instantiation};
 use super::{class_definition, map_literal};
 
+// for every `&` / `|` and comparison in the postfix sequence,
+// map the index where its right-hand operand starts
+// to the index of the operator itself.
+fn short_circuit_marks(elements: &ASTVec) -> HashMap<usize, usize> {
+    let mut marks = HashMap::<usize, usize>::new();
+
+    for (symbol_index, node) in elements.iter().enumerate() {
+        let ASTNode::SymbolLiteral(sym) = node else {
+            continue;
+        };
+        if *sym != Symbols::AndSign && *sym != Symbols::OrSign && !Symbols::is_compare_symbol(*sym)
+        {
+            continue;
+        }
+
+        // walk backward until exactly one operand is covered
+        let mut operand_needed = 1;
+        let mut index = symbol_index;
+        while operand_needed > 0 && index > 0 {
+            index -= 1;
+            match &elements[index] {
+                ASTNode::SymbolLiteral(sym) if Symbols::is_unary_symbol(*sym) => {}
+                ASTNode::SymbolLiteral(_) => operand_needed += 1,
+                _ => operand_needed -= 1,
+            }
+        }
+        if operand_needed == 0 {
+            marks.insert(index, symbol_index);
+        }
+    }
+    return marks;
+}
+
 pub fn resolve(node: &ExpressionNode, scope: &mut Scope) -> Result<Value, ()> {
     let elements = &node.elements;
     if elements.len() == 0 {
         return Ok(Value::EMPTY);
     }
 
+    let marks = short_circuit_marks(elements);
     let mut value_stack = Vec::<Value>::new();
 
-    for current_node in elements {
+    // for each value in value_stack, the right-hand operand
+    // of the comparison which produced it,
+    // used for chained comparisons like `a < b <= c`.
+    let mut compare_tails = Vec::<Option<Value>>::new();
+
+    let mut index = 0;
+    while index < elements.len() {
+        if let (Some(symbol_index), Some(left_value)) = (marks.get(&index), value_stack.last()) {
+            // the left-hand operand of `&` / `|` has been computed,
+            // skip the right-hand operand if it can not change the result.
+            // lazy-expression is excluded since it is composed by `operate`.
+            let ASTNode::SymbolLiteral(sym) = elements[*symbol_index] else {
+                unreachable!()
+            };
+            let is_chained = matches!(compare_tails.last(), Some(Some(_)));
+            let is_short_circuit = match (left_value, sym) {
+                (Value::LazyExpression(_), _) => false,
+                (_, Symbols::AndSign) => !left_value.get_bool(),
+                (_, Symbols::OrSign) => left_value.get_bool(),
+                // chained comparison with a false left-hand part
+                _ => is_chained && !left_value.get_bool(),
+            };
+            if is_short_circuit {
+                if !Symbols::is_compare_symbol(sym) {
+                    let left_value = value_stack.pop().unwrap();
+                    value_stack.push(Value::from(left_value.get_bool()));
+                    compare_tails.pop();
+                    compare_tails.push(None);
+                }
+                index = symbol_index + 1;
+                continue;
+            }
+        }
+
+        let current_node = &elements[index];
+        index += 1;
+
+        let mut current_tail = None;
+
         let current_value = match current_node {
             ASTNode::Expression(node) => resolve(node, scope)?,
 
             ASTNode::NumberLiteral(num) => Value::Number(num.clone()),
+            ASTNode::QuantityLiteral(quantity) => Value::Quantity(quantity.clone()),
             ASTNode::StringLiteral(str) => Value::from(str.clone()),
+            ASTNode::FormatString(node) => {
+                let mut args = vec![];
+                for arg in &node.args {
+                    args.push(resolve(arg, scope)?);
+                }
+                Value::from(format_values(&node.template, &args)?)
+            }
 
             ASTNode::LazyExpression(node) => {
-                Value::LazyExpression(into_rc_refcell(node.sub_sequence.clone()))
+                Value::LazyExpression(into_rc_refcell(node.as_ref().clone()))
             }
 
             ASTNode::ImportStatement(node) => {
@@ -40,25 +127,77 @@ pub fn resolve(node: &ExpressionNode, scope: &mut Scope) -> Result<Value, ()> {
             ASTNode::ClassDefinition(node) => Value::from(class_definition::resolve(node)?),
 
             ASTNode::SymbolLiteral(sym) => {
+                if Symbols::is_unary_symbol(*sym) {
+                    compare_tails.pop();
+                }
+
                 if *sym == Symbols::Not {
-                    // get last value and expected as Number | Boolean typed
+                    // get last value and convert it to Boolean
                     if let Some(val) = value_stack.pop() {
-                        if let Value::Number(num) = val {
-                            Value::Number(num.not())
-                        } else if let Value::Boolean(bool_val) = val {
-                            Value::from(!bool_val)
-                        } else {
-                            return Err(type_error(
-                                Some("Not operator"),
-                                vec![ValueType::Number],
-                                val.get_type(),
-                            )?);
-                        }
+                        Value::from(!val.get_bool())
                     } else {
                         return Err(syntax_error(
                             "operating number is missing for Not operator",
                         )?);
                     }
+                } else if *sym == Symbols::BitNot {
+                    // get last value and expected as Number typed
+                    match value_stack.pop() {
+                        Some(Value::Number(num)) => Value::Number((!num)?),
+                        Some(val) => {
+                            return Err(type_error(
+                                Some("BitNot operator"),
+                                vec![ValueType::Number],
+                                val.get_type(),
+                            )?)
+                        }
+                        None => {
+                            return Err(syntax_error(
+                                "operating number is missing for BitNot operator",
+                            )?)
+                        }
+                    }
+                } else if *sym == Symbols::Negate {
+                    // get last value and expected as Number typed
+                    match value_stack.pop() {
+                        Some(Value::Number(num)) => Value::Number(Number::Int(0) - num),
+                        Some(Value::Quantity(quantity)) => Value::Quantity(quantity.negate()),
+                        Some(val) if MatrixModule::is_matrix(&val) => MatrixModule::operate(
+                            &Value::Number(Number::Int(0)),
+                            &val,
+                            Symbols::Minus,
+                        )?,
+                        Some(val) if PolyModule::is_poly(&val) => PolyModule::operate(
+                            &Value::Number(Number::Int(0)),
+                            &val,
+                            Symbols::Minus,
+                        )?,
+                        Some(val) if TimeModule::is_time(&val) => TimeModule::negate(&val)?,
+                        Some(Value::LazyExpression(lazy_expr)) => {
+                            // compose as a new lazy-expression
+                            let mut new_lazy_expr = lazy_expr.borrow().clone();
+                            let ASTNode::Expression(expr_node) = &mut new_lazy_expr.sub_sequence
+                            else {
+                                unreachable!()
+                            };
+                            expr_node
+                                .elements
+                                .push(ASTNode::SymbolLiteral(Symbols::Negate));
+                            Value::from(new_lazy_expr)
+                        }
+                        Some(val) => {
+                            return Err(type_error(
+                                Some("Negate operator"),
+                                vec![ValueType::Number],
+                                val.get_type(),
+                            )?)
+                        }
+                        None => {
+                            return Err(syntax_error(
+                                "operating number is missing for Negate operator",
+                            )?)
+                        }
+                    }
                 } else {
                     if value_stack.len() < 2 {
                         // no enough value for operating
@@ -69,8 +208,26 @@ pub fn resolve(node: &ExpressionNode, scope: &mut Scope) -> Result<Value, ()> {
 
                     let num2 = value_stack.pop().unwrap();
                     let num1 = value_stack.pop().unwrap();
+                    compare_tails.pop();
+                    let num1_tail = compare_tails.pop().unwrap();
                     let current_symbol = *sym;
-                    operate(num1, num2, current_symbol)?
+
+                    if !Symbols::is_compare_symbol(current_symbol) {
+                        operate(num1, num2, current_symbol)?
+                    } else {
+                        let result = match (num1, num1_tail) {
+                            // `a < b <= c` is resolved as `a < b & b <= c`
+                            (Value::Boolean(false), Some(_)) => Value::from(false),
+                            (Value::Boolean(true), Some(last_right)) => {
+                                operate(last_right, num2.clone(), current_symbol)?
+                            }
+                            (num1, _) => operate(num1, num2.clone(), current_symbol)?,
+                        };
+                        if let Value::Boolean(_) = result {
+                            current_tail = Some(num2);
+                        }
+                        result
+                    }
                 }
             }
             ASTNode::ArrayLiteral(node) => Value::from(array_literal::resolve(node, scope)?),
@@ -89,6 +246,7 @@ pub fn resolve(node: &ExpressionNode, scope: &mut Scope) -> Result<Value, ()> {
             }
         };
         value_stack.push(current_value);
+        compare_tails.push(current_tail);
     }
     Ok(value_stack.remove(0))
 }
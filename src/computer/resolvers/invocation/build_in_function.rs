@@ -15,6 +15,15 @@ fn call(function: &BuildInFunction, scope: &mut Scope) -> Result<Value, ()> {
         BuildInFnIdenti::Map(map_fn) => map_fn.call(scope),
         BuildInFnIdenti::FileSystem(fs_fn) => fs_fn.call(scope),
         BuildInFnIdenti::BitOps(b_ops_fn) => b_ops_fn.call(scope),
+        BuildInFnIdenti::Matrix(matrix_fn) => matrix_fn.call(scope),
+        BuildInFnIdenti::Stats(stats_fn) => stats_fn.call(scope),
+        BuildInFnIdenti::Random(random_fn) => random_fn.call(scope),
+        BuildInFnIdenti::Poly(poly_fn) => poly_fn.call(scope),
+        BuildInFnIdenti::Regex(regex_fn) => regex_fn.call(scope),
+        BuildInFnIdenti::Json(json_fn) => json_fn.call(scope),
+        BuildInFnIdenti::Csv(csv_fn) => csv_fn.call(scope),
+        BuildInFnIdenti::Time(time_fn) => time_fn.call(scope),
+        BuildInFnIdenti::Set(set_fn) => set_fn.call(scope),
     }
 }
 
@@ -44,3 +53,20 @@ pub fn invoke(
 
     Ok(fn_result)
 }
+
+pub fn value_invoke(
+    function: &BuildInFunction,
+    values: Vec<Value>,
+    scope: &mut Scope,
+) -> Result<Value, ()> {
+    let mut local_scope = LocalScope::init();
+    Function::param_bind(&function.params, values, &mut local_scope)?;
+
+    let mut local_scope_cached = scope.local.take();
+    scope.local = Some(local_scope);
+    let fn_result = call(function, scope);
+    // restore the caller scope even if calling failed
+    scope.local = local_scope_cached.take();
+
+    fn_result
+}
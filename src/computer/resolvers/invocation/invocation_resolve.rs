@@ -24,7 +24,7 @@ fn function_invoke(
     scope: &mut Scope,
 ) -> Result<Value, ()> {
     let invoke_result = match function_value {
-        Value::LazyExpression(le) => lazy_expression::invoke(&*le.borrow(), scope)?,
+        Value::LazyExpression(le) => lazy_expression::invoke(&le.borrow(), params, scope)?,
         Value::Function(fn_enum) => match fn_enum {
             Function::BuildIn(build_in_fn) => {
                 build_in_function::invoke(&build_in_fn.as_ref().borrow(), params, scope)?
@@ -44,6 +44,32 @@ fn function_invoke(
     Ok(invoke_result)
 }
 
+// call a function value with computed values,
+// used by build-in functions which accept callbacks.
+pub fn value_invoke(
+    function_value: &Value,
+    values: Vec<Value>,
+    scope: &mut Scope,
+) -> Result<Value, ()> {
+    let invoke_result = match function_value {
+        Value::LazyExpression(le) => lazy_expression::value_invoke(&le.borrow(), values, scope)?,
+        Value::Function(Function::BuildIn(build_in_fn)) => {
+            build_in_function::value_invoke(&build_in_fn.as_ref().borrow(), values, scope)?
+        }
+        Value::Function(Function::UserDefined(user_defined_fn)) => {
+            user_defined_function::value_invoke(&user_defined_fn.as_ref().borrow(), values, scope)?
+        }
+        _ => {
+            return Err(type_error(
+                None,
+                vec![ValueType::Function, ValueType::LazyExpression],
+                function_value.get_type(),
+            )?)
+        }
+    };
+    Ok(invoke_result)
+}
+
 pub fn resolve(node: &InvocationNode, scope: &mut Scope) -> Result<Value, ()> {
     let params = &node.params;
 
@@ -1,10 +1,119 @@
-use crate::computer::resolvers::sequence;
-use crate::public::compile_time::ast::ast_enum::ASTNode;
-use crate::public::run_time::scope::Scope;
+use crate::computer::resolvers::{expression, sequence};
+use crate::public::compile_time::ast::types::{ExpressionNode, LazyExpressionNode};
+use crate::public::error::{range_error, reference_error, ReferenceType};
+use crate::public::run_time::scope::{LocalScope, Scope};
 use crate::public::value::value::Value;
 
-pub fn invoke(le_body: &ASTNode, scope: &mut Scope) -> Result<Value, ()> {
+// the variable bound when a lazy-expression without params is called with value
+const LAZY_EXPR_PARAM: &str = "x";
+
+pub fn invoke(
+    le: &LazyExpressionNode,
+    params: &Vec<ExpressionNode>,
+    scope: &mut Scope,
+) -> Result<Value, ()> {
     // le -> lazy_expression
-    let result = sequence::resolve(le_body, scope)?;
-    return Ok(result);
+    if le.params.is_empty() && params.is_empty() {
+        let result = sequence::resolve(&le.sub_sequence, scope)?;
+        return Ok(result);
+    }
+
+    let mut values = Vec::<Value>::new();
+    for param in params {
+        values.push(expression::resolve(param, scope)?);
+    }
+    return call(le, values, true, scope);
+}
+
+// the bindings map is spread only for the direct invocation,
+// the Map values from callbacks are bound as `x`.
+fn bind(
+    le: &LazyExpressionNode,
+    values: Vec<Value>,
+    spread_bindings: bool,
+    local_scope: &mut LocalScope,
+) -> Result<(), ()> {
+    if le.params.is_empty() {
+        if values.len() > 1 {
+            return Err(range_error(
+                "lazy-expression invocation",
+                "at most 1",
+                values.len(),
+            )?);
+        }
+        match values.into_iter().next() {
+            // bindings map, like: `expr({x: 1; y: 2})`
+            Some(Value::Map(bindings)) if spread_bindings => {
+                for (name, value) in bindings.borrow().iter() {
+                    local_scope.variables.insert(name.clone(), value.clone());
+                }
+            }
+            Some(value) => {
+                local_scope
+                    .variables
+                    .insert(String::from(LAZY_EXPR_PARAM), value);
+            }
+            None => {}
+        }
+        return Ok(());
+    }
+
+    // bindings map for the declared params, like: `f({x: 2; y: 3})` with `f = {x, y => x + y}`
+    if let [Value::Map(bindings)] = values.as_slice() {
+        if spread_bindings && le.params.len() > 1 {
+            let bindings = bindings.borrow();
+            for param in &le.params {
+                let Some(value) = bindings.get(param) else {
+                    return Err(reference_error(ReferenceType::Property, param)?);
+                };
+                local_scope.variables.insert(param.clone(), value);
+            }
+            return Ok(());
+        }
+    }
+
+    if values.len() != le.params.len() {
+        return Err(range_error(
+            "lazy-expression invocation",
+            le.params.len(),
+            values.len(),
+        )?);
+    }
+    for (param, value) in le.params.iter().zip(values) {
+        local_scope.variables.insert(param.clone(), value);
+    }
+    Ok(())
+}
+
+pub fn value_invoke(
+    le: &LazyExpressionNode,
+    values: Vec<Value>,
+    scope: &mut Scope,
+) -> Result<Value, ()> {
+    return call(le, values, false, scope);
+}
+
+fn call(
+    le: &LazyExpressionNode,
+    values: Vec<Value>,
+    spread_bindings: bool,
+    scope: &mut Scope,
+) -> Result<Value, ()> {
+    // computed in a temporary local scope, like: `{x ^ 2}` with `x = 3`,
+    // variables of the caller are still visible.
+    let mut local_scope = match &scope.local {
+        Some(caller_scope) => LocalScope {
+            variables: caller_scope.variables.clone(),
+        },
+        None => LocalScope::init(),
+    };
+    bind(le, values, spread_bindings, &mut local_scope)?;
+
+    let mut local_scope_cached = scope.local.take();
+    scope.local = Some(local_scope);
+    let result = sequence::resolve(&le.sub_sequence, scope);
+    // restore the caller scope even if computing failed
+    scope.local = local_scope_cached.take();
+
+    result
 }
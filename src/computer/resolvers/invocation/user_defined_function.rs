@@ -42,3 +42,20 @@ pub fn invoke(
 
     Ok(fn_result)
 }
+
+pub fn value_invoke(
+    function: &UserDefinedFunction,
+    values: Vec<Value>,
+    scope: &mut Scope,
+) -> Result<Value, ()> {
+    let mut local_scope = LocalScope::init();
+    Function::param_bind(&function.params, values, &mut local_scope)?;
+
+    let mut local_scope_cached = scope.local.take();
+    scope.local = Some(local_scope);
+    let fn_result = call(function, scope);
+    // restore the caller scope even if calling failed
+    scope.local = local_scope_cached.take();
+
+    fn_result
+}
@@ -12,6 +12,6 @@ mod instantiation;
 mod map_literal;
 
 mod composer;
-mod invocation;
+pub mod invocation;
 
 mod operate;
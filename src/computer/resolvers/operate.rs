@@ -1,6 +1,10 @@
 use crate::public::compile_time::ast::ast_enum::ASTNode;
-use crate::public::error::{internal_error, syntax_error, InternalComponent};
+use crate::public::error::{internal_error, math_error, syntax_error, InternalComponent};
+use crate::public::std::modules::matrix::MatrixModule;
+use crate::public::std::modules::poly::PolyModule;
+use crate::public::std::modules::time::TimeModule;
 use crate::public::value::number::Number;
+use crate::public::value::quantity::Quantity;
 use crate::public::value::symbols::Symbols;
 use crate::public::value::value::Value;
 
@@ -8,8 +12,17 @@ pub fn operate(val1: Value, val2: Value, operator: Symbols) -> Result<Value, ()>
     let result = match (&val1, &val2, operator) {
         (Value::Number(num1_ref), Value::Number(num2_ref), _) => {
             // number computing and comparing
-            let num1 = *num1_ref;
-            let num2 = *num2_ref;
+            let num1 = num1_ref.clone();
+            let num2 = num2_ref.clone();
+
+            let is_ordering = Symbols::is_compare_symbol(operator)
+                && operator != Symbols::CompareEqual
+                && operator != Symbols::NotEqual;
+            if is_ordering
+                && (matches!(num1, Number::Complex(_, _)) || matches!(num2, Number::Complex(_, _)))
+            {
+                return Err(math_error("complex numbers are unordered")?);
+            }
 
             match operator {
                 Symbols::Plus => Value::Number(num1 + num2),
@@ -17,21 +30,56 @@ pub fn operate(val1: Value, val2: Value, operator: Symbols) -> Result<Value, ()>
                 Symbols::Multiply => Value::Number(num1 * num2),
                 Symbols::Divide => Value::Number(num1 / num2),
                 Symbols::Mod => Value::Number(Number::modulo(num1, num2)),
+                Symbols::FloorDivide => Value::Number(Number::floor_div(num1, num2)),
                 Symbols::Power => Value::Number(Number::pow(num1, num2)),
+                Symbols::BitAnd => Value::Number((num1 & num2)?),
+                Symbols::BitOr => Value::Number((num1 | num2)?),
+                Symbols::BitXor => Value::Number((num1 ^ num2)?),
+                Symbols::LeftShift => Value::Number((num1 << num2)?),
+                Symbols::RightShift => Value::Number((num1 >> num2)?),
                 Symbols::LessThan => Value::from(num1 < num2),
                 Symbols::MoreThan => Value::from(num1 > num2),
                 Symbols::NotEqual => Value::from(num1 != num2),
                 Symbols::CompareEqual => Value::from(num1 == num2),
                 Symbols::LessThanEqual => Value::from(num1 <= num2),
                 Symbols::MoreThanEqual => Value::from(num1 >= num2),
-                Symbols::AndSign => Value::from(num1.int_value() != 0 && num2.int_value() != 0),
-                Symbols::OrSign => Value::from(num1.int_value() != 0 || num2.int_value() != 0),
+                Symbols::AndSign => Value::from(val1.get_bool() && val2.get_bool()),
+                Symbols::OrSign => Value::from(val1.get_bool() || val2.get_bool()),
+                Symbols::Convert => {
+                    return Err(math_error("the conversion target should be a unit")?);
+                }
                 _ => {
                     let msg = format!("unexpected symbol `{}` for operating", operator);
                     return Err(internal_error(InternalComponent::Computer, &msg)?);
                 }
             }
         }
+        (Value::Quantity(_), Value::Quantity(_) | Value::Number(_), _)
+        | (Value::Number(_), Value::Quantity(_), _) => {
+            // quantity computing with dimension checking
+            Quantity::operate(&val1, &val2, operator)?
+        }
+        (Value::Object(_), Value::Object(_) | Value::Number(_), _)
+        | (Value::Number(_), Value::Object(_), _)
+            if MatrixModule::is_matrix(&val1) || MatrixModule::is_matrix(&val2) =>
+        {
+            // element-wise matrix computing
+            MatrixModule::operate(&val1, &val2, operator)?
+        }
+        (Value::Object(_), Value::Object(_) | Value::Number(_), _)
+        | (Value::Number(_), Value::Object(_), _)
+            if PolyModule::is_poly(&val1) || PolyModule::is_poly(&val2) =>
+        {
+            // polynomial computing
+            PolyModule::operate(&val1, &val2, operator)?
+        }
+        (Value::Object(_), Value::Object(_) | Value::Number(_), _)
+        | (Value::Number(_), Value::Object(_), _)
+            if TimeModule::is_time(&val1) || TimeModule::is_time(&val2) =>
+        {
+            // date-time and duration computing
+            TimeModule::operate(&val1, &val2, operator)?
+        }
         (Value::String(str_ref), _, Symbols::Plus) => {
             // stringify computing
             let mut str_cloned = str_ref.borrow().clone();
@@ -41,7 +89,7 @@ pub fn operate(val1: Value, val2: Value, operator: Symbols) -> Result<Value, ()>
         }
         (
             Value::LazyExpression(lazy_expr),
-            Value::Number(_) | Value::String(_) | Value::LazyExpression(_),
+            Value::Number(_) | Value::Quantity(_) | Value::String(_) | Value::LazyExpression(_),
             _,
         ) => {
             // lazy expression computing
@@ -67,7 +115,7 @@ pub fn operate(val1: Value, val2: Value, operator: Symbols) -> Result<Value, ()>
             //     },
             // )
             let mut new_lazy_expr = lazy_expr.borrow().clone();
-            let ASTNode::Expression(expr_node) = &mut new_lazy_expr else {
+            let ASTNode::Expression(expr_node) = &mut new_lazy_expr.sub_sequence else {
                 unreachable!()
             };
             // push added value and Symbols
@@ -77,14 +125,24 @@ pub fn operate(val1: Value, val2: Value, operator: Symbols) -> Result<Value, ()>
                 Value::Number(num) => {
                     expr_elements.push(ASTNode::NumberLiteral(num));
                 }
+                Value::Quantity(quantity) => {
+                    expr_elements.push(ASTNode::QuantityLiteral(quantity));
+                }
                 Value::String(str) => {
                     let cloned = str.borrow().clone();
                     expr_elements.push(ASTNode::StringLiteral(cloned));
                 }
                 Value::LazyExpression(other_lexpr) => {
-                    let ASTNode::Expression(expr_node) = &*other_lexpr.borrow() else {
+                    let other_lexpr_ref = other_lexpr.borrow();
+                    let ASTNode::Expression(expr_node) = &other_lexpr_ref.sub_sequence else {
                         unreachable!()
                     };
+                    // composed lazy-expression takes params of both sides
+                    for param in &other_lexpr_ref.params {
+                        if !new_lazy_expr.params.contains(param) {
+                            new_lazy_expr.params.push(param.clone());
+                        }
+                    }
                     let cloned_elements = expr_node.elements.clone();
                     expr_elements.extend(cloned_elements.into_iter());
                 }
@@ -41,6 +41,14 @@ fn args_resolve(
                     calc_env.version_output();
                     process::exit(0);
                 }
+                CommandArg::Seed => {
+                    let seed_arg = args.pop_front().unwrap_or_default();
+                    let Ok(seed) = seed_arg.parse::<i64>() else {
+                        println!("Invalid seed: {}.", seed_arg);
+                        process::exit(0);
+                    };
+                    unsafe { ENV_OPTION.seed = Some(seed as u64) }
+                }
                 CommandArg::Headfile => {
                     // remaining args as headfile
                     calc_env.headfiles = args.clone();
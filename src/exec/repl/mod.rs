@@ -7,7 +7,7 @@ use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 
 use super::attempt::attempt;
 use crate::public::env::{Env, ENV_OPTION};
-use crate::public::error::{import_error, syntax_error};
+use crate::public::error::import_error;
 use crate::public::run_time::scope::Scope;
 use crate::public::value::value::Value;
 use crate::utils::completer::Completer;
@@ -24,6 +24,15 @@ fn import_all(scope: &mut Scope) -> Result<(), ()> {
     scope.import_std("Map")?;
     scope.import_std("FS")?;
     scope.import_std("BitOps")?;
+    scope.import_std("Matrix")?;
+    scope.import_std("Stats")?;
+    scope.import_std("Random")?;
+    scope.import_std("Poly")?;
+    scope.import_std("Regex")?;
+    scope.import_std("JSON")?;
+    scope.import_std("CSV")?;
+    scope.import_std("Time")?;
+    scope.import_std("Set")?;
     Ok(())
 }
 
@@ -62,10 +71,6 @@ pub fn repl(scope: &mut Scope, calc_env: Env) -> io::Result<()> {
         let line_content = match sig {
             Signal::NewLine(line) => line,
             Signal::Interrupt => break,
-            Signal::NonASCII => {
-                syntax_error("non-ASCII character").unwrap_err();
-                continue;
-            }
         };
 
         let result: Result<Value, ()>;
@@ -4,6 +4,7 @@ pub mod readlines;
 use std::time::Instant;
 
 use crate::public::env::{Env, ENV_OPTION};
+use crate::public::error::syntax_error;
 use crate::public::run_time::scope::Scope;
 
 use super::attempt::attempt;
@@ -32,11 +33,28 @@ pub fn run(path: &str, scope: &mut Scope) {
     let mut cached_multiline = String::new();
     let mut line_count = 0;
     let mut brace_count = 0;
+    // the unclosed multi-line string literal
+    let mut cached_string = String::new();
+    let mut open_string = None;
+    let mut string_line = 0;
 
     while let Some(Ok(current_line)) = script_lines.next() {
-        let mut current_line = pre_processer::process(current_line);
+        let mut current_line = pre_processer::process(current_line, &mut open_string);
         line_count += 1;
 
+        if open_string.is_some() {
+            if cached_string.is_empty() {
+                string_line = line_count;
+            }
+            cached_string.push_str(&current_line);
+            cached_string.push('\n');
+            continue;
+        }
+        if !cached_string.is_empty() {
+            cached_string.push_str(&current_line);
+            current_line = std::mem::take(&mut cached_string);
+        }
+
         // skip blank line
         if current_line.is_empty() {
             continue;
@@ -76,4 +94,9 @@ pub fn run(path: &str, scope: &mut Scope) {
             _ => unreachable!(),
         }
     }
+
+    if open_string.is_some() {
+        syntax_error("unclosed multi-line string literal").unwrap_err();
+        println!("Error occured at line {}.", string_line);
+    }
 }
@@ -1,18 +1,78 @@
+use crate::utils::unicode::is_identi_continue;
+
 enum State {
     Indent,
     Code,
 }
 
-pub fn process(source: String) -> String {
+// the string literal which is not closed at the line end,
+// only triple-quoted string literals can be continued to the next line.
+#[derive(Clone, Copy)]
+pub struct OpenString {
+    quote: char,
+    is_triple: bool,
+    is_raw: bool,
+}
+
+pub fn process(source: String, open_string: &mut Option<OpenString>) -> String {
     let mut result = String::new();
-    let mut state = State::Indent;
+    // the indent of the continued multi-line string is kept
+    let mut state = match open_string {
+        Some(_) => State::Code,
+        None => State::Indent,
+    };
+    let mut string = open_string.take();
+
+    let chars: Vec<char> = source.chars().collect();
+    let is_tripled = |index: usize, quote: char| {
+        chars.get(index + 1) == Some(&quote) && chars.get(index + 2) == Some(&quote)
+    };
+
+    let mut index = 0;
+    while index < chars.len() {
+        let ch = chars[index];
+        index += 1;
+
+        if let Some(current) = string {
+            result.push(ch);
+            if ch == '\\' && !current.is_raw {
+                // escaped character
+                if let Some(escaped) = chars.get(index) {
+                    result.push(*escaped);
+                    index += 1;
+                }
+            } else if ch == current.quote && !current.is_triple {
+                string = None;
+            } else if ch == current.quote && is_tripled(index - 1, ch) {
+                result.extend([ch, ch]);
+                index += 2;
+                string = None;
+            }
+            continue;
+        }
 
-    for ch in source.chars() {
         if ch == '#' {
             // avoid comments
             break;
         }
 
+        if ch == '\'' || ch == '\"' {
+            // raw string literal: `r"..."`
+            let mut prefix = result.chars().rev();
+            let is_raw =
+                prefix.next() == Some('r') && !prefix.next().is_some_and(is_identi_continue);
+            let is_triple = is_tripled(index - 1, ch);
+            if is_triple {
+                result.extend([ch, ch]);
+                index += 2;
+            }
+            string = Some(OpenString {
+                quote: ch,
+                is_triple,
+                is_raw,
+            });
+        }
+
         match state {
             State::Indent => {
                 if !(ch == ' ' || ch == '\t') {
@@ -25,6 +85,14 @@ pub fn process(source: String) -> String {
         }
     }
 
+    if let Some(current) = string {
+        if current.is_triple {
+            // the whitespaces at the end belong to the string
+            *open_string = Some(current);
+            return result;
+        }
+    }
+
     // remove the WhiteSpaces and Tabs at the peak of result
     let mut white_space_count = 0;
     for ch in result.chars().rev() {
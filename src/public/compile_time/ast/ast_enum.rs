@@ -1,11 +1,11 @@
 use core::fmt;
 
-use crate::public::value::{number::Number, symbols::Symbols};
+use crate::public::value::{number::Number, quantity::Quantity, symbols::Symbols};
 
 use super::types::{
     ArrayLiteralNode, AssignmentNode, ClassDefinitionNode, ElementReadingNode, ExpressionNode,
-    FunctionDefinitionNode, ImportNode, InstantiationNode, InvocationNode, LazyExpressionNode,
-    MapLiteralNode, ObjectReadingNode, StatementNode, VariableNode,
+    FormatStringNode, FunctionDefinitionNode, ImportNode, InstantiationNode, InvocationNode,
+    LazyExpressionNode, MapLiteralNode, ObjectReadingNode, StatementNode, VariableNode,
 };
 
 #[cfg_attr(debug_assertions, derive(Debug))]
@@ -19,8 +19,10 @@ pub enum ASTNode {
     Comment,
 
     NumberLiteral(Number),
+    QuantityLiteral(Quantity),
     StringLiteral(String),
     SymbolLiteral(Symbols),
+    FormatString(Box<FormatStringNode>),
 
     Variable(Box<VariableNode>),
     Assignment(Box<AssignmentNode>),
@@ -47,8 +49,10 @@ impl fmt::Display for ASTNode {
         let content = match self {
             Self::Comment => "Comment",
             Self::NumberLiteral(_) => "NumberLiteral",
+            Self::QuantityLiteral(_) => "QuantityLiteral",
             Self::StringLiteral(_) => "StringLiteral",
             Self::SymbolLiteral(_) => "SymbolLiteral",
+            Self::FormatString(_) => "FormatString",
             Self::Variable(_) => "Variable",
             Self::Assignment(_) => "Assignment",
             Self::ArrayLiteral(_) => "ArrayLiteral",
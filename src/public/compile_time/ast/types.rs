@@ -40,7 +40,14 @@ pub struct ExpressionNode {
 }
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[derive(PartialEq, Clone)]
+pub struct FormatStringNode {
+    pub template: String,
+    pub args: Vec<ExpressionNode>,
+}
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(PartialEq, Clone)]
 pub struct LazyExpressionNode {
+    pub params: Vec<String>,
     pub sub_sequence: ASTNode,
 }
 #[cfg_attr(debug_assertions, derive(Debug))]
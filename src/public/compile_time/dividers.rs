@@ -3,6 +3,7 @@ pub enum Divider {
     Comma,     // ','
     Colon,     // ':'
     Semicolon, // ';'
+    Arrow,     // '=>'
 }
 
 impl From<char> for Divider {
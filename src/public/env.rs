@@ -6,12 +6,14 @@ pub struct EnvOption {
     pub timer: bool,
     pub is_repl: bool,
     pub support_ansi: bool,
+    pub seed: Option<u64>,
 }
 
 pub static mut ENV_OPTION: EnvOption = EnvOption {
     timer: false,
     is_repl: false,
     support_ansi: false,
+    seed: None,
 };
 
 // --- --- --- --- --- ---
@@ -12,4 +12,12 @@ use value::value::ValueType;
 pub trait Param {
     fn type__(&self) -> ValueType;
     fn identi(&self) -> &str;
+    // optional param is bound as `Value::EMPTY` when missing
+    fn is_optional(&self) -> bool {
+        false
+    }
+    // variadic param collects the rest actual params as an Array
+    fn is_variadic(&self) -> bool {
+        false
+    }
 }
@@ -1,6 +1,8 @@
 use crate::public::std::modules::{
-    array::ArrayModule, basic::BasicModule, bit_ops::BitOpsModule, file_system::FileSysModule,
-    map::MapModule, math::MathModule, string::StringModule,
+    array::ArrayModule, basic::BasicModule, bit_ops::BitOpsModule, csv::CsvModule,
+    file_system::FileSysModule, json::JsonModule, map::MapModule, math::MathModule,
+    matrix::MatrixModule, poly::PolyModule, random::RandomModule, regex::RegexModule,
+    set::SetModule, stats::StatsModule, string::StringModule, time::TimeModule,
 };
 
 #[derive(PartialEq, Clone)]
@@ -12,4 +14,13 @@ pub enum BuildInFnIdenti {
     Map(MapModule),
     FileSystem(FileSysModule),
     BitOps(BitOpsModule),
+    Matrix(MatrixModule),
+    Stats(StatsModule),
+    Random(RandomModule),
+    Poly(PolyModule),
+    Regex(RegexModule),
+    Json(JsonModule),
+    Csv(CsvModule),
+    Time(TimeModule),
+    Set(SetModule),
 }
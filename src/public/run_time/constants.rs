@@ -14,10 +14,12 @@ pub static mut IS_INITED: bool = false;
 pub static mut VOID_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
 pub static mut BOOL_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
 pub static mut NUMBER_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
+pub static mut QUANTITY_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
 pub static mut UNIQUE_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
 pub static mut STRING_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
 pub static mut ARRAY_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
 pub static mut MAP_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
+pub static mut SET_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
 pub static mut LAZYEXPR_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
 pub static mut FUNCTION_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
 pub static mut CLASS_T: GlobalUnique = EMPTY_GLOBAL_UNIQUE;
@@ -29,10 +31,12 @@ unsafe fn static_init() {
     VOID_T.init("Void-Type");
     BOOL_T.init("Boolean-Type");
     NUMBER_T.init("Number-Type");
+    QUANTITY_T.init("Quantity-Type");
     UNIQUE_T.init("Number-Type");
     STRING_T.init("String-Type");
     ARRAY_T.init("Array-Type");
     MAP_T.init("Map-Type");
+    SET_T.init("Set-Type");
     LAZYEXPR_T.init("Lazy-Expression-Type");
     FUNCTION_T.init("Function-Type");
     CLASS_T.init("Class-Type");
@@ -48,10 +52,12 @@ pub unsafe fn entry() -> HashMap<String, Value> {
         (String::from("VOID"), Value::from(VOID_T.unwrap())),
         (String::from("BOOLEAN"), Value::from(BOOL_T.unwrap())),
         (String::from("NUMBER"), Value::from(NUMBER_T.unwrap())),
+        (String::from("QUANTITY"), Value::from(QUANTITY_T.unwrap())),
         (String::from("UNIQUE"), Value::from(UNIQUE_T.unwrap())),
         (String::from("STRING"), Value::from(STRING_T.unwrap())),
         (String::from("ARRAY"), Value::from(ARRAY_T.unwrap())),
         (String::from("MAP"), Value::from(MAP_T.unwrap())),
+        (String::from("SET"), Value::from(SET_T.unwrap())),
         (String::from("LAZYEXPR"), Value::from(LAZYEXPR_T.unwrap())),
         (String::from("FUNCION"), Value::from(FUNCTION_T.unwrap())),
         (String::from("CLASS"), Value::from(CLASS_T.unwrap())),
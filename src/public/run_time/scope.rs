@@ -35,7 +35,7 @@ impl LocalScope {
 
 // --- --- --- --- --- ---
 
-const STD_MODULE_COUNT: usize = 7;
+const STD_MODULE_COUNT: usize = 16;
 const STD_MODULE_DATA: [(&'static str, StdModules); STD_MODULE_COUNT] = [
     ("Basic", StdModules::Basic),
     ("Math", StdModules::Math),
@@ -44,6 +44,15 @@ const STD_MODULE_DATA: [(&'static str, StdModules); STD_MODULE_COUNT] = [
     ("Map", StdModules::Map),
     ("FS", StdModules::FileSystem),
     ("BitOps", StdModules::BitOps),
+    ("Matrix", StdModules::Matrix),
+    ("Stats", StdModules::Stats),
+    ("Random", StdModules::Random),
+    ("Poly", StdModules::Poly),
+    ("Regex", StdModules::Regex),
+    ("JSON", StdModules::Json),
+    ("CSV", StdModules::Csv),
+    ("Time", StdModules::Time),
+    ("Set", StdModules::Set),
 ];
 pub struct Scope {
     pub global: GlobalScope,
@@ -1,9 +1,11 @@
 use std::rc::Rc;
 
 use self::modules::{
-    array::ArrayModule, basic::BasicModule, bit_ops::BitOpsModule, file_system::FileSysModule,
-    map::MapModule, math::MathModule, string::StringModule, ClassModule, FunctionModule,
-    ObjectModule,
+    array::ArrayModule, basic::BasicModule, bit_ops::BitOpsModule, csv::CsvModule,
+    file_system::FileSysModule, json::JsonModule, map::MapModule, math::MathModule,
+    matrix::MatrixModule, poly::PolyModule, random::RandomModule, regex::RegexModule,
+    set::SetModule, stats::StatsModule, string::StringModule, time::TimeModule, ClassModule,
+    FunctionModule, ObjectModule,
 };
 use super::value::{
     oop::{class::Class, object::Object},
@@ -14,6 +16,8 @@ pub mod modules;
 
 mod utils;
 
+pub use self::utils::formatter::format_values;
+
 #[derive(PartialEq, Clone, Copy)]
 pub enum StdModules {
     Basic,
@@ -23,6 +27,15 @@ pub enum StdModules {
     Map,
     FileSystem,
     BitOps,
+    Matrix,
+    Stats,
+    Random,
+    Poly,
+    Regex,
+    Json,
+    Csv,
+    Time,
+    Set,
 }
 
 impl StdModules {
@@ -38,6 +51,12 @@ impl StdModules {
         match self {
             StdModules::Math => MathModule::module_object(),
             StdModules::FileSystem => FileSysModule::module_object(),
+            StdModules::Matrix => MatrixModule::module_object(),
+            StdModules::Stats => StatsModule::module_object(),
+            StdModules::Random => RandomModule::module_object(),
+            StdModules::Json => JsonModule::module_object(),
+            StdModules::Csv => CsvModule::module_object(),
+            StdModules::Time => TimeModule::module_object(),
             _ => unreachable!(),
         }
     }
@@ -47,6 +66,9 @@ impl StdModules {
             StdModules::Array => ArrayModule::module_class(),
             StdModules::String => StringModule::module_class(),
             StdModules::Map => MapModule::module_class(),
+            StdModules::Poly => PolyModule::module_class(),
+            StdModules::Regex => RegexModule::module_class(),
+            StdModules::Set => SetModule::module_class(),
             _ => unreachable!(),
         }
     }
@@ -1,13 +1,19 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::rc::Rc;
 
+use crate::computer::resolvers::invocation::invocation_resolve::value_invoke;
+use crate::public::error::{math_error, type_error};
 use crate::public::run_time::build_in::BuildInFnIdenti;
 use crate::public::run_time::scope::Scope;
 use crate::public::std::{ModuleClass, EMPTY_MODULE_CLASS};
 use crate::public::std::utils::get_self_prop::get_self_prop;
+use crate::public::value::array::{ArrayLiteral, RawArray};
 use crate::public::value::function::{BuildInFnParam, BuildInFunction, Function};
 use crate::public::value::oop::class::{Class, Property};
 use crate::public::value::value::{Value, ValueType};
 
+use super::super::utils::get_callee::get_callee;
 use super::super::utils::get_val::get_val;
 use super::{BuildInFnCall, ClassModule};
 
@@ -22,6 +28,182 @@ pub enum ArrayModule {
     CONTAINS,
     SLICE,
     JOIN,
+
+    MAP,
+    FILTER,
+    REDUCE,
+    FIND,
+    FINDINDEX,
+    ANY,
+    ALL,
+    FOREACH,
+    FLATMAP,
+    ZIP,
+    ENUMERATE,
+    REVERSE,
+    INDEXOF,
+    UNIQUE,
+    SORT,
+}
+
+// raw Array or Array object
+fn get_elements(value: &Value) -> Option<ArrayLiteral> {
+    let arr_value = match value {
+        Value::Array(_) => value.clone(),
+        Value::Object(_) => get_self_prop(value, "v").ok()?,
+        _ => return None,
+    };
+    let Value::Array(arr) = arr_value else {
+        return None;
+    };
+    let elements = arr.borrow().iter().cloned().collect();
+    Some(elements)
+}
+
+// default ordering for Numbers or Strings
+fn default_compare(val1: &Value, val2: &Value) -> Result<Ordering, ()> {
+    let ordering = match (val1, val2) {
+        (Value::Number(num1), Value::Number(num2)) => num1.partial_cmp(num2),
+        (Value::String(str1), Value::String(str2)) => str1.borrow().partial_cmp(&*str2.borrow()),
+        (Value::Number(_) | Value::String(_), _) => {
+            // elements of different types, like: `[1, "a"]`
+            return Err(type_error(
+                Some("sorting"),
+                vec![val1.get_type()],
+                val2.get_type(),
+            )?);
+        }
+        _ => {
+            return Err(type_error(
+                Some("sorting"),
+                vec![ValueType::Number, ValueType::String],
+                val1.get_type(),
+            )?);
+        }
+    };
+    match ordering {
+        Some(ordering) => Ok(ordering),
+        None => {
+            let msg = format!("can not compare {} with {}", val1, val2);
+            Err(math_error(&msg)?)
+        }
+    }
+}
+// the comparator returns Number, negative for `val1` before `val2`
+fn callback_compare(
+    comparator: &Value,
+    val1: &Value,
+    val2: &Value,
+    scope: &mut Scope,
+) -> Result<Ordering, ()> {
+    let result = value_invoke(comparator, vec![val1.clone(), val2.clone()], scope)?;
+    let Value::Number(num) = &result else {
+        return Err(type_error(
+            Some("comparator result"),
+            vec![ValueType::Number],
+            result.get_type(),
+        )?);
+    };
+    Ok(num
+        .float_value()
+        .partial_cmp(&0.0)
+        .unwrap_or(Ordering::Equal))
+}
+
+// stable merge sort, the first error stops sorting.
+// the comparator may not be a total order, it never panics.
+fn merge_sort(
+    mut values: Vec<Value>,
+    compare: &mut impl FnMut(&Value, &Value) -> Result<Ordering, ()>,
+) -> Result<Vec<Value>, ()> {
+    if values.len() <= 1 {
+        return Ok(values);
+    }
+    let right_half = values.split_off(values.len() / 2);
+    let left = merge_sort(values, compare)?;
+    let right = merge_sort(right_half, compare)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(val1), Some(val2)) = (left.peek(), right.peek()) {
+        // the left one goes first when equal, to keep stable
+        if compare(val1, val2)? == Ordering::Greater {
+            merged.extend(right.next());
+        } else {
+            merged.extend(left.next());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
+// methods which call back functions,
+// the array is not borrowed when calling back.
+fn callback_call(
+    module_fn: &ArrayModule,
+    arr: &Rc<RefCell<RawArray>>,
+    scope: &mut Scope,
+) -> Result<Value, ()> {
+    let elements: ArrayLiteral = arr.borrow().iter().cloned().collect();
+
+    if *module_fn == ArrayModule::SORT {
+        let comparator = get_val("comparator", scope)?;
+        let sorted = merge_sort(
+            elements.into_iter().collect(),
+            &mut |val1, val2| match comparator {
+                Value::Void(_) => default_compare(val1, val2),
+                _ => callback_compare(&comparator, val1, val2, scope),
+            },
+        )?;
+        *arr.borrow_mut() = RawArray::from(ArrayLiteral::from(sorted));
+        return Ok(Value::Array(arr.clone()));
+    }
+
+    if *module_fn == ArrayModule::REDUCE {
+        let callee = get_callee("f", scope)?;
+        let mut iter = elements.into_iter();
+        let mut accumulator = match get_val("initial", scope)? {
+            Value::Void(_) => match iter.next() {
+                Some(first) => first,
+                None => return Err(math_error("reduce of empty Array without initial value")?),
+            },
+            initial => initial,
+        };
+        for element in iter {
+            accumulator = value_invoke(&callee, vec![accumulator, element], scope)?;
+        }
+        return Ok(accumulator);
+    }
+
+    let callee = get_callee("f", scope)?;
+    let mut results = ArrayLiteral::new();
+    for (index, element) in elements.into_iter().enumerate() {
+        let result = value_invoke(&callee, vec![element.clone()], scope)?;
+        match module_fn {
+            ArrayModule::MAP => results.push_back(result),
+            ArrayModule::FILTER if result.get_bool() => results.push_back(element),
+            ArrayModule::FIND if result.get_bool() => return Ok(element),
+            ArrayModule::FINDINDEX if result.get_bool() => return Ok(Value::from(index as i64)),
+            ArrayModule::ANY if result.get_bool() => return Ok(Value::from(true)),
+            ArrayModule::ALL if !result.get_bool() => return Ok(Value::from(false)),
+            ArrayModule::FLATMAP => match get_elements(&result) {
+                Some(sub_elements) => results.extend(sub_elements),
+                None => results.push_back(result),
+            },
+            _ => {}
+        }
+    }
+
+    let result = match module_fn {
+        ArrayModule::MAP | ArrayModule::FILTER | ArrayModule::FLATMAP => Value::from(results),
+        ArrayModule::FINDINDEX => Value::from(-1_i64),
+        ArrayModule::ANY => Value::from(false),
+        ArrayModule::ALL => Value::from(true),
+        _ => Value::EMPTY,
+    };
+    Ok(result)
 }
 
 pub static mut MODULE_CLASS: ModuleClass = EMPTY_MODULE_CLASS;
@@ -81,6 +263,62 @@ impl ClassModule for ArrayModule {
             identi: BuildInFnIdenti::Array(Self::JOIN),
         };
 
+        // methods with callback
+        let map = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "f"),
+            ],
+            identi: BuildInFnIdenti::Array(Self::MAP),
+        };
+        // `map` as function template
+        let mut filter = map.clone();
+        let mut find = map.clone();
+        let mut find_index = map.clone();
+        let mut any = map.clone();
+        let mut all = map.clone();
+        let mut for_each = map.clone();
+        let mut flat_map = map.clone();
+        filter.identi = BuildInFnIdenti::Array(Self::FILTER);
+        find.identi = BuildInFnIdenti::Array(Self::FIND);
+        find_index.identi = BuildInFnIdenti::Array(Self::FINDINDEX);
+        any.identi = BuildInFnIdenti::Array(Self::ANY);
+        all.identi = BuildInFnIdenti::Array(Self::ALL);
+        for_each.identi = BuildInFnIdenti::Array(Self::FOREACH);
+        flat_map.identi = BuildInFnIdenti::Array(Self::FLATMAP);
+
+        let reduce = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "f"),
+                BuildInFnParam(ValueType::Void, "initial?"),
+            ],
+            identi: BuildInFnIdenti::Array(Self::REDUCE),
+        };
+        let sort = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "comparator?"),
+            ],
+            identi: BuildInFnIdenti::Array(Self::SORT),
+        };
+
+        let zip = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "other"),
+            ],
+            identi: BuildInFnIdenti::Array(Self::ZIP),
+        };
+        let mut index_of = contains.clone();
+        index_of.identi = BuildInFnIdenti::Array(Self::INDEXOF);
+        let mut enumerate = pop.clone();
+        let mut reverse = pop.clone();
+        let mut unique = pop.clone();
+        enumerate.identi = BuildInFnIdenti::Array(Self::ENUMERATE);
+        reverse.identi = BuildInFnIdenti::Array(Self::REVERSE);
+        unique.identi = BuildInFnIdenti::Array(Self::UNIQUE);
+
         // --- --- --- --- --- ---
 
         return Class::new(
@@ -95,6 +333,21 @@ impl ClassModule for ArrayModule {
                 (String::from("contains"), Function::from(contains)),
                 (String::from("slice"), Function::from(slice)),
                 (String::from("join"), Function::from(join)),
+                (String::from("map"), Function::from(map)),
+                (String::from("filter"), Function::from(filter)),
+                (String::from("reduce"), Function::from(reduce)),
+                (String::from("find"), Function::from(find)),
+                (String::from("find_index"), Function::from(find_index)),
+                (String::from("any"), Function::from(any)),
+                (String::from("all"), Function::from(all)),
+                (String::from("for_each"), Function::from(for_each)),
+                (String::from("flat_map"), Function::from(flat_map)),
+                (String::from("zip"), Function::from(zip)),
+                (String::from("enumerate"), Function::from(enumerate)),
+                (String::from("reverse"), Function::from(reverse)),
+                (String::from("index_of"), Function::from(index_of)),
+                (String::from("unique"), Function::from(unique)),
+                (String::from("sort"), Function::from(sort)),
             ],
         );
     }
@@ -115,6 +368,19 @@ impl BuildInFnCall for ArrayModule {
         let Value::Array(arr) = arr_value else {
             unreachable!()
         };
+        if let Self::MAP
+        | Self::FILTER
+        | Self::REDUCE
+        | Self::FIND
+        | Self::FINDINDEX
+        | Self::ANY
+        | Self::ALL
+        | Self::FOREACH
+        | Self::FLATMAP
+        | Self::SORT = self
+        {
+            return callback_call(self, &arr, scope);
+        }
         let mut arr_ref = arr.borrow_mut();
 
         let result = match self {
@@ -179,6 +445,63 @@ impl BuildInFnCall for ArrayModule {
                 let result_str = arr_ref.join(&*divider_ref);
                 Value::from(result_str)
             }
+
+            Self::ZIP => {
+                // pairs until the shorter one ends
+                let other_value = get_val("other", scope)?;
+                let Some(other_elements) = get_elements(&other_value) else {
+                    return Err(type_error(
+                        Some("Array.zip"),
+                        vec![ValueType::Array],
+                        other_value.get_type(),
+                    )?);
+                };
+                let pairs: ArrayLiteral = arr_ref
+                    .iter()
+                    .zip(other_elements)
+                    .map(|(element, other)| {
+                        Value::from(ArrayLiteral::from([element.clone(), other]))
+                    })
+                    .collect();
+                Value::from(pairs)
+            }
+            Self::ENUMERATE => {
+                let pairs: ArrayLiteral = arr_ref
+                    .iter()
+                    .enumerate()
+                    .map(|(index, element)| {
+                        Value::from(ArrayLiteral::from([
+                            Value::from(index as i64),
+                            element.clone(),
+                        ]))
+                    })
+                    .collect();
+                Value::from(pairs)
+            }
+            Self::REVERSE => {
+                let reversed: ArrayLiteral = arr_ref.iter().rev().cloned().collect();
+                *arr_ref = RawArray::from(reversed);
+                Value::Array(arr.clone())
+            }
+            Self::INDEXOF => {
+                let target_value = get_val("value", scope)?;
+                match arr_ref.iter().position(|element| *element == target_value) {
+                    Some(index) => Value::from(index as i64),
+                    None => Value::from(-1_i64),
+                }
+            }
+            Self::UNIQUE => {
+                // keep the first occurrence of each element
+                let mut elements = ArrayLiteral::new();
+                for element in arr_ref.iter() {
+                    if !elements.contains(element) {
+                        elements.push_back(element.clone());
+                    }
+                }
+                Value::from(elements)
+            }
+
+            _ => unreachable!(),
         };
         return Ok(result);
     }
@@ -1,30 +1,42 @@
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::io::{self, Write};
 use std::process;
 use std::rc::Rc;
 
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 
-use crate::public::error::{internal_error, type_error, InternalComponent, math_error};
+use crate::public::compile_time::ast::types::LazyExpressionNode;
+use crate::public::error::{
+    internal_error, math_error, range_error, syntax_error, type_error, InternalComponent,
+};
 use crate::public::run_time::build_in::BuildInFnIdenti;
 use crate::public::run_time::constants::{
-    ARRAY_T, BOOL_T, CLASS_T, FUNCTION_T, LAZYEXPR_T, MAP_T, NUMBER_T, OBJECT_T, STRING_T,
-    UNIQUE_T, VOID_T,
+    ARRAY_T, BOOL_T, CLASS_T, FUNCTION_T, LAZYEXPR_T, MAP_T, NUMBER_T, OBJECT_T, QUANTITY_T, SET_T,
+    STRING_T, UNIQUE_T, VOID_T,
 };
 use crate::public::run_time::scope::Scope;
 use crate::public::std::modules::map::MapModule;
+use crate::public::std::utils::formatter::format_values;
 use crate::public::std::utils::get_self_prop::get_self_prop;
 use crate::public::std::utils::str_to_num::str_to_num;
 use crate::public::value::array::{ArrayLiteral, RawArray};
+use crate::public::value::decimal::{
+    Decimal, RoundingMode, DECIMAL_CONTEXT, MAX_DECIMAL_PRECISION,
+};
 use crate::public::value::function::{BuildInFnParam, BuildInFunction};
 use crate::public::value::map::RawMap;
 use crate::public::value::number::Number;
+use crate::public::value::quantity::Quantity;
+use crate::public::value::set::RawSet;
+use crate::public::value::symbolic::Term;
 use crate::public::value::unique::Unique;
 use crate::public::value::value::{Value, ValueType};
 use crate::public::value::GetAddr;
 
 use super::super::utils::get_val::get_val;
 use super::array::ArrayModule;
+use super::set::SetModule;
 use super::string::StringModule;
 use super::{BuildInFnCall, ClassModule, FunctionModule};
 
@@ -37,6 +49,7 @@ pub enum BasicModule {
     INT,
     FLOAT,
     FRACTION,
+    DECIMAL,
     BOOLEAN,
     UNIQUE,
     STRING,
@@ -45,7 +58,37 @@ pub enum BasicModule {
     ASCII,
     LEN,
 
+    DECIMALPRECISION,
+    DECIMALROUNDING,
     EXIT,
+
+    DIFF,
+    SIMPLIFY,
+    SUBSTITUTE,
+
+    FORMAT,
+}
+
+// get symbolic term from the lazy-expression
+fn to_term(l_expr: &Rc<RefCell<LazyExpressionNode>>) -> Result<Term, ()> {
+    let Some(term) = Term::from_lazy_expr(&l_expr.borrow().sub_sequence) else {
+        return Err(math_error("the lazy-expression is not symbolic")?);
+    };
+    Ok(term)
+}
+// get symbolic term and params of the lazy-expression param
+fn get_term(scope: &mut Scope) -> Result<(Term, Vec<String>), ()> {
+    let Value::LazyExpression(l_expr) = get_val("expr", scope)? else {
+        unreachable!()
+    };
+    let params = l_expr.borrow().params.clone();
+    Ok((to_term(&l_expr)?, params))
+}
+fn term_to_value(term: Term, params: Vec<String>) -> Value {
+    Value::from(LazyExpressionNode {
+        params,
+        sub_sequence: term.to_lazy_expr(),
+    })
 }
 
 impl FunctionModule for BasicModule {
@@ -69,11 +112,45 @@ impl FunctionModule for BasicModule {
             ],
             identi: BuildInFnIdenti::Basic(Self::FRACTION),
         };
+        let decimal_precision = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Number, "precision")],
+            identi: BuildInFnIdenti::Basic(Self::DECIMALPRECISION),
+        };
+        let decimal_rounding = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::String, "mode")],
+            identi: BuildInFnIdenti::Basic(Self::DECIMALROUNDING),
+        };
         let unique = BuildInFunction {
             params: vec![BuildInFnParam(ValueType::String, "input")],
             identi: BuildInFnIdenti::Basic(Self::UNIQUE),
         };
 
+        let diff = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::LazyExpression, "expr"),
+                BuildInFnParam(ValueType::String, "variable"),
+            ],
+            identi: BuildInFnIdenti::Basic(Self::DIFF),
+        };
+        let simplify = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::LazyExpression, "expr")],
+            identi: BuildInFnIdenti::Basic(Self::SIMPLIFY),
+        };
+        let substitute = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::LazyExpression, "expr"),
+                BuildInFnParam(ValueType::Map, "bindings"),
+            ],
+            identi: BuildInFnIdenti::Basic(Self::SUBSTITUTE),
+        };
+        let format = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::String, "template"),
+                BuildInFnParam(ValueType::Void, "...args"),
+            ],
+            identi: BuildInFnIdenti::Basic(Self::FORMAT),
+        };
+
         // --- --- --- --- --- ---
 
         let function_template = BuildInFunction {
@@ -84,6 +161,7 @@ impl FunctionModule for BasicModule {
         let mut clone = function_template.clone();
         let mut int = function_template.clone();
         let mut float = function_template.clone();
+        let mut decimal = function_template.clone();
         let mut boolean = function_template.clone();
         let mut string = function_template.clone();
         let mut ascii = function_template.clone();
@@ -91,6 +169,7 @@ impl FunctionModule for BasicModule {
         clone.identi = BuildInFnIdenti::Basic(Self::CLONE);
         int.identi = BuildInFnIdenti::Basic(Self::INT);
         float.identi = BuildInFnIdenti::Basic(Self::FLOAT);
+        decimal.identi = BuildInFnIdenti::Basic(Self::DECIMAL);
         boolean.identi = BuildInFnIdenti::Basic(Self::BOOLEAN);
         string.identi = BuildInFnIdenti::Basic(Self::STRING);
         ascii.identi = BuildInFnIdenti::Basic(Self::ASCII);
@@ -103,6 +182,15 @@ impl FunctionModule for BasicModule {
             (String::from("int"), Value::from(int)),
             (String::from("float"), Value::from(float)),
             (String::from("fraction"), Value::from(fraction)),
+            (String::from("decimal"), Value::from(decimal)),
+            (
+                String::from("decimal_precision"),
+                Value::from(decimal_precision),
+            ),
+            (
+                String::from("decimal_rounding"),
+                Value::from(decimal_rounding),
+            ),
             (String::from("bool"), Value::from(boolean)),
             (String::from("unique"), Value::from(unique)),
             (String::from("string"), Value::from(string)),
@@ -110,6 +198,10 @@ impl FunctionModule for BasicModule {
             (String::from("ascii"), Value::from(ascii)),
             (String::from("len"), Value::from(len)),
             (String::from("exit"), Value::from(exit)),
+            (String::from("diff"), Value::from(diff)),
+            (String::from("simplify"), Value::from(simplify)),
+            (String::from("substitute"), Value::from(substitute)),
+            (String::from("format"), Value::from(format)),
         ];
     }
 }
@@ -148,19 +240,103 @@ impl BuildInFnCall for BasicModule {
                     return Err(math_error("the divisor should not to be ZERO")?);
                 }
 
-                if let (Value::Number(Number::Int(upper)), Value::Number(Number::Int(lower))) =
-                    (upper_value, lower_value)
-                {
-                    return Ok(Value::Number(Number::Fraction(upper, lower)));
-                } else {
-                    return Err(internal_error(
-                        InternalComponent::Std,
-                        "two Int typed value is expected",
+                match (upper_value, lower_value) {
+                    (Value::Number(upper), Value::Number(lower))
+                        if upper.is_integer() && lower.is_integer() =>
+                    {
+                        let fraction = Number::fraction(upper.big_value(), lower.big_value());
+                        return Ok(Value::Number(fraction));
+                    }
+                    _ => {
+                        return Err(internal_error(
+                            InternalComponent::Std,
+                            "two Int typed value is expected",
+                        )?);
+                    }
+                }
+            }
+            Self::DECIMALPRECISION => {
+                let precision_value = get_val("precision", scope)?;
+                let precision = precision_value.get_i64()?;
+                if precision < 0 {
+                    return Err(math_error("decimal precision should not be negative")?);
+                }
+                if precision > MAX_DECIMAL_PRECISION as i64 {
+                    return Err(range_error(
+                        "decimal precision",
+                        format!("0..={}", MAX_DECIMAL_PRECISION),
+                        precision as usize,
                     )?);
                 }
+                unsafe { DECIMAL_CONTEXT.precision = precision as u32 };
+                Value::EMPTY
+            }
+            Self::DECIMALROUNDING => {
+                let mode_value = get_val("mode", scope)?;
+                let mode_ref = mode_value.get_str()?;
+                let Some(rounding) = RoundingMode::from_str(&mode_ref) else {
+                    return Err(syntax_error(
+                        "rounding mode should be one of `half_even`, `half_up`, `half_down`, `up`, `down`, `ceiling` and `floor`",
+                    )?);
+                };
+                unsafe { DECIMAL_CONTEXT.rounding = rounding };
+                Value::EMPTY
             }
             Self::EXIT => process::exit(0),
 
+            Self::DIFF => {
+                let (term, params) = get_term(scope)?;
+                let variable = get_val("variable", scope)?;
+                let derivative = term.diff(&variable.get_str()?)?;
+                term_to_value(derivative, params)
+            }
+            Self::SIMPLIFY => {
+                let (term, params) = get_term(scope)?;
+                term_to_value(term.simplify(), params)
+            }
+            Self::SUBSTITUTE => {
+                // the binding values can be Number or lazy-expression
+                let (term, mut params) = get_term(scope)?;
+                let Value::Map(bindings_map) = get_val("bindings", scope)? else {
+                    unreachable!()
+                };
+                let mut bindings = HashMap::<String, Term>::new();
+                for (name, value) in bindings_map.borrow().iter() {
+                    let bound_term = match value {
+                        Value::Number(num) => Term::Number(num.clone()),
+                        Value::LazyExpression(l_expr) => {
+                            // substituted lazy-expression brings its params
+                            for param in &l_expr.borrow().params {
+                                if !params.contains(param) {
+                                    params.push(param.clone());
+                                }
+                            }
+                            to_term(l_expr)?
+                        }
+                        _ => {
+                            return Err(type_error(
+                                Some(name),
+                                vec![ValueType::Number, ValueType::LazyExpression],
+                                value.get_type(),
+                            )?)
+                        }
+                    };
+                    bindings.insert(name.clone(), bound_term);
+                }
+                params.retain(|param| !bindings.contains_key(param));
+                term_to_value(term.substitute(&bindings), params)
+            }
+
+            Self::FORMAT => {
+                let template_value = get_val("template", scope)?;
+                let Value::Array(args) = get_val("args", scope)? else {
+                    unreachable!()
+                };
+                let args: Vec<Value> = args.borrow().iter().cloned().collect();
+                let template_ref = template_value.get_str()?;
+                Value::from(format_values(&template_ref, &args)?)
+            }
+
             _ => {
                 let input = get_val("input", scope)?;
 
@@ -171,10 +347,12 @@ impl BuildInFnCall for BasicModule {
                                 ValueType::Void => &VOID_T,
                                 ValueType::Boolean => &BOOL_T,
                                 ValueType::Number => &NUMBER_T,
+                                ValueType::Quantity => &QUANTITY_T,
                                 ValueType::Unique => &UNIQUE_T,
                                 ValueType::String => &STRING_T,
                                 ValueType::Array => &ARRAY_T,
                                 ValueType::Map => &MAP_T,
+                                ValueType::Set => &SET_T,
                                 ValueType::LazyExpression => &LAZYEXPR_T,
                                 ValueType::Function => &FUNCTION_T,
                                 ValueType::Class => &CLASS_T,
@@ -187,6 +365,9 @@ impl BuildInFnCall for BasicModule {
 
                     Self::INT => match input {
                         Value::Number(num) => Value::Number(num.int()),
+                        Value::Quantity(quantity) => {
+                            Value::Quantity(Quantity::new(quantity.value.int(), quantity.unit))
+                        }
                         Value::String(str) => {
                             let refer = str.as_ref().borrow();
                             let i = str_to_num::<i64>(refer)?;
@@ -203,6 +384,9 @@ impl BuildInFnCall for BasicModule {
                     },
                     Self::FLOAT => match input {
                         Value::Number(num) => Value::Number(num.float()),
+                        Value::Quantity(quantity) => {
+                            Value::Quantity(Quantity::new(quantity.value.float(), quantity.unit))
+                        }
                         Value::String(str) => {
                             let refer = str.as_ref().borrow();
                             let f = str_to_num::<f64>(refer)?;
@@ -218,6 +402,42 @@ impl BuildInFnCall for BasicModule {
                         }
                     },
 
+                    Self::DECIMAL => {
+                        let decimal = match &input {
+                            Value::Number(num) if num.is_integer() => {
+                                Decimal::from_integer(num.big_value())
+                            }
+                            Value::Number(Number::Fraction(upper, lower)) => {
+                                Decimal::from_fraction(upper, lower, 0)
+                            }
+                            Value::Number(Number::Decimal(decimal)) => decimal.clone(),
+                            Value::Number(Number::Float(f)) if f.is_finite() => {
+                                // use the shortest representation of the float
+                                Decimal::parse(&f.to_string()).unwrap()
+                            }
+                            Value::Number(_) => {
+                                return Err(math_error(
+                                    "only finite real number can be converted to Decimal",
+                                )?)
+                            }
+                            Value::String(str) => {
+                                let refer = str.as_ref().borrow();
+                                let Some(decimal) = Decimal::parse(&refer) else {
+                                    let msg = format!("invalid decimal string `{}`", refer);
+                                    return Err(syntax_error(&msg)?);
+                                };
+                                decimal
+                            }
+                            _ => {
+                                return Err(type_error(
+                                    Some("Build-in function `decimal`"),
+                                    vec![ValueType::Number, ValueType::String],
+                                    input.get_type(),
+                                )?)
+                            }
+                        };
+                        Value::Number(Number::Decimal(decimal))
+                    }
                     Self::BOOLEAN => Value::Boolean(input.get_bool()),
                     Self::STRING => Value::from(input.to_raw_string()),
                     Self::UNIQUE => Value::from(Unique::from(input.to_raw_string())),
@@ -261,15 +481,23 @@ impl BuildInFnCall for BasicModule {
                             let length = refer.len() as i64;
                             return Value::from(length);
                         }
+                        #[inline]
+                        fn set_length(set: &Rc<RefCell<RawSet>>) -> Value {
+                            let refer = set.borrow();
+                            let length = refer.len() as i64;
+                            return Value::from(length);
+                        }
                         match &input {
                             Value::Array(arr) => return Ok(array_length(arr)),
                             Value::String(str) => return Ok(string_length(str)),
                             Value::Map(map) => return Ok(map_length(map)),
+                            Value::Set(set) => return Ok(set_length(set)),
                             Value::Object(obj) => {
                                 if let Some(proto) = obj.borrow().get_proto() {
                                     let string_cls = StringModule::module_class();
                                     let array_cls = ArrayModule::module_class();
                                     let map_cls = MapModule::module_class();
+                                    let set_cls = SetModule::module_class();
                                     let proto_addr = proto.get_addr();
 
                                     match proto_addr {
@@ -294,6 +522,13 @@ impl BuildInFnCall for BasicModule {
                                             };
                                             return Ok(map_length(&map_ref));
                                         }
+                                        x if x == set_cls.get_addr() => {
+                                            let set_val = get_self_prop(&input, "v")?;
+                                            let Value::Set(set_ref) = set_val else {
+                                                unreachable!()
+                                            };
+                                            return Ok(set_length(&set_ref));
+                                        }
                                         _ => {}
                                     }
                                 }
@@ -302,7 +537,12 @@ impl BuildInFnCall for BasicModule {
                         };
                         return Err(type_error(
                             Some("Build-in function `len`"),
-                            vec![ValueType::Array, ValueType::String, ValueType::Map],
+                            vec![
+                                ValueType::Array,
+                                ValueType::String,
+                                ValueType::Map,
+                                ValueType::Set,
+                            ],
                             input.get_type(),
                         )?);
                     }
@@ -0,0 +1,355 @@
+use crate::public::error::{
+    internal_error, range_error, syntax_error, type_error, InternalComponent,
+};
+use crate::public::run_time::build_in::BuildInFnIdenti;
+use crate::public::run_time::scope::Scope;
+use crate::public::std::utils::str_to_num::parse_number;
+use crate::public::value::array::ArrayLiteral;
+use crate::public::value::function::{BuildInFnParam, BuildInFunction};
+use crate::public::value::map::{InternalMap, RawMap};
+use crate::public::value::number::Number;
+use crate::public::value::oop::object::Object;
+use crate::public::value::value::{Value, ValueType};
+
+use super::super::utils::get_val::get_val;
+use super::file_system::file_ops::{file_read, file_write};
+use super::file_system::file_props;
+use super::{BuildInFnCall, ObjectModule};
+
+#[derive(PartialEq, Clone)]
+pub enum CsvModule {
+    PARSE,
+    READ,
+    STRINGIFY,
+    WRITE,
+}
+
+// the field is quoted or not
+type Field = (String, bool);
+
+fn get_delimiter(scope: &mut Scope, fn_name: &str) -> Result<char, ()> {
+    let delimiter_value = get_val("delimiter", scope)?;
+    if let Value::Void(_) = delimiter_value {
+        return Ok(',');
+    }
+    let delimiter_ref = delimiter_value.get_str()?;
+    let mut delimiter_chars = delimiter_ref.chars();
+    match (delimiter_chars.next(), delimiter_chars.next()) {
+        (Some(ch), None) if ch != '"' && ch != '\n' && ch != '\r' => Ok(ch),
+        _ => Err(range_error(
+            fn_name,
+            "1 character except quote and newline",
+            delimiter_ref.chars().count(),
+        )?),
+    }
+}
+
+fn get_header(scope: &mut Scope) -> Result<bool, ()> {
+    match get_val("header", scope)? {
+        Value::Void(_) => Ok(false),
+        Value::Boolean(header) => Ok(header),
+        val => Err(type_error(
+            Some("CSV header"),
+            vec![ValueType::Boolean],
+            val.get_type(),
+        )?),
+    }
+}
+
+// --- --- --- --- --- ---
+
+// split records and fields, quoted fields may contain delimiters, quotes and newlines.
+fn split_records(source: &str, delimiter: char) -> Result<Vec<Vec<Field>>, ()> {
+    let mut records = vec![];
+    let mut record = Vec::<Field>::new();
+    let mut field = String::new();
+    // the current field is quoted, and the quote is not closed yet
+    let mut is_quoted = false;
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut quote_line = 1;
+
+    let mut chars = source.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            match ch {
+                // `""` is the escaped quote
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if ch == '\n' {
+                        line += 1;
+                    }
+                    field.push(ch);
+                }
+            }
+            continue;
+        }
+
+        match ch {
+            '"' if field.is_empty() && !is_quoted => {
+                is_quoted = true;
+                in_quotes = true;
+                quote_line = line;
+            }
+            _ if is_quoted && ch != delimiter && ch != '\r' && ch != '\n' => {
+                let msg = format!(
+                    "invalid CSV at line {}: unexpected character after closing quote",
+                    line
+                );
+                return Err(syntax_error(&msg)?);
+            }
+            _ if ch == delimiter => {
+                record.push((std::mem::take(&mut field), is_quoted));
+                is_quoted = false;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\r' | '\n' => {
+                line += 1;
+                // blank lines are skipped
+                if !record.is_empty() || !field.is_empty() || is_quoted {
+                    record.push((std::mem::take(&mut field), is_quoted));
+                    records.push(std::mem::take(&mut record));
+                    is_quoted = false;
+                }
+            }
+            _ => field.push(ch),
+        }
+    }
+
+    if in_quotes {
+        let msg = format!("invalid CSV at line {}: unclosed quoted field", quote_line);
+        return Err(syntax_error(&msg)?);
+    }
+    if !record.is_empty() || !field.is_empty() || is_quoted {
+        record.push((field, is_quoted));
+        records.push(record);
+    }
+    Ok(records)
+}
+
+// unquoted numeric fields are converted to Numbers
+fn field_value((text, is_quoted): Field) -> Value {
+    if !is_quoted {
+        if let Some(num) = parse_number(text.trim()) {
+            return Value::Number(num);
+        }
+    }
+    Value::from(text)
+}
+
+// Array of row Arrays, or Array of Maps with the first row as header
+fn parse(source: &str, delimiter: char, header: bool) -> Result<Value, ()> {
+    let mut records = split_records(source, delimiter)?.into_iter();
+    let mut rows = ArrayLiteral::new();
+
+    if !header {
+        for record in records {
+            let row: ArrayLiteral = record.into_iter().map(field_value).collect();
+            rows.push_back(Value::from(row));
+        }
+        return Ok(Value::from(rows));
+    }
+
+    let Some(keys) = records.next() else {
+        return Ok(Value::from(rows));
+    };
+    let keys: Vec<String> = keys.into_iter().map(|(key, _)| key).collect();
+    for (index, record) in records.enumerate() {
+        if record.len() > keys.len() {
+            // the header is the line 1
+            let param = format!("CSV fields of record {}", index + 2);
+            return Err(range_error(&param, keys.len(), record.len())?);
+        }
+        // missing fields are Void
+        let mut row = InternalMap::new();
+        let mut fields = record.into_iter();
+        for key in &keys {
+            let value = fields.next().map_or(Value::EMPTY, field_value);
+            row.insert(key.clone(), value);
+        }
+        rows.push_back(Value::from(RawMap::new(row)));
+    }
+    Ok(Value::from(rows))
+}
+
+// --- --- --- --- --- ---
+
+fn field_text(value: &Value) -> Result<String, ()> {
+    let text = match value {
+        Value::Void(_) => String::new(),
+        Value::String(str_value) => str_value.borrow().clone(),
+        Value::Boolean(bool_value) => bool_value.to_string(),
+        Value::Number(Number::Float(float)) => float.to_string(),
+        Value::Number(num @ Number::Fraction(_, _)) => num.float_value().to_string(),
+        Value::Number(num) => num.to_string(),
+        _ => {
+            return Err(type_error(
+                Some("CSV field"),
+                vec![ValueType::Boolean, ValueType::Number, ValueType::String],
+                value.get_type(),
+            )?)
+        }
+    };
+    Ok(text)
+}
+
+fn escape_field(text: String, delimiter: char) -> String {
+    let need_quote = text.contains(delimiter)
+        || text.contains(['"', '\n', '\r'])
+        || text.starts_with(' ')
+        || text.ends_with(' ');
+    if !need_quote {
+        return text;
+    }
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+fn write_record(fields: Vec<String>, delimiter: char, result: &mut String) {
+    let escaped: Vec<String> = fields
+        .into_iter()
+        .map(|field| escape_field(field, delimiter))
+        .collect();
+    result.push_str(&escaped.join(&delimiter.to_string()));
+    result.push('\n');
+}
+
+// rows of Arrays, or rows of Maps with their sorted keys of the first row as header
+fn stringify(rows_value: &Value, delimiter: char) -> Result<String, ()> {
+    let Value::Array(rows) = rows_value else {
+        unreachable!()
+    };
+    let rows: ArrayLiteral = rows.borrow().iter().cloned().collect();
+    let mut result = String::new();
+
+    let keys = match rows.front() {
+        Some(Value::Map(first)) => {
+            let mut keys: Vec<String> = first.borrow().keys().cloned().collect();
+            keys.sort();
+            write_record(keys.clone(), delimiter, &mut result);
+            Some(keys)
+        }
+        _ => None,
+    };
+
+    for row in &rows {
+        let fields = match (row, &keys) {
+            (Value::Array(row), None) => {
+                let mut fields = vec![];
+                for value in row.borrow().iter() {
+                    fields.push(field_text(value)?);
+                }
+                fields
+            }
+            (Value::Map(row), Some(keys)) => {
+                let row_ref = row.borrow();
+                let mut fields = vec![];
+                for key in keys {
+                    fields.push(field_text(&row_ref.get(key).unwrap_or(Value::EMPTY))?);
+                }
+                fields
+            }
+            _ => {
+                let expected = match keys {
+                    Some(_) => ValueType::Map,
+                    None => ValueType::Array,
+                };
+                return Err(type_error(Some("CSV row"), vec![expected], row.get_type())?);
+            }
+        };
+        write_record(fields, delimiter, &mut result);
+    }
+    Ok(result)
+}
+
+// --- --- --- --- --- ---
+
+impl ObjectModule for CsvModule {
+    fn module_object() -> Object {
+        let parse = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::String, "text"),
+                BuildInFnParam(ValueType::Void, "header?"),
+                BuildInFnParam(ValueType::Void, "delimiter?"),
+            ],
+            identi: BuildInFnIdenti::Csv(Self::PARSE),
+        };
+        let read = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Object, "file"),
+                BuildInFnParam(ValueType::Void, "header?"),
+                BuildInFnParam(ValueType::Void, "delimiter?"),
+            ],
+            identi: BuildInFnIdenti::Csv(Self::READ),
+        };
+        let stringify = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Array, "rows"),
+                BuildInFnParam(ValueType::Void, "delimiter?"),
+            ],
+            identi: BuildInFnIdenti::Csv(Self::STRINGIFY),
+        };
+        let write = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Object, "file"),
+                BuildInFnParam(ValueType::Array, "rows"),
+                BuildInFnParam(ValueType::Void, "delimiter?"),
+            ],
+            identi: BuildInFnIdenti::Csv(Self::WRITE),
+        };
+
+        let module_obj_props = vec![
+            (String::from("parse"), Value::from(parse)),
+            (String::from("read"), Value::from(read)),
+            (String::from("stringify"), Value::from(stringify)),
+            (String::from("write"), Value::from(write)),
+        ];
+        return Object::new(module_obj_props, None);
+    }
+}
+
+impl BuildInFnCall for CsvModule {
+    fn call(&self, scope: &mut Scope) -> Result<Value, ()> {
+        let result = match self {
+            Self::PARSE => {
+                let delimiter = get_delimiter(scope, "CSV.parse")?;
+                let header = get_header(scope)?;
+                let text_value = get_val("text", scope)?;
+                let text_ref = text_value.get_str()?;
+                parse(&text_ref, delimiter, header)?
+            }
+            Self::READ => {
+                let delimiter = get_delimiter(scope, "CSV.read")?;
+                let header = get_header(scope)?;
+                let (path, file_info) = file_props(&get_val("file", scope)?)?;
+                let content = file_read(&path.get_str()?, file_info)?;
+                let Value::String(content_ref) = content else {
+                    let msg = format!("path '{}' is not a legal file", path.get_str()?);
+                    return Err(internal_error(InternalComponent::Std, &msg)?);
+                };
+                let content_str = content_ref.borrow();
+                parse(&content_str, delimiter, header)?
+            }
+            Self::STRINGIFY => {
+                let delimiter = get_delimiter(scope, "CSV.stringify")?;
+                let rows_value = get_val("rows", scope)?;
+                Value::from(stringify(&rows_value, delimiter)?)
+            }
+            Self::WRITE => {
+                let delimiter = get_delimiter(scope, "CSV.write")?;
+                let (path, file_info) = file_props(&get_val("file", scope)?)?;
+                let content = stringify(&get_val("rows", scope)?, delimiter)?;
+                file_write(&path.get_str()?, Value::from(content), file_info)?;
+                Value::EMPTY
+            }
+        };
+        Ok(result)
+    }
+}
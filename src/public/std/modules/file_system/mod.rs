@@ -1,4 +1,4 @@
-mod file_ops;
+pub(super) mod file_ops;
 mod fs_ops;
 
 use std::path::Path;
@@ -74,6 +74,15 @@ fn static_class_setter() {
     };
 }
 
+// the path and the (exist, is_dir, is_file) info of `File` object
+pub(super) fn file_props(file_value: &Value) -> Result<(Value, (Value, Value, Value)), ()> {
+    let path = get_self_prop(file_value, "path")?;
+    let exist = get_self_prop(file_value, "exist")?;
+    let is_dir = get_self_prop(file_value, "is_dir")?;
+    let is_file = get_self_prop(file_value, "is_file")?;
+    Ok((path, (exist, is_dir, is_file)))
+}
+
 impl ObjectModule for FileSysModule {
     fn module_object() -> Object {
         if unsafe { FILE_CLASS == None } {
@@ -156,12 +165,7 @@ impl BuildInFnCall for FileSysModule {
             _ => {
                 // the code following is used as the method of class `File`.
                 let self_value = get_val("self", scope)?;
-
-                let self_path = get_self_prop(&self_value, "path")?;
-                let exist = get_self_prop(&self_value, "exist")?;
-                let is_dir = get_self_prop(&self_value, "is_dir")?;
-                let is_file = get_self_prop(&self_value, "is_file")?;
-                let file_info = (exist, is_dir, is_file);
+                let (self_path, file_info) = file_props(&self_value)?;
 
                 let temp = self_path.get_str()?;
                 let file_path = temp.as_str();
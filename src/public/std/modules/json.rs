@@ -0,0 +1,516 @@
+use std::rc::Rc;
+
+use crate::public::error::{math_error, syntax_error, type_error};
+use crate::public::run_time::build_in::BuildInFnIdenti;
+use crate::public::run_time::scope::Scope;
+use crate::public::value::array::ArrayLiteral;
+use crate::public::value::big_int::BigInt;
+use crate::public::value::function::{BuildInFnParam, BuildInFunction};
+use crate::public::value::map::{InternalMap, RawMap};
+use crate::public::value::number::Number;
+use crate::public::value::oop::object::Object;
+use crate::public::value::value::{Value, ValueType};
+
+use super::super::utils::get_val::get_val;
+use super::{BuildInFnCall, ObjectModule};
+
+#[derive(PartialEq, Clone)]
+pub enum JsonModule {
+    PARSE,
+    STRINGIFY,
+}
+
+// max nesting depth of arrays and objects
+const MAX_DEPTH: usize = 512;
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn error<T>(&self, msg: &str) -> Result<T, ()> {
+        let msg = format!("invalid JSON at index {}: {}", self.position, msg);
+        Err(syntax_error(&msg)?)
+    }
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+    fn expect(&mut self, ch: char) -> Result<(), ()> {
+        if self.peek() != Some(ch) {
+            let msg = format!("expected '{}'", ch);
+            return self.error(&msg);
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, ()> {
+        if depth > MAX_DEPTH {
+            return self.error("too deeply nested");
+        }
+        self.skip_whitespace();
+
+        let value = match self.peek() {
+            Some('{') => self.object(depth)?,
+            Some('[') => self.array(depth)?,
+            Some('"') => Value::from(self.string()?),
+            Some('-' | '0'..='9') => Value::Number(self.number()?),
+            Some('t') => self.literal("true", Value::from(true))?,
+            Some('f') => self.literal("false", Value::from(false))?,
+            Some('n') => self.literal("null", Value::EMPTY)?,
+            Some(_) => return self.error("unexpected character"),
+            None => return self.error("unexpected end of input"),
+        };
+        self.skip_whitespace();
+        Ok(value)
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, ()> {
+        let is_matched = word
+            .chars()
+            .enumerate()
+            .all(|(offset, ch)| self.chars.get(self.position + offset) == Some(&ch));
+        if !is_matched {
+            return self.error("unexpected character");
+        }
+        self.position += word.len();
+        Ok(value)
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value, ()> {
+        self.position += 1;
+        let mut map = InternalMap::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Value::from(RawMap::new(map)));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return self.error("expected string key");
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.value(depth + 1)?;
+            map.insert(key, value);
+
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    break;
+                }
+                _ => return self.error("expected ',' or '}'"),
+            }
+        }
+        Ok(Value::from(RawMap::new(map)))
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, ()> {
+        self.position += 1;
+        let mut elements = ArrayLiteral::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Value::from(elements));
+        }
+        loop {
+            elements.push_back(self.value(depth + 1)?);
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    break;
+                }
+                _ => return self.error("expected ',' or ']'"),
+            }
+        }
+        Ok(Value::from(elements))
+    }
+
+    fn string(&mut self) -> Result<String, ()> {
+        self.position += 1;
+        let mut result = String::new();
+
+        loop {
+            let Some(ch) = self.peek() else {
+                return self.error("unclosed string");
+            };
+            self.position += 1;
+
+            match ch {
+                '"' => break,
+                '\\' => {
+                    let Some(escaped) = self.peek() else {
+                        return self.error("unclosed string");
+                    };
+                    self.position += 1;
+                    let converted = match escaped {
+                        '"' | '\\' | '/' => escaped,
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => self.unicode_escape()?,
+                        _ => {
+                            self.position -= 1;
+                            return self.error("invalid escape sequence");
+                        }
+                    };
+                    result.push(converted);
+                }
+                _ if (ch as u32) < 0x20 => {
+                    self.position -= 1;
+                    return self.error("control character in string");
+                }
+                _ => result.push(ch),
+            }
+        }
+        Ok(result)
+    }
+
+    // `\uXXXX`, the surrogate pair is combined
+    fn unicode_escape(&mut self) -> Result<char, ()> {
+        let read_hex = |parser: &mut Self| -> Result<u32, ()> {
+            let digits: String = parser.chars.iter().skip(parser.position).take(4).collect();
+            let is_valid = digits.len() == 4 && digits.chars().all(|ch| ch.is_ascii_hexdigit());
+            if !is_valid {
+                return parser.error("invalid unicode escape");
+            }
+            parser.position += 4;
+            Ok(u32::from_str_radix(&digits, 16).unwrap())
+        };
+
+        let high = read_hex(self)?;
+        let code_point = if (0xD800..0xDC00).contains(&high) {
+            let is_pair =
+                self.peek() == Some('\\') && self.chars.get(self.position + 1) == Some(&'u');
+            if !is_pair {
+                return self.error("unpaired surrogate");
+            }
+            self.position += 2;
+            let low = read_hex(self)?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return self.error("unpaired surrogate");
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        match char::from_u32(code_point) {
+            Some(ch) => Ok(ch),
+            None => self.error("unpaired surrogate"),
+        }
+    }
+
+    // Int for integers, Float for the number with fraction or exponent
+    fn number(&mut self) -> Result<Number, ()> {
+        let start = self.position;
+        let mut is_float = false;
+
+        let is_negative = self.peek() == Some('-');
+        if is_negative {
+            self.position += 1;
+        }
+        let digits_start = self.position;
+        match self.peek() {
+            Some('0') => self.position += 1,
+            Some('1'..='9') => self.skip_digits(),
+            _ => return self.error("invalid number"),
+        }
+        if self.peek() == Some('.') {
+            is_float = true;
+            self.position += 1;
+            if !self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+                return self.error("invalid number");
+            }
+            self.skip_digits();
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            is_float = true;
+            self.position += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.position += 1;
+            }
+            if !self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+                return self.error("invalid number");
+            }
+            self.skip_digits();
+        }
+
+        if is_float {
+            let literal: String = self.chars[start..self.position].iter().collect();
+            return match literal.parse::<f64>() {
+                Ok(float) => Ok(Number::Float(float)),
+                Err(_) => self.error("invalid number"),
+            };
+        }
+        // the magnitude is parsed without sign
+        let digits: String = self.chars[digits_start..self.position].iter().collect();
+        let Some(magnitude) = BigInt::from_str_radix(&digits, 10) else {
+            return self.error("invalid number");
+        };
+        let big = if is_negative { -&magnitude } else { magnitude };
+        Ok(Number::from_big(big))
+    }
+    fn skip_digits(&mut self) {
+        while self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            self.position += 1;
+        }
+    }
+}
+
+fn parse(source: &str) -> Result<Value, ()> {
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        position: 0,
+    };
+    let value = parser.value(0)?;
+    if parser.position < parser.chars.len() {
+        return parser.error("unexpected trailing characters");
+    }
+    Ok(value)
+}
+
+// --- --- --- --- --- ---
+
+struct Stringifier {
+    indent: usize,
+    // addresses of the Arrays, Maps and Objects being converted
+    ancestors: Vec<usize>,
+}
+
+fn escape_string(str_ref: &str, result: &mut String) {
+    result.push('"');
+    for ch in str_ref.chars() {
+        match ch {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '\u{8}' => result.push_str("\\b"),
+            '\u{c}' => result.push_str("\\f"),
+            _ if (ch as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", ch as u32)),
+            _ => result.push(ch),
+        }
+    }
+    result.push('"');
+}
+
+impl Stringifier {
+    fn new_line(&self, level: usize, result: &mut String) {
+        if self.indent > 0 {
+            result.push('\n');
+            result.push_str(&" ".repeat(self.indent * level));
+        }
+    }
+
+    fn enter(&mut self, addr: usize) -> Result<(), ()> {
+        if self.ancestors.contains(&addr) {
+            return Err(math_error("cyclic structure can not be converted to JSON")?);
+        }
+        self.ancestors.push(addr);
+        Ok(())
+    }
+
+    fn number(num: &Number, result: &mut String) -> Result<(), ()> {
+        match num {
+            Number::Int(_) | Number::BigInt(_) | Number::Decimal(_) => {
+                result.push_str(&num.to_string())
+            }
+            Number::Float(_) | Number::Fraction(_, _) => {
+                let float = num.float_value();
+                if !float.is_finite() {
+                    return Err(math_error("infinite or NaN can not be converted to JSON")?);
+                }
+                result.push_str(&format!("{:?}", float));
+            }
+            Number::Complex(_, _) | Number::NotANumber => {
+                let msg = format!("the number `{}` can not be converted to JSON", num);
+                return Err(math_error(&msg)?);
+            }
+        }
+        Ok(())
+    }
+
+    // `[v]` for Array and Set
+    fn elements(
+        &mut self,
+        elements: ArrayLiteral,
+        level: usize,
+        result: &mut String,
+    ) -> Result<(), ()> {
+        if elements.is_empty() {
+            result.push_str("[]");
+            return Ok(());
+        }
+        result.push('[');
+        for (index, element) in elements.iter().enumerate() {
+            if index > 0 {
+                result.push(',');
+            }
+            self.new_line(level + 1, result);
+            self.value(element, level + 1, result)?;
+        }
+        self.new_line(level, result);
+        result.push(']');
+        Ok(())
+    }
+    // `{"k": v}` for Map and Object
+    fn entries(
+        &mut self,
+        entries: Vec<(String, Value)>,
+        level: usize,
+        result: &mut String,
+    ) -> Result<(), ()> {
+        if entries.is_empty() {
+            result.push_str("{}");
+            return Ok(());
+        }
+        let separator = if self.indent > 0 { ": " } else { ":" };
+
+        result.push('{');
+        for (index, (key, value)) in entries.iter().enumerate() {
+            if index > 0 {
+                result.push(',');
+            }
+            self.new_line(level + 1, result);
+            escape_string(key, result);
+            result.push_str(separator);
+            self.value(value, level + 1, result)?;
+        }
+        self.new_line(level, result);
+        result.push('}');
+        Ok(())
+    }
+
+    fn value(&mut self, value: &Value, level: usize, result: &mut String) -> Result<(), ()> {
+        match value {
+            Value::Void(_) => result.push_str("null"),
+            Value::Boolean(bool_value) => result.push_str(&bool_value.to_string()),
+            Value::Number(num) => Self::number(num, result)?,
+            Value::String(str_value) => escape_string(&str_value.borrow(), result),
+
+            Value::Array(arr) => {
+                self.enter(Rc::as_ptr(arr) as usize)?;
+                let elements: ArrayLiteral = arr.borrow().iter().cloned().collect();
+                self.elements(elements, level, result)?;
+                self.ancestors.pop();
+            }
+            Value::Set(set) => {
+                // Set elements are scalars, no cycle is possible
+                let elements: ArrayLiteral = set.borrow().iter().cloned().collect();
+                self.elements(elements, level, result)?;
+            }
+            Value::Map(map) => {
+                self.enter(Rc::as_ptr(map) as usize)?;
+                let mut entries: Vec<(String, Value)> = map
+                    .borrow()
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                entries.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+                self.entries(entries, level, result)?;
+                self.ancestors.pop();
+            }
+            Value::Object(obj) => {
+                // methods of module objects are skipped
+                self.enter(Rc::as_ptr(obj) as usize)?;
+                let entries = obj
+                    .borrow()
+                    .properties()
+                    .into_iter()
+                    .filter(|(_, v)| !matches!(v, Value::Function(_)))
+                    .collect();
+                self.entries(entries, level, result)?;
+                self.ancestors.pop();
+            }
+
+            _ => {
+                return Err(type_error(
+                    Some("JSON.stringify"),
+                    vec![
+                        ValueType::Boolean,
+                        ValueType::Number,
+                        ValueType::String,
+                        ValueType::Array,
+                        ValueType::Set,
+                        ValueType::Map,
+                        ValueType::Object,
+                    ],
+                    value.get_type(),
+                )?)
+            }
+        }
+        Ok(())
+    }
+}
+
+// --- --- --- --- --- ---
+
+impl ObjectModule for JsonModule {
+    fn module_object() -> Object {
+        let parse = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::String, "text"),
+            ],
+            identi: BuildInFnIdenti::Json(Self::PARSE),
+        };
+        let stringify = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "value"),
+                BuildInFnParam(ValueType::Void, "indent?"),
+            ],
+            identi: BuildInFnIdenti::Json(Self::STRINGIFY),
+        };
+
+        let module_obj_props = vec![
+            (String::from("parse"), Value::from(parse)),
+            (String::from("stringify"), Value::from(stringify)),
+        ];
+        return Object::new(module_obj_props, None);
+    }
+}
+
+impl BuildInFnCall for JsonModule {
+    fn call(&self, scope: &mut Scope) -> Result<Value, ()> {
+        let result = match self {
+            Self::PARSE => {
+                let text_value = get_val("text", scope)?;
+                let text_ref = text_value.get_str()?;
+                parse(&text_ref)?
+            }
+            Self::STRINGIFY => {
+                // compact output if `indent` is omitted or ZERO
+                let value = get_val("value", scope)?;
+                let indent = match get_val("indent", scope)? {
+                    Value::Void(_) => 0,
+                    val => val.get_i64()?.max(0) as usize,
+                };
+
+                let mut stringifier = Stringifier {
+                    indent,
+                    ancestors: vec![],
+                };
+                let mut result = String::new();
+                stringifier.value(&value, 0, &mut result)?;
+                Value::from(result)
+            }
+        };
+        Ok(result)
+    }
+}
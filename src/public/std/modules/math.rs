@@ -1,10 +1,13 @@
-use crate::public::error::math_error;
+use crate::computer::resolvers::invocation::invocation_resolve::value_invoke;
+use crate::public::error::{math_error, type_error};
 use crate::public::run_time::build_in::BuildInFnIdenti;
 use crate::public::run_time::scope::Scope;
 use crate::public::value::function::{BuildInFnParam, BuildInFunction};
+use crate::public::value::number::Number;
 use crate::public::value::oop::object::Object;
 use crate::public::value::value::{Value, ValueType};
 
+use super::super::utils::get_callee::get_callee;
 use super::super::utils::get_val::get_val;
 use super::{BuildInFnCall, ObjectModule};
 
@@ -30,6 +33,251 @@ pub enum MathModule {
     SQRT,
     FLOOR,
     ROUND,
+
+    ARG,
+    CONJ,
+    RE,
+    IM,
+    POW,
+
+    DIFF,
+    INTEGRATE,
+    ROOT,
+    MINIMIZE,
+    SUM,
+}
+
+// --- --- --- --- --- ---
+// numerical calculus with Function or LazyExpression
+
+const CALCULUS_TOLERANCE: f64 = 1e-10;
+const MAX_ITERATION: usize = 200;
+const MAX_SIMPSON_DEPTH: usize = 50;
+
+fn evaluate(callee: &Value, input: Number, scope: &mut Scope) -> Result<Number, ()> {
+    let result = value_invoke(callee, vec![Value::Number(input)], scope)?;
+    let Value::Number(num) = result else {
+        return Err(type_error(
+            Some("callback result"),
+            vec![ValueType::Number],
+            result.get_type(),
+        )?);
+    };
+    Ok(num)
+}
+fn evaluate_f64(callee: &Value, input: f64, scope: &mut Scope) -> Result<f64, ()> {
+    let result = evaluate(callee, Number::Float(input), scope)?;
+    match result {
+        Number::Complex(_, _) => Err(math_error("real number is expected, found Complex number")?),
+        // like: dividing by ZERO in the callback
+        Number::NotANumber => {
+            let msg = format!("the function is not a number at {}", input);
+            Err(math_error(&msg)?)
+        }
+        _ if !result.float_value().is_finite() => {
+            let msg = format!("the function is not finite at {}", input);
+            Err(math_error(&msg)?)
+        }
+        _ => Ok(result.float_value()),
+    }
+}
+
+// central difference with Richardson extrapolation
+fn derivative(callee: &Value, x: f64, scope: &mut Scope) -> Result<f64, ()> {
+    let mut central = |h: f64| -> Result<f64, ()> {
+        let forward = evaluate_f64(callee, x + h, scope)?;
+        let backward = evaluate_f64(callee, x - h, scope)?;
+        Ok((forward - backward) / (2.0 * h))
+    };
+    let h = 1e-3 * x.abs().max(1.0);
+    let coarse = central(h)?;
+    let fine = central(h / 2.0)?;
+    Ok((4.0 * fine - coarse) / 3.0)
+}
+
+// adaptive Simpson's rule
+fn integral(callee: &Value, a: f64, b: f64, scope: &mut Scope) -> Result<f64, ()> {
+    struct Segment {
+        a: f64,
+        b: f64,
+        fa: f64,
+        fm: f64,
+        fb: f64,
+        whole: f64,
+    }
+    fn simpson(a: f64, b: f64, fa: f64, fm: f64, fb: f64) -> f64 {
+        (b - a) / 6.0 * (fa + 4.0 * fm + fb)
+    }
+    fn adapt(
+        callee: &Value,
+        segment: Segment,
+        tolerance: f64,
+        depth: usize,
+        scope: &mut Scope,
+    ) -> Result<f64, ()> {
+        let Segment {
+            a,
+            b,
+            fa,
+            fm,
+            fb,
+            whole,
+        } = segment;
+        let m = (a + b) / 2.0;
+        let lm = (a + m) / 2.0;
+        let rm = (m + b) / 2.0;
+        let flm = evaluate_f64(callee, lm, scope)?;
+        let frm = evaluate_f64(callee, rm, scope)?;
+        let left = simpson(a, m, fa, flm, fm);
+        let right = simpson(m, b, fm, frm, fb);
+
+        let delta = left + right - whole;
+        if delta.abs() <= 15.0 * tolerance {
+            return Ok(left + right + delta / 15.0);
+        }
+        if depth >= MAX_SIMPSON_DEPTH {
+            return Err(math_error("the integral does not converge")?);
+        }
+        let left_segment = Segment {
+            a,
+            b: m,
+            fa,
+            fm: flm,
+            fb: fm,
+            whole: left,
+        };
+        let right_segment = Segment {
+            a: m,
+            b,
+            fa: fm,
+            fm: frm,
+            fb,
+            whole: right,
+        };
+        Ok(
+            adapt(callee, left_segment, tolerance / 2.0, depth + 1, scope)?
+                + adapt(callee, right_segment, tolerance / 2.0, depth + 1, scope)?,
+        )
+    }
+
+    let fa = evaluate_f64(callee, a, scope)?;
+    let fm = evaluate_f64(callee, (a + b) / 2.0, scope)?;
+    let fb = evaluate_f64(callee, b, scope)?;
+    let segment = Segment {
+        a,
+        b,
+        fa,
+        fm,
+        fb,
+        whole: simpson(a, b, fa, fm, fb),
+    };
+    adapt(callee, segment, CALCULUS_TOLERANCE, 0, scope)
+}
+
+// Brent's method in the bracket [a, b]
+fn find_root(callee: &Value, mut a: f64, mut b: f64, scope: &mut Scope) -> Result<f64, ()> {
+    let mut fa = evaluate_f64(callee, a, scope)?;
+    let mut fb = evaluate_f64(callee, b, scope)?;
+    if fa == 0.0 {
+        return Ok(a);
+    }
+    if fb == 0.0 {
+        return Ok(b);
+    }
+    if fa.signum() == fb.signum() {
+        return Err(math_error(
+            "the root is not bracketed, `f(a)` and `f(b)` should have different signs",
+        )?);
+    }
+
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+    for _ in 0..MAX_ITERATION {
+        if fb.signum() == fc.signum() {
+            (c, fc) = (a, fa);
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            (a, fa) = (b, fb);
+            (b, fb) = (c, fc);
+            (c, fc) = (a, fa);
+        }
+
+        let tolerance = 2.0 * f64::EPSILON * b.abs() + CALCULUS_TOLERANCE / 2.0;
+        let middle = (c - b) / 2.0;
+        if middle.abs() <= tolerance || fb == 0.0 {
+            return Ok(b);
+        }
+
+        if e.abs() >= tolerance && fa.abs() > fb.abs() {
+            // inverse quadratic interpolation or secant
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * middle * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * middle * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if 2.0 * p < (3.0 * middle * q - (tolerance * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = middle;
+                e = d;
+            }
+        } else {
+            // bisection
+            d = middle;
+            e = d;
+        }
+
+        (a, fa) = (b, fb);
+        b += if d.abs() > tolerance {
+            d
+        } else {
+            tolerance.copysign(middle)
+        };
+        fb = evaluate_f64(callee, b, scope)?;
+    }
+    Ok(b)
+}
+
+// golden section search in [a, b]
+fn find_minimum(callee: &Value, mut a: f64, mut b: f64, scope: &mut Scope) -> Result<f64, ()> {
+    let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+    let mut x1 = b - ratio * (b - a);
+    let mut x2 = a + ratio * (b - a);
+    let mut f1 = evaluate_f64(callee, x1, scope)?;
+    let mut f2 = evaluate_f64(callee, x2, scope)?;
+
+    for _ in 0..MAX_ITERATION {
+        if (b - a).abs() <= CALCULUS_TOLERANCE * (1.0 + x1.abs()) {
+            break;
+        }
+        if f1 < f2 {
+            b = x2;
+            (x2, f2) = (x1, f1);
+            x1 = b - ratio * (b - a);
+            f1 = evaluate_f64(callee, x1, scope)?;
+        } else {
+            a = x1;
+            (x1, f1) = (x2, f2);
+            x2 = a + ratio * (b - a);
+            f2 = evaluate_f64(callee, x2, scope)?;
+        }
+    }
+    Ok((a + b) / 2.0)
 }
 
 impl ObjectModule for MathModule {
@@ -59,6 +307,10 @@ impl ObjectModule for MathModule {
         let mut sqrt = sin.clone();
         let mut floor = sin.clone();
         let mut round = sin.clone();
+        let mut arg = sin.clone();
+        let mut conj = sin.clone();
+        let mut re = sin.clone();
+        let mut im = sin.clone();
 
         cos.identi = BuildInFnIdenti::Math(Self::COS);
         tan.identi = BuildInFnIdenti::Math(Self::TAN);
@@ -78,6 +330,10 @@ impl ObjectModule for MathModule {
         sqrt.identi = BuildInFnIdenti::Math(Self::SQRT);
         floor.identi = BuildInFnIdenti::Math(Self::FLOOR);
         round.identi = BuildInFnIdenti::Math(Self::ROUND);
+        arg.identi = BuildInFnIdenti::Math(Self::ARG);
+        conj.identi = BuildInFnIdenti::Math(Self::CONJ);
+        re.identi = BuildInFnIdenti::Math(Self::RE);
+        im.identi = BuildInFnIdenti::Math(Self::IM);
 
         let log = BuildInFunction {
             params: vec![
@@ -87,6 +343,45 @@ impl ObjectModule for MathModule {
             ],
             identi: BuildInFnIdenti::Math(Self::LOG),
         };
+        let pow = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "base"),
+                BuildInFnParam(ValueType::Number, "exponent"),
+            ],
+            identi: BuildInFnIdenti::Math(Self::POW),
+        };
+
+        let diff = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "f"),
+                BuildInFnParam(ValueType::Number, "x"),
+            ],
+            identi: BuildInFnIdenti::Math(Self::DIFF),
+        };
+        let integrate = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "f"),
+                BuildInFnParam(ValueType::Number, "a"),
+                BuildInFnParam(ValueType::Number, "b"),
+            ],
+            identi: BuildInFnIdenti::Math(Self::INTEGRATE),
+        };
+        let mut root = integrate.clone();
+        let mut minimize = integrate.clone();
+        root.identi = BuildInFnIdenti::Math(Self::ROOT);
+        minimize.identi = BuildInFnIdenti::Math(Self::MINIMIZE);
+        let sum = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "f"),
+                BuildInFnParam(ValueType::Number, "from"),
+                BuildInFnParam(ValueType::Number, "to"),
+            ],
+            identi: BuildInFnIdenti::Math(Self::SUM),
+        };
 
         let module_obj_props = vec![
             (String::from("sin"), Value::from(sin)),
@@ -109,6 +404,16 @@ impl ObjectModule for MathModule {
             (String::from("sqrt"), Value::from(sqrt)),
             (String::from("floor"), Value::from(floor)),
             (String::from("round"), Value::from(round)),
+            (String::from("arg"), Value::from(arg)),
+            (String::from("conj"), Value::from(conj)),
+            (String::from("re"), Value::from(re)),
+            (String::from("im"), Value::from(im)),
+            (String::from("pow"), Value::from(pow)),
+            (String::from("diff"), Value::from(diff)),
+            (String::from("integrate"), Value::from(integrate)),
+            (String::from("root"), Value::from(root)),
+            (String::from("minimize"), Value::from(minimize)),
+            (String::from("sum"), Value::from(sum)),
         ];
         return Object::new(module_obj_props, None);
     }
@@ -125,6 +430,72 @@ impl BuildInFnCall for MathModule {
                 let nature_f = natural.get_f64()?;
                 nature_f.log(base_f)
             }
+            Self::POW => {
+                let Value::Number(base) = get_val("base", scope)? else {
+                    unreachable!()
+                };
+                let Value::Number(exponent) = get_val("exponent", scope)? else {
+                    unreachable!()
+                };
+                return Ok(Value::Number(Number::pow(base, exponent)));
+            }
+            Self::DIFF => {
+                let callee = get_callee("f", scope)?;
+                let x = get_val("x", scope)?.get_f64()?;
+                derivative(&callee, x, scope)?
+            }
+            Self::INTEGRATE | Self::ROOT | Self::MINIMIZE => {
+                let callee = get_callee("f", scope)?;
+                let a = get_val("a", scope)?.get_f64()?;
+                let b = get_val("b", scope)?.get_f64()?;
+                if !a.is_finite() || !b.is_finite() {
+                    return Err(math_error("the interval bounds should be finite")?);
+                }
+
+                match self {
+                    Self::INTEGRATE => integral(&callee, a, b, scope)?,
+                    Self::ROOT => find_root(&callee, a, b, scope)?,
+                    Self::MINIMIZE => find_minimum(&callee, a.min(b), a.max(b), scope)?,
+                    _ => unreachable!(),
+                }
+            }
+            Self::SUM => {
+                // exact summation over the integers in [from, to]
+                let callee = get_callee("f", scope)?;
+                let from = get_val("from", scope)?.get_i64()?;
+                let to = get_val("to", scope)?.get_i64()?;
+
+                let mut total = Number::Int(0);
+                for index in from..=to {
+                    total = total + evaluate(&callee, Number::Int(index), scope)?;
+                }
+                return Ok(Value::Number(total.simplify()));
+            }
+            Self::SQRT
+            | Self::EXP
+            | Self::LN
+            | Self::ABS
+            | Self::ARG
+            | Self::CONJ
+            | Self::RE
+            | Self::IM => {
+                // functions with complex branches
+                let Value::Number(input) = get_val("input", scope)? else {
+                    unreachable!()
+                };
+                let result = match self {
+                    Self::SQRT => input.sqrt(),
+                    Self::EXP => input.exp(),
+                    Self::LN => input.ln(),
+                    Self::ABS => input.abs(),
+                    Self::ARG => input.arg(),
+                    Self::CONJ => input.conj(),
+                    Self::RE => input.real(),
+                    Self::IM => input.imaginary(),
+                    _ => unreachable!(),
+                };
+                return Ok(Value::Number(result));
+            }
             _ => {
                 let input = get_val("input", scope)?;
                 let f = input.get_f64()?;
@@ -140,10 +511,6 @@ impl BuildInFnCall for MathModule {
                     Self::DEG => f.to_degrees(),
                     Self::LOG10 => f.log10(),
                     Self::LOG2 => f.log2(),
-                    Self::LN => f.ln(),
-                    Self::EXP => f.exp(),
-                    Self::ABS => f.abs(),
-                    Self::SQRT => f.sqrt(),
                     Self::FLOOR => f.floor(),
                     Self::ROUND => f.round(),
 
@@ -0,0 +1,541 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::rc::Rc;
+
+use crossterm::style::Stylize;
+
+use crate::public::env::ENV_OPTION;
+use crate::public::error::{math_error, range_error, type_error};
+use crate::public::run_time::build_in::BuildInFnIdenti;
+use crate::public::run_time::scope::Scope;
+use crate::public::std::utils::get_self_prop::get_self_prop;
+use crate::public::std::{ModuleClass, EMPTY_MODULE_CLASS};
+use crate::public::value::array::{ArrayLiteral, RawArray};
+use crate::public::value::display_indent;
+use crate::public::value::function::{BuildInFnParam, BuildInFunction, Function};
+use crate::public::value::number::Number;
+use crate::public::value::oop::class::{Class, Property};
+use crate::public::value::oop::object::Object;
+use crate::public::value::symbols::Symbols;
+use crate::public::value::value::{Value, ValueType};
+use crate::public::value::GetAddr;
+
+use super::super::utils::get_val::get_val;
+use super::{BuildInFnCall, ClassModule, ObjectModule};
+
+#[derive(PartialEq, Clone)]
+pub enum MatrixModule {
+    // module object functions
+    FROM,
+    IDENTITY,
+    ZEROS,
+
+    // matrix methods
+    ROWS,
+    COLS,
+    TRANSPOSE,
+    DOT,
+    DET,
+    INVERSE,
+    SOLVE,
+}
+
+type Rows = Vec<Vec<Number>>;
+
+// limit of the elements count for `identity` and `zeros`
+const MAX_ELEMENTS: i64 = 1_000_000;
+
+// get rows from nested Array of Numbers, like: `[[1, 2], [3, 4]]`
+fn array_to_rows(value: &Value) -> Option<Rows> {
+    let Value::Array(arr) = value else {
+        return None;
+    };
+    let mut rows = Rows::new();
+    for row_value in arr.borrow().iter() {
+        let Value::Array(row_arr) = row_value else {
+            return None;
+        };
+        let mut row = vec![];
+        for item in row_arr.borrow().iter() {
+            let Value::Number(num) = item else {
+                return None;
+            };
+            row.push(num.clone());
+        }
+        rows.push(row);
+    }
+
+    // the matrix should be rectangular and not empty
+    let cols = rows.first()?.len();
+    if cols == 0 || rows.iter().any(|row| row.len() != cols) {
+        return None;
+    }
+    Some(rows)
+}
+
+fn rows_to_array(rows: Rows) -> Value {
+    let mut arr = ArrayLiteral::new();
+    for row in rows {
+        let row_arr: ArrayLiteral = row.into_iter().map(Value::Number).collect();
+        arr.push_back(Value::from(row_arr));
+    }
+    Value::from(arr)
+}
+
+fn matrix_value(rows: Rows) -> Result<Value, ()> {
+    let params = ArrayLiteral::from([rows_to_array(rows)]);
+    let matrix_obj = Class::instantiate(MatrixModule::module_class(), params)?;
+    return Ok(Value::from(matrix_obj));
+}
+
+fn self_rows(self_value: &Value) -> Result<Rows, ()> {
+    let arr_value = get_self_prop(self_value, "v")?;
+    match array_to_rows(&arr_value) {
+        Some(rows) => Ok(rows),
+        None => Err(math_error(
+            "the matrix should be a rectangular nested Array of Numbers",
+        )?),
+    }
+}
+
+fn shape(rows: &Rows) -> (usize, usize) {
+    (rows.len(), rows[0].len())
+}
+
+fn check_square(rows: &Rows) -> Result<usize, ()> {
+    let (row_count, col_count) = shape(rows);
+    if row_count != col_count {
+        let msg = format!(
+            "square matrix is expected, found {}x{}",
+            row_count, col_count
+        );
+        return Err(math_error(&msg)?);
+    }
+    Ok(row_count)
+}
+
+fn transpose(rows: &Rows) -> Rows {
+    let (row_count, col_count) = shape(rows);
+    (0..col_count)
+        .map(|col| (0..row_count).map(|row| rows[row][col].clone()).collect())
+        .collect()
+}
+
+fn product(rows1: &Rows, rows2: &Rows) -> Result<Rows, ()> {
+    let (row_count, inner) = shape(rows1);
+    let (inner2, col_count) = shape(rows2);
+    if inner != inner2 {
+        let msg = format!(
+            "matrix product of {}x{} and {}x{} is not available",
+            row_count, inner, inner2, col_count
+        );
+        return Err(math_error(&msg)?);
+    }
+
+    let columns = transpose(rows2);
+    let mut result = Rows::new();
+    for row in rows1 {
+        let mut result_row = vec![];
+        for column in &columns {
+            let mut sum = Number::Int(0);
+            for (item1, item2) in row.iter().zip(column) {
+                sum = sum + item1.clone() * item2.clone();
+            }
+            result_row.push(sum.simplify());
+        }
+        result.push(result_row);
+    }
+    Ok(result)
+}
+
+// Gauss-Jordan elimination for `[matrix | augment]`,
+// returns the determinant and the reduced augment part,
+// the reduced part is None if the matrix is singular.
+fn eliminate(mut matrix: Rows, mut augment: Rows) -> (Number, Option<Rows>) {
+    let size = matrix.len();
+    let mut det = Number::Int(1);
+
+    for col in 0..size {
+        // partial pivoting: the row with the max absolute value
+        let pivot_index = (col..size)
+            .max_by(|&row1, &row2| {
+                let abs1 = matrix[row1][col].abs().float_value();
+                let abs2 = matrix[row2][col].abs().float_value();
+                abs1.partial_cmp(&abs2).unwrap_or(Ordering::Equal)
+            })
+            .unwrap();
+        if matrix[pivot_index][col].is_zero() {
+            return (Number::Int(0), None);
+        }
+        if pivot_index != col {
+            matrix.swap(pivot_index, col);
+            augment.swap(pivot_index, col);
+            det = Number::Int(0) - det;
+        }
+
+        let pivot = matrix[col][col].clone();
+        det = det * pivot.clone();
+        for item in matrix[col].iter_mut().chain(augment[col].iter_mut()) {
+            *item = Number::exact_div(item.clone(), pivot.clone());
+        }
+
+        let pivot_row = matrix[col].clone();
+        let pivot_augment = augment[col].clone();
+        for row in 0..size {
+            let factor = matrix[row][col].clone();
+            if row == col || factor.is_zero() {
+                continue;
+            }
+            let targets = matrix[row].iter_mut().chain(augment[row].iter_mut());
+            let sources = pivot_row.iter().chain(pivot_augment.iter());
+            for (target, source) in targets.zip(sources) {
+                *target = (target.clone() - source.clone() * factor.clone()).simplify();
+            }
+        }
+    }
+    (det.simplify(), Some(augment))
+}
+
+fn singular_error() -> Result<(), ()> {
+    math_error("the matrix is singular")
+}
+
+// --- --- --- --- --- ---
+
+static mut MODULE_CLASS: ModuleClass = EMPTY_MODULE_CLASS;
+impl ClassModule for MatrixModule {
+    fn __static_class__() -> Class {
+        let rows = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Object, "self")],
+            identi: BuildInFnIdenti::Matrix(Self::ROWS),
+        };
+        // `rows` as function template
+        let mut cols = rows.clone();
+        let mut transpose = rows.clone();
+        let mut det = rows.clone();
+        let mut inverse = rows.clone();
+        cols.identi = BuildInFnIdenti::Matrix(Self::COLS);
+        transpose.identi = BuildInFnIdenti::Matrix(Self::TRANSPOSE);
+        det.identi = BuildInFnIdenti::Matrix(Self::DET);
+        inverse.identi = BuildInFnIdenti::Matrix(Self::INVERSE);
+
+        let dot = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Object, "other"),
+            ],
+            identi: BuildInFnIdenti::Matrix(Self::DOT),
+        };
+        let solve = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "target"),
+            ],
+            identi: BuildInFnIdenti::Matrix(Self::SOLVE),
+        };
+
+        return Class::new(
+            vec![Property(ValueType::Array, String::from("v"))],
+            vec![
+                (String::from("rows"), Function::from(rows)),
+                (String::from("cols"), Function::from(cols)),
+                (String::from("transpose"), Function::from(transpose)),
+                (String::from("dot"), Function::from(dot)),
+                (String::from("det"), Function::from(det)),
+                (String::from("inverse"), Function::from(inverse)),
+                (String::from("solve"), Function::from(solve)),
+            ],
+        );
+    }
+
+    fn module_class() -> Rc<Class> {
+        let class = unsafe {
+            MODULE_CLASS.none_to_init(Self::__static_class__);
+            MODULE_CLASS.unwrap()
+        };
+        return class;
+    }
+}
+
+impl ObjectModule for MatrixModule {
+    fn module_object() -> Object {
+        let from = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Array, "input"),
+            ],
+            identi: BuildInFnIdenti::Matrix(Self::FROM),
+        };
+        let identity = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "size"),
+            ],
+            identi: BuildInFnIdenti::Matrix(Self::IDENTITY),
+        };
+        let zeros = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "rows"),
+                BuildInFnParam(ValueType::Number, "cols"),
+            ],
+            identi: BuildInFnIdenti::Matrix(Self::ZEROS),
+        };
+
+        let module_obj_props = vec![
+            (String::from("from"), Value::from(from)),
+            (String::from("identity"), Value::from(identity)),
+            (String::from("zeros"), Value::from(zeros)),
+        ];
+        return Object::new(module_obj_props, None);
+    }
+}
+
+impl MatrixModule {
+    pub fn is_matrix(value: &Value) -> bool {
+        let Value::Object(obj) = value else {
+            return false;
+        };
+        return Self::is_matrix_object(&obj.borrow());
+    }
+    pub fn is_matrix_object(obj: &Object) -> bool {
+        let Some(proto) = obj.get_proto() else {
+            return false;
+        };
+        return proto.get_addr() == Self::module_class().get_addr();
+    }
+
+    // element-wise computing with matrix or number
+    pub fn operate(val1: &Value, val2: &Value, operator: Symbols) -> Result<Value, ()> {
+        fn operand_rows(value: &Value) -> Result<Option<Rows>, ()> {
+            match value {
+                Value::Number(_) => Ok(None),
+                _ => Ok(Some(self_rows(value)?)),
+            }
+        }
+        let rows1 = operand_rows(val1)?;
+        let rows2 = operand_rows(val2)?;
+
+        if let Symbols::CompareEqual | Symbols::NotEqual = operator {
+            let is_equal = rows1.is_some() && rows1 == rows2;
+            return Ok(Value::from(is_equal == (operator == Symbols::CompareEqual)));
+        }
+
+        let number_operator: fn(Number, Number) -> Number = match operator {
+            Symbols::Plus => |num1, num2| num1 + num2,
+            Symbols::Minus => |num1, num2| num1 - num2,
+            Symbols::Multiply => |num1, num2| num1 * num2,
+            Symbols::Divide => Number::exact_div,
+            _ => {
+                let msg = format!("the operation {} is not available for Matrix", operator);
+                return Err(math_error(&msg)?);
+            }
+        };
+
+        // regard Number as the matrix with the same shape
+        let (row_count, col_count) = match (&rows1, &rows2) {
+            (Some(rows1), Some(rows2)) => {
+                if shape(rows1) != shape(rows2) {
+                    let (row1, col1) = shape(rows1);
+                    let (row2, col2) = shape(rows2);
+                    let msg = format!(
+                        "shape {}x{} and {}x{} mismatch for {}",
+                        row1, col1, row2, col2, operator
+                    );
+                    return Err(math_error(&msg)?);
+                }
+                shape(rows1)
+            }
+            (Some(rows), None) | (None, Some(rows)) => shape(rows),
+            (None, None) => unreachable!(),
+        };
+        let element = |rows: &Option<Rows>, value: &Value, row: usize, col: usize| match rows {
+            Some(rows) => rows[row][col].clone(),
+            None => {
+                let Value::Number(num) = value else {
+                    unreachable!()
+                };
+                num.clone()
+            }
+        };
+
+        let mut result = Rows::new();
+        for row in 0..row_count {
+            let mut result_row = vec![];
+            for col in 0..col_count {
+                let num1 = element(&rows1, val1, row, col);
+                let num2 = element(&rows2, val2, row, col);
+                result_row.push(number_operator(num1, num2).simplify());
+            }
+            result.push(result_row);
+        }
+        return matrix_value(result);
+    }
+
+    // tabular display, like:
+    // [ 1  2 ]
+    // [ 3  4 ]
+    pub fn display(f: &mut fmt::Formatter<'_>, obj: &Object, level: usize) -> Option<fmt::Result> {
+        let rows = array_to_rows(&obj.get("v").ok()?)?;
+        let cells: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| row.iter().map(|num| num.to_string()).collect())
+            .collect();
+        let (_, col_count) = shape(&rows);
+        let widths: Vec<usize> = (0..col_count)
+            .map(|col| cells.iter().map(|row| row[col].len()).max().unwrap())
+            .collect();
+
+        let mut display_row = |index: usize, row: &Vec<String>| -> fmt::Result {
+            if index > 0 {
+                write!(f, "\r\n{}", display_indent(level - 1))?;
+            }
+            write!(f, "[")?;
+            for (cell, width) in row.iter().zip(&widths) {
+                let padded = format!("{:>width$}", cell, width = width);
+                if unsafe { ENV_OPTION.support_ansi } {
+                    write!(f, " {}", padded.yellow())?;
+                } else {
+                    write!(f, " {}", padded)?;
+                }
+            }
+            write!(f, " ]")
+        };
+        for (index, row) in cells.iter().enumerate() {
+            if let Err(err) = display_row(index, row) {
+                return Some(Err(err));
+            }
+        }
+        Some(Ok(()))
+    }
+}
+
+impl BuildInFnCall for MatrixModule {
+    fn call(&self, scope: &mut Scope) -> Result<Value, ()> {
+        let self_value = get_val("self", scope)?;
+
+        let result = match self {
+            Self::FROM => {
+                let input = get_val("input", scope)?;
+                let Some(rows) = array_to_rows(&input) else {
+                    return Err(math_error(
+                        "the matrix should be a rectangular nested Array of Numbers",
+                    )?);
+                };
+                matrix_value(rows)?
+            }
+            Self::IDENTITY | Self::ZEROS => {
+                let (row_count, col_count) = if *self == Self::IDENTITY {
+                    let size = get_val("size", scope)?.get_i64()?;
+                    (size, size)
+                } else {
+                    let row_count = get_val("rows", scope)?.get_i64()?;
+                    let col_count = get_val("cols", scope)?.get_i64()?;
+                    (row_count, col_count)
+                };
+                if row_count < 1 || col_count < 1 {
+                    return Err(math_error("the size of matrix should be positive")?);
+                }
+                let element_count = row_count.saturating_mul(col_count);
+                if element_count > MAX_ELEMENTS {
+                    let expected = format!("at most {}", MAX_ELEMENTS);
+                    let found = element_count as usize;
+                    return Err(range_error("matrix elements", expected, found)?);
+                }
+
+                let mut rows = vec![vec![Number::Int(0); col_count as usize]; row_count as usize];
+                if *self == Self::IDENTITY {
+                    for (index, row) in rows.iter_mut().enumerate() {
+                        row[index] = Number::Int(1);
+                    }
+                }
+                matrix_value(rows)?
+            }
+
+            Self::ROWS => Value::from(shape(&self_rows(&self_value)?).0 as i64),
+            Self::COLS => Value::from(shape(&self_rows(&self_value)?).1 as i64),
+            Self::TRANSPOSE => matrix_value(transpose(&self_rows(&self_value)?))?,
+            Self::DOT => {
+                let other_value = get_val("other", scope)?;
+                if !Self::is_matrix(&other_value) {
+                    return Err(math_error("Matrix is expected for matrix product")?);
+                }
+                let rows1 = self_rows(&self_value)?;
+                let rows2 = self_rows(&other_value)?;
+                matrix_value(product(&rows1, &rows2)?)?
+            }
+            Self::DET => {
+                let rows = self_rows(&self_value)?;
+                let size = check_square(&rows)?;
+                let (det, _) = eliminate(rows, vec![vec![]; size]);
+                Value::Number(det)
+            }
+            Self::INVERSE => {
+                let rows = self_rows(&self_value)?;
+                let size = check_square(&rows)?;
+                let mut identity = vec![vec![Number::Int(0); size]; size];
+                for (index, row) in identity.iter_mut().enumerate() {
+                    row[index] = Number::Int(1);
+                }
+                let (_, Some(inverse)) = eliminate(rows, identity) else {
+                    return Err(singular_error()?);
+                };
+                matrix_value(inverse)?
+            }
+            Self::SOLVE => {
+                // solve `A x = b`, the `b` can be Array or Matrix
+                let rows = self_rows(&self_value)?;
+                let size = check_square(&rows)?;
+                let target = get_val("target", scope)?;
+
+                let is_vector = matches!(target, Value::Array(_)) && !Self::is_matrix(&target);
+                let augment = match &target {
+                    Value::Array(arr) => {
+                        let mut augment = Rows::new();
+                        for item in arr.borrow().iter() {
+                            let Value::Number(num) = item else {
+                                return Err(type_error(
+                                    Some("Matrix.solve"),
+                                    vec![ValueType::Number],
+                                    item.get_type(),
+                                )?);
+                            };
+                            augment.push(vec![num.clone()]);
+                        }
+                        augment
+                    }
+                    _ if Self::is_matrix(&target) => self_rows(&target)?,
+                    _ => {
+                        return Err(type_error(
+                            Some("Matrix.solve"),
+                            vec![ValueType::Array, ValueType::Object],
+                            target.get_type(),
+                        )?)
+                    }
+                };
+                if augment.len() != size {
+                    let msg = format!(
+                        "the target should have {} rows, found {}",
+                        size,
+                        augment.len()
+                    );
+                    return Err(math_error(&msg)?);
+                }
+
+                let (_, Some(solution)) = eliminate(rows, augment) else {
+                    return Err(singular_error()?);
+                };
+                if is_vector {
+                    let arr: ArrayLiteral = solution
+                        .into_iter()
+                        .map(|mut row| Value::Number(row.remove(0)))
+                        .collect();
+                    Value::from(RawArray::from(arr))
+                } else {
+                    matrix_value(solution)?
+                }
+            }
+        };
+        return Ok(result);
+    }
+}
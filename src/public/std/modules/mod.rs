@@ -1,10 +1,19 @@
 pub mod array;
 pub mod basic;
 pub mod bit_ops;
+pub mod csv;
 pub mod file_system;
+pub mod json;
 pub mod map;
 pub mod math;
+pub mod matrix;
+pub mod poly;
+pub mod random;
+pub mod regex;
+pub mod set;
+pub mod stats;
 pub mod string;
+pub mod time;
 
 use std::rc::Rc;
 
@@ -28,12 +37,24 @@ pub fn import_resolver(scope: &mut Scope, target_module: &StdModules, module_nam
             scope.global.variables.extend(fn_list);
         }
 
-        StdModules::FileSystem | StdModules::Math => {
+        StdModules::FileSystem
+        | StdModules::Math
+        | StdModules::Matrix
+        | StdModules::Stats
+        | StdModules::Random
+        | StdModules::Json
+        | StdModules::Csv
+        | StdModules::Time => {
             let module_obj = target_module.get_obj_entry();
             scope.assign(String::from(module_name), Value::from(module_obj));
         }
 
-        StdModules::String | StdModules::Array | StdModules::Map => {
+        StdModules::String
+        | StdModules::Array
+        | StdModules::Map
+        | StdModules::Poly
+        | StdModules::Regex
+        | StdModules::Set => {
             let module_cls = target_module.get_cls_entry();
             scope.assign(String::from(module_name), Value::Class(module_cls));
         }
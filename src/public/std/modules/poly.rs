@@ -0,0 +1,626 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::public::error::{math_error, range_error, type_error};
+use crate::public::run_time::build_in::BuildInFnIdenti;
+use crate::public::run_time::scope::Scope;
+use crate::public::std::utils::get_self_prop::get_self_prop;
+use crate::public::std::{ModuleClass, EMPTY_MODULE_CLASS};
+use crate::public::value::array::{ArrayLiteral, RawArray};
+use crate::public::value::big_int::BigInt;
+use crate::public::value::function::{BuildInFnParam, BuildInFunction, Function};
+use crate::public::value::number::{complex_div, complex_mul, ComplexPair, Number};
+use crate::public::value::oop::class::{Class, Property};
+use crate::public::value::oop::object::Object;
+use crate::public::value::symbols::Symbols;
+use crate::public::value::value::{Value, ValueType};
+use crate::public::value::GetAddr;
+
+use super::super::utils::get_val::get_val;
+use super::{BuildInFnCall, ClassModule};
+
+#[derive(PartialEq, Clone)]
+pub enum PolyModule {
+    EVAL,
+    DEGREE,
+    DERIVATIVE,
+    INTEGRAL,
+    DIVMOD,
+    ROOTS,
+}
+
+// coefficients in ascending order of power,
+// while the `v` property is in descending order, like: `[1, -3, 2]` for `x^2 - 3x + 2`.
+type Coeffs = Vec<Number>;
+
+// the max degree of the Poly power result
+const MAX_POWER_DEGREE: i64 = 1_000;
+
+// remove ZERO coefficients of the highest powers
+fn trim(mut coeffs: Coeffs) -> Coeffs {
+    while coeffs.last().is_some_and(|coeff| coeff.is_zero()) {
+        coeffs.pop();
+    }
+    coeffs
+}
+
+fn array_to_coeffs(value: &Value) -> Option<Coeffs> {
+    let Value::Array(arr) = value else {
+        return None;
+    };
+    let mut coeffs = Coeffs::new();
+    for item in arr.borrow().iter().rev() {
+        let Value::Number(num) = item else {
+            return None;
+        };
+        coeffs.push(num.clone());
+    }
+    Some(trim(coeffs))
+}
+
+fn poly_value(coeffs: Coeffs) -> Result<Value, ()> {
+    let arr: ArrayLiteral = trim(coeffs).into_iter().rev().map(Value::Number).collect();
+    let params = ArrayLiteral::from([Value::from(arr)]);
+    let poly_obj = Class::instantiate(PolyModule::module_class(), params)?;
+    return Ok(Value::from(poly_obj));
+}
+
+fn self_coeffs(self_value: &Value) -> Result<Coeffs, ()> {
+    let arr_value = get_self_prop(self_value, "v")?;
+    match array_to_coeffs(&arr_value) {
+        Some(coeffs) => Ok(coeffs),
+        None => Err(math_error("the polynomial should be an Array of Numbers")?),
+    }
+}
+
+// Poly or Number as coefficients
+fn operand_coeffs(value: &Value) -> Result<Coeffs, ()> {
+    match value {
+        Value::Number(num) => Ok(trim(vec![num.clone()])),
+        _ if PolyModule::is_poly(value) => self_coeffs(value),
+        _ => Err(type_error(
+            Some("Poly operand"),
+            vec![ValueType::Number, ValueType::Object],
+            value.get_type(),
+        )?),
+    }
+}
+
+// --- --- --- --- --- ---
+
+fn add(coeffs1: &Coeffs, coeffs2: &Coeffs) -> Coeffs {
+    let size = coeffs1.len().max(coeffs2.len());
+    let zero = Number::Int(0);
+    let result = (0..size)
+        .map(|power| {
+            let coeff1 = coeffs1.get(power).unwrap_or(&zero).clone();
+            let coeff2 = coeffs2.get(power).unwrap_or(&zero).clone();
+            (coeff1 + coeff2).simplify()
+        })
+        .collect();
+    trim(result)
+}
+fn scale(coeffs: &Coeffs, factor: &Number) -> Coeffs {
+    let result = coeffs
+        .iter()
+        .map(|coeff| (coeff.clone() * factor.clone()).simplify())
+        .collect();
+    trim(result)
+}
+fn multiply(coeffs1: &Coeffs, coeffs2: &Coeffs) -> Coeffs {
+    if coeffs1.is_empty() || coeffs2.is_empty() {
+        return Coeffs::new();
+    }
+    let mut result = vec![Number::Int(0); coeffs1.len() + coeffs2.len() - 1];
+    for (power1, coeff1) in coeffs1.iter().enumerate() {
+        for (power2, coeff2) in coeffs2.iter().enumerate() {
+            let target = &mut result[power1 + power2];
+            *target = (target.clone() + coeff1.clone() * coeff2.clone()).simplify();
+        }
+    }
+    trim(result)
+}
+
+// polynomial long division, returns the quotient and the remainder
+fn divide(dividend: &Coeffs, divisor: &Coeffs) -> Result<(Coeffs, Coeffs), ()> {
+    let Some(leading) = divisor.last() else {
+        return Err(math_error("the divisor polynomial should not be ZERO")?);
+    };
+    let divisor_degree = divisor.len() - 1;
+    let mut remainder = dividend.clone();
+    if remainder.len() < divisor.len() {
+        return Ok((Coeffs::new(), remainder));
+    }
+
+    let mut quotient = vec![Number::Int(0); remainder.len() - divisor_degree];
+    for power in (0..quotient.len()).rev() {
+        let factor = Number::exact_div(remainder[power + divisor_degree].clone(), leading.clone());
+        for (offset, coeff) in divisor.iter().enumerate() {
+            let target = &mut remainder[power + offset];
+            *target = (target.clone() - factor.clone() * coeff.clone()).simplify();
+        }
+        quotient[power] = factor;
+    }
+    remainder.truncate(divisor_degree);
+    Ok((trim(quotient), trim(remainder)))
+}
+
+// Horner's method
+fn evaluate(coeffs: &Coeffs, x: &Number) -> Number {
+    let mut result = Number::Int(0);
+    for coeff in coeffs.iter().rev() {
+        result = (result * x.clone() + coeff.clone()).simplify();
+    }
+    result
+}
+
+fn derivative(coeffs: &Coeffs) -> Coeffs {
+    let result = coeffs
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(power, coeff)| (coeff.clone() * Number::Int(power as i64)).simplify())
+        .collect();
+    trim(result)
+}
+fn integral(coeffs: &Coeffs) -> Coeffs {
+    let mut result = vec![Number::Int(0)];
+    for (power, coeff) in coeffs.iter().enumerate() {
+        result.push(Number::exact_div(
+            coeff.clone(),
+            Number::Int(power as i64 + 1),
+        ));
+    }
+    trim(result)
+}
+
+// --- --- --- --- --- ---
+
+fn is_rational(num: &Number) -> bool {
+    matches!(
+        num,
+        Number::Int(_) | Number::BigInt(_) | Number::Fraction(_, _)
+    )
+}
+
+// positive divisors of the leading coefficient of the integer scaled polynomial,
+// which are the possible denominators of rational roots.
+fn leading_divisors(coeffs: &Coeffs) -> Option<Vec<i64>> {
+    // the largest leading coefficient to be factored
+    const MAX_LEADING: i64 = 1_000_000_000_000;
+
+    let mut lcm = BigInt::one();
+    for coeff in coeffs {
+        if let Number::Fraction(_, lower) = coeff {
+            let gcd = lcm.gcd(lower);
+            lcm = (&lcm * lower).div_rem(&gcd).0;
+        }
+    }
+    let leading = (coeffs.last()?.clone() * Number::from_big(lcm)).simplify();
+    let Number::Int(leading) = leading.abs() else {
+        return None;
+    };
+    if leading > MAX_LEADING {
+        return None;
+    }
+
+    let mut divisors = vec![];
+    let mut factor = 1;
+    while factor * factor <= leading {
+        if leading % factor == 0 {
+            divisors.push(factor);
+            if factor * factor != leading {
+                divisors.push(leading / factor);
+            }
+        }
+        factor += 1;
+    }
+    divisors.sort();
+    Some(divisors)
+}
+
+fn complex_evaluate(coeffs: &[ComplexPair], x: ComplexPair) -> ComplexPair {
+    let mut result = (0.0, 0.0);
+    for coeff in coeffs.iter().rev() {
+        let product = complex_mul(result, x);
+        result = (product.0 + coeff.0, product.1 + coeff.1);
+    }
+    result
+}
+fn modulus((real, imaginary): ComplexPair) -> f64 {
+    real.hypot(imaginary)
+}
+
+// all complex roots by Durand-Kerner iteration, then polished by Newton's method
+fn numeric_roots(coeffs: &Coeffs) -> Vec<ComplexPair> {
+    const MAX_ITERATIONS: usize = 1000;
+    const TOLERANCE: f64 = 1e-15;
+
+    let degree = coeffs.len() - 1;
+    let leading = coeffs[degree].complex_pair();
+    let monic: Vec<ComplexPair> = coeffs
+        .iter()
+        .map(|coeff| complex_div(coeff.complex_pair(), leading))
+        .collect();
+
+    // initial guesses on the circle of Cauchy bound
+    let radius = 1.0
+        + monic[..degree]
+            .iter()
+            .map(|&coeff| modulus(coeff))
+            .fold(0.0, f64::max);
+    let mut roots: Vec<ComplexPair> = (0..degree)
+        .map(|index| {
+            let angle = 2.0 * std::f64::consts::PI * index as f64 / degree as f64 + 0.4;
+            (radius * angle.cos(), radius * angle.sin())
+        })
+        .collect();
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut max_step: f64 = 0.0;
+        for index in 0..degree {
+            let current = roots[index];
+            let mut denominator = (1.0, 0.0);
+            for (other_index, other) in roots.iter().enumerate() {
+                if other_index != index {
+                    denominator =
+                        complex_mul(denominator, (current.0 - other.0, current.1 - other.1));
+                }
+            }
+            if modulus(denominator) == 0.0 {
+                continue;
+            }
+            let step = complex_div(complex_evaluate(&monic, current), denominator);
+            roots[index] = (current.0 - step.0, current.1 - step.1);
+            max_step = max_step.max(modulus(step) / (1.0 + modulus(current)));
+        }
+        if max_step < TOLERANCE {
+            break;
+        }
+    }
+
+    let slope_coeffs: Vec<ComplexPair> = monic
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(power, coeff)| (coeff.0 * power as f64, coeff.1 * power as f64))
+        .collect();
+    for root in &mut roots {
+        for _ in 0..3 {
+            let value = complex_evaluate(&monic, *root);
+            let slope = complex_evaluate(&slope_coeffs, *root);
+            if modulus(slope) == 0.0 {
+                break;
+            }
+            let step = complex_div(value, slope);
+            let polished = (root.0 - step.0, root.1 - step.1);
+            // accept only the improving step
+            if modulus(complex_evaluate(&monic, polished)) >= modulus(value) {
+                break;
+            }
+            *root = polished;
+        }
+        // drop the rounding noise of both parts
+        if root.1.abs() <= 1e-10 * (1.0 + root.0.abs()) {
+            root.1 = 0.0;
+        }
+        if root.0.abs() <= 1e-10 * (1.0 + root.1.abs()) {
+            root.0 = 0.0;
+        }
+    }
+    roots
+}
+
+// rational roots are found exactly, others are computed numerically
+fn roots(coeffs: &Coeffs) -> Result<Vec<Number>, ()> {
+    if coeffs.is_empty() {
+        return Err(math_error("every number is the root of ZERO polynomial")?);
+    }
+    let mut coeffs = coeffs.clone();
+    let mut result = Vec::<Number>::new();
+
+    while coeffs.len() > 1 && coeffs[0].is_zero() {
+        coeffs.remove(0);
+        result.push(Number::Int(0));
+    }
+
+    // approach the numeric roots with the rational `p / q`,
+    // the `q` is the divisor of the leading coefficient.
+    if coeffs.iter().all(is_rational) {
+        'search: while coeffs.len() > 2 {
+            let Some(divisors) = leading_divisors(&coeffs) else {
+                break;
+            };
+            for (real, imaginary) in numeric_roots(&coeffs) {
+                if imaginary.abs() > 1e-3 * (1.0 + real.abs()) {
+                    continue;
+                }
+                for divisor in &divisors {
+                    let upper = (real * *divisor as f64).round();
+                    if upper.abs() >= i64::MAX as f64 {
+                        continue;
+                    }
+                    let candidate =
+                        Number::exact_div(Number::Int(upper as i64), Number::Int(*divisor));
+                    if evaluate(&coeffs, &candidate).is_zero() {
+                        let factor = vec![Number::Int(0) - candidate.clone(), Number::Int(1)];
+                        coeffs = divide(&coeffs, &factor)?.0;
+                        result.push(candidate);
+                        continue 'search;
+                    }
+                }
+            }
+            break;
+        }
+    }
+
+    match coeffs.len() - 1 {
+        0 => {}
+        1 => result.push(Number::exact_div(
+            Number::Int(0) - coeffs[0].clone(),
+            coeffs[1].clone(),
+        )),
+        2 => {
+            // quadratic formula
+            let (c, b, a) = (&coeffs[0], &coeffs[1], &coeffs[2]);
+            let discriminant = b.clone() * b.clone() - Number::Int(4) * a.clone() * c.clone();
+            let root = discriminant.sqrt();
+            let double_a = Number::Int(2) * a.clone();
+            let negative_b = Number::Int(0) - b.clone();
+            result.push((negative_b.clone() - root.clone()) / double_a.clone());
+            result.push((negative_b + root) / double_a);
+        }
+        _ => {
+            for (real, imaginary) in numeric_roots(&coeffs) {
+                result.push(Number::complex(real, imaginary));
+            }
+        }
+    }
+
+    result.sort_by(|num1, num2| {
+        let (real1, imaginary1) = num1.complex_pair();
+        let (real2, imaginary2) = num2.complex_pair();
+        real1.partial_cmp(&real2).unwrap_or(Ordering::Equal).then(
+            imaginary1
+                .partial_cmp(&imaginary2)
+                .unwrap_or(Ordering::Equal),
+        )
+    });
+    Ok(result)
+}
+
+// polynomial in `x` notation, like: `x^2 - 3x + 2`
+fn poly_text(coeffs: &Coeffs) -> String {
+    let mut text = String::new();
+    for (power, coeff) in coeffs.iter().enumerate().rev() {
+        if coeff.is_zero() {
+            continue;
+        }
+        let is_negative = !matches!(coeff, Number::Complex(_, _)) && coeff.float_value() < 0.0;
+        let magnitude = if is_negative {
+            coeff.abs()
+        } else {
+            coeff.clone()
+        };
+
+        if text.is_empty() {
+            if is_negative {
+                text.push('-');
+            }
+        } else {
+            text.push_str(if is_negative { " - " } else { " + " });
+        }
+
+        let coeff_text = match magnitude {
+            Number::Int(1) if power > 0 => String::new(),
+            Number::Complex(_, _) => format!("({})", magnitude),
+            _ => magnitude.to_string(),
+        };
+        let term = match power {
+            0 => coeff_text,
+            1 => format!("{}x", coeff_text),
+            _ => format!("{}x^{}", coeff_text, power),
+        };
+        text.push_str(&term);
+    }
+    if text.is_empty() {
+        text.push('0');
+    }
+    text
+}
+
+// --- --- --- --- --- ---
+
+static mut MODULE_CLASS: ModuleClass = EMPTY_MODULE_CLASS;
+impl ClassModule for PolyModule {
+    fn __static_class__() -> Class {
+        let degree = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Object, "self")],
+            identi: BuildInFnIdenti::Poly(Self::DEGREE),
+        };
+        // `degree` as function template
+        let mut derivative = degree.clone();
+        let mut integral = degree.clone();
+        let mut roots = degree.clone();
+        derivative.identi = BuildInFnIdenti::Poly(Self::DERIVATIVE);
+        integral.identi = BuildInFnIdenti::Poly(Self::INTEGRAL);
+        roots.identi = BuildInFnIdenti::Poly(Self::ROOTS);
+
+        let eval = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "x"),
+            ],
+            identi: BuildInFnIdenti::Poly(Self::EVAL),
+        };
+        let divmod = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "divisor"),
+            ],
+            identi: BuildInFnIdenti::Poly(Self::DIVMOD),
+        };
+
+        return Class::new(
+            vec![Property(ValueType::Array, String::from("v"))],
+            vec![
+                (String::from("eval"), Function::from(eval)),
+                (String::from("degree"), Function::from(degree)),
+                (String::from("derivative"), Function::from(derivative)),
+                (String::from("integral"), Function::from(integral)),
+                (String::from("divmod"), Function::from(divmod)),
+                (String::from("roots"), Function::from(roots)),
+            ],
+        );
+    }
+
+    fn module_class() -> Rc<Class> {
+        let class = unsafe {
+            MODULE_CLASS.none_to_init(Self::__static_class__);
+            MODULE_CLASS.unwrap()
+        };
+        return class;
+    }
+}
+
+impl PolyModule {
+    pub fn is_poly(value: &Value) -> bool {
+        let Value::Object(obj) = value else {
+            return false;
+        };
+        return Self::is_poly_object(&obj.borrow());
+    }
+    pub fn is_poly_object(obj: &Object) -> bool {
+        let Some(proto) = obj.get_proto() else {
+            return false;
+        };
+        return proto.get_addr() == Self::module_class().get_addr();
+    }
+
+    // computing with polynomial or number
+    pub fn operate(val1: &Value, val2: &Value, operator: Symbols) -> Result<Value, ()> {
+        let coeffs1 = operand_coeffs(val1)?;
+
+        if operator == Symbols::Power {
+            let exponent = match val2 {
+                Value::Number(Number::Int(exponent)) if *exponent >= 0 => *exponent,
+                _ => {
+                    return Err(math_error(
+                        "the exponent of Poly should be a non-negative Int",
+                    )?)
+                }
+            };
+            let degree = (coeffs1.len() as i64 - 1).max(0).saturating_mul(exponent);
+            if degree > MAX_POWER_DEGREE {
+                return Err(range_error(
+                    "Poly power degree",
+                    format!("at most {}", MAX_POWER_DEGREE),
+                    degree as usize,
+                )?);
+            }
+
+            // exponentiation by squaring
+            let (mut result, mut base, mut exponent) = (vec![Number::Int(1)], coeffs1, exponent);
+            while exponent > 0 {
+                if exponent % 2 == 1 {
+                    result = multiply(&result, &base);
+                }
+                exponent /= 2;
+                if exponent > 0 {
+                    base = multiply(&base, &base);
+                }
+            }
+            return poly_value(result);
+        }
+
+        let coeffs2 = operand_coeffs(val2)?;
+        let result = match operator {
+            Symbols::CompareEqual => return Ok(Value::from(coeffs1 == coeffs2)),
+            Symbols::NotEqual => return Ok(Value::from(coeffs1 != coeffs2)),
+
+            Symbols::Plus => add(&coeffs1, &coeffs2),
+            Symbols::Minus => add(&coeffs1, &scale(&coeffs2, &Number::Int(-1))),
+            Symbols::Multiply => multiply(&coeffs1, &coeffs2),
+            Symbols::Divide => {
+                let Value::Number(divisor) = val2 else {
+                    return Err(math_error(
+                        "use `//` and `%` for the division of polynomials",
+                    )?);
+                };
+                if divisor.is_zero() {
+                    return Err(math_error("the divisor should not to be ZERO")?);
+                }
+                let result = coeffs1
+                    .iter()
+                    .map(|coeff| Number::exact_div(coeff.clone(), divisor.clone()))
+                    .collect();
+                trim(result)
+            }
+            Symbols::FloorDivide => divide(&coeffs1, &coeffs2)?.0,
+            Symbols::Mod => divide(&coeffs1, &coeffs2)?.1,
+            _ => {
+                let msg = format!("the operation {} is not available for Poly", operator);
+                return Err(math_error(&msg)?);
+            }
+        };
+        poly_value(result)
+    }
+
+    pub fn display(f: &mut fmt::Formatter<'_>, obj: &Object) -> Option<fmt::Result> {
+        let coeffs = array_to_coeffs(&obj.get("v").ok()?)?;
+        Some(write!(f, "{}", poly_text(&coeffs)))
+    }
+}
+
+impl BuildInFnCall for PolyModule {
+    fn call(&self, scope: &mut Scope) -> Result<Value, ()> {
+        let self_value = get_val("self", scope)?;
+        let coeffs = self_coeffs(&self_value)?;
+
+        let result = match self {
+            Self::EVAL => {
+                // evaluate at a Number or each Number of Array
+                let x = get_val("x", scope)?;
+                match &x {
+                    Value::Number(num) => Value::Number(evaluate(&coeffs, num)),
+                    Value::Array(arr) => {
+                        let mut results = ArrayLiteral::new();
+                        for item in arr.borrow().iter() {
+                            let Value::Number(num) = item else {
+                                return Err(type_error(
+                                    Some("Poly.eval"),
+                                    vec![ValueType::Number],
+                                    item.get_type(),
+                                )?);
+                            };
+                            results.push_back(Value::Number(evaluate(&coeffs, num)));
+                        }
+                        Value::from(RawArray::from(results))
+                    }
+                    _ => {
+                        return Err(type_error(
+                            Some("Poly.eval"),
+                            vec![ValueType::Number, ValueType::Array],
+                            x.get_type(),
+                        )?)
+                    }
+                }
+            }
+            Self::DEGREE => Value::from(coeffs.len().saturating_sub(1) as i64),
+            Self::DERIVATIVE => poly_value(derivative(&coeffs))?,
+            Self::INTEGRAL => poly_value(integral(&coeffs))?,
+            Self::DIVMOD => {
+                let divisor = operand_coeffs(&get_val("divisor", scope)?)?;
+                let (quotient, remainder) = divide(&coeffs, &divisor)?;
+                let pair = ArrayLiteral::from([poly_value(quotient)?, poly_value(remainder)?]);
+                Value::from(pair)
+            }
+            Self::ROOTS => {
+                let roots: ArrayLiteral = roots(&coeffs)?.into_iter().map(Value::Number).collect();
+                Value::from(roots)
+            }
+        };
+        Ok(result)
+    }
+}
@@ -0,0 +1,264 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::public::env::ENV_OPTION;
+use crate::public::error::{math_error, range_error};
+use crate::public::run_time::build_in::BuildInFnIdenti;
+use crate::public::run_time::scope::Scope;
+use crate::public::value::array::{ArrayLiteral, RawArray};
+use crate::public::value::function::{BuildInFnParam, BuildInFunction};
+use crate::public::value::oop::object::Object;
+use crate::public::value::value::{Value, ValueType};
+
+use super::super::utils::get_val::get_val;
+use super::{BuildInFnCall, ObjectModule};
+
+// xoshiro256** generator, the state is expanded from seed by splitmix64.
+struct Generator([u64; 4]);
+
+impl Generator {
+    fn from_seed(seed: u64) -> Self {
+        let mut mixer = seed;
+        let mut split_mix = || {
+            mixer = mixer.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = mixer;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^ (z >> 31)
+        };
+        Self([split_mix(), split_mix(), split_mix(), split_mix()])
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let state = &mut self.0;
+        let result = state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let temp = state[1] << 17;
+        state[2] ^= state[0];
+        state[3] ^= state[1];
+        state[1] ^= state[2];
+        state[0] ^= state[3];
+        state[2] ^= temp;
+        state[3] = state[3].rotate_left(45);
+        result
+    }
+
+    // uniform float in [0, 1) with 53 random bits
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    // uniform integer in [0, bound), rejection sampling avoids modulo bias
+    fn next_below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+}
+
+static mut GENERATOR: Option<Generator> = None;
+
+// the generator is seeded by `--seed` option or the current time
+fn generator() -> &'static mut Generator {
+    unsafe {
+        if GENERATOR.is_none() {
+            let seed = match ENV_OPTION.seed {
+                Some(seed) => seed,
+                None => SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_nanos() as u64)
+                    .unwrap_or(0),
+            };
+            GENERATOR = Some(Generator::from_seed(seed));
+        }
+        GENERATOR.as_mut().unwrap()
+    }
+}
+
+fn get_array(scope: &mut Scope) -> Result<ArrayLiteral, ()> {
+    let Value::Array(arr) = get_val("input", scope)? else {
+        unreachable!()
+    };
+    let elements: ArrayLiteral = arr.borrow().iter().cloned().collect();
+    Ok(elements)
+}
+
+// --- --- --- --- --- ---
+
+#[derive(PartialEq, Clone)]
+pub enum RandomModule {
+    RAND,
+    INT,
+    CHOICE,
+    SHUFFLE,
+    SAMPLE,
+    NORMAL,
+    EXPONENTIAL,
+    SEED,
+}
+
+impl ObjectModule for RandomModule {
+    fn module_object() -> Object {
+        let rand = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Object, "self")],
+            identi: BuildInFnIdenti::Random(Self::RAND),
+        };
+        let int = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "lo"),
+                BuildInFnParam(ValueType::Number, "hi"),
+            ],
+            identi: BuildInFnIdenti::Random(Self::INT),
+        };
+
+        let choice = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Array, "input"),
+            ],
+            identi: BuildInFnIdenti::Random(Self::CHOICE),
+        };
+        let mut shuffle = choice.clone();
+        shuffle.identi = BuildInFnIdenti::Random(Self::SHUFFLE);
+        let sample = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Array, "input"),
+                BuildInFnParam(ValueType::Number, "count"),
+            ],
+            identi: BuildInFnIdenti::Random(Self::SAMPLE),
+        };
+
+        let normal = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "mean"),
+                BuildInFnParam(ValueType::Number, "stddev"),
+            ],
+            identi: BuildInFnIdenti::Random(Self::NORMAL),
+        };
+        let exponential = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "rate"),
+            ],
+            identi: BuildInFnIdenti::Random(Self::EXPONENTIAL),
+        };
+        let seed = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "seed"),
+            ],
+            identi: BuildInFnIdenti::Random(Self::SEED),
+        };
+
+        let module_obj_props = vec![
+            (String::from("rand"), Value::from(rand)),
+            (String::from("int"), Value::from(int)),
+            (String::from("choice"), Value::from(choice)),
+            (String::from("shuffle"), Value::from(shuffle)),
+            (String::from("sample"), Value::from(sample)),
+            (String::from("normal"), Value::from(normal)),
+            (String::from("exponential"), Value::from(exponential)),
+            (String::from("seed"), Value::from(seed)),
+        ];
+        return Object::new(module_obj_props, None);
+    }
+}
+
+impl BuildInFnCall for RandomModule {
+    fn call(&self, scope: &mut Scope) -> Result<Value, ()> {
+        let result = match self {
+            Self::RAND => Value::from(generator().next_f64()),
+            Self::INT => {
+                // uniform integer in [lo, hi]
+                let lo = get_val("lo", scope)?.get_i64()?;
+                let hi = get_val("hi", scope)?.get_i64()?;
+                if lo > hi {
+                    let msg = format!("the lower bound {} is greater than {}", lo, hi);
+                    return Err(math_error(&msg)?);
+                }
+                let span = hi.abs_diff(lo);
+                let offset = match span.checked_add(1) {
+                    Some(bound) => generator().next_below(bound),
+                    None => generator().next_u64(),
+                };
+                Value::from(lo.wrapping_add(offset as i64))
+            }
+
+            Self::CHOICE => {
+                let elements = get_array(scope)?;
+                if elements.is_empty() {
+                    return Err(math_error("cannot choose from an empty Array")?);
+                }
+                let index = generator().next_below(elements.len() as u64) as usize;
+                elements[index].clone()
+            }
+            Self::SHUFFLE => {
+                // Fisher-Yates shuffle in place
+                let arr_value = get_val("input", scope)?;
+                let Value::Array(arr) = &arr_value else {
+                    unreachable!()
+                };
+                let mut arr_ref = arr.borrow_mut();
+                for index in (1..arr_ref.len()).rev() {
+                    let target = generator().next_below(index as u64 + 1) as usize;
+                    let temp = arr_ref[index].clone();
+                    arr_ref[index] = arr_ref[target].clone();
+                    arr_ref[target] = temp;
+                }
+                drop(arr_ref);
+                arr_value
+            }
+            Self::SAMPLE => {
+                // `count` distinct positions without replacement
+                let mut elements = get_array(scope)?;
+                let count = get_val("count", scope)?.get_i64()?;
+                if count < 0 {
+                    return Err(math_error("the sample count should not be negative")?);
+                }
+                if count as usize > elements.len() {
+                    return Err(range_error(
+                        "sample count",
+                        format!("at most {}", elements.len()),
+                        count as usize,
+                    )?);
+                }
+                let mut result = ArrayLiteral::new();
+                for _ in 0..count {
+                    let index = generator().next_below(elements.len() as u64) as usize;
+                    result.push_back(elements.swap_remove_back(index).unwrap());
+                }
+                Value::from(RawArray::from(result))
+            }
+
+            Self::NORMAL => {
+                // Box-Muller transform
+                let mean = get_val("mean", scope)?.get_f64()?;
+                let stddev = get_val("stddev", scope)?.get_f64()?;
+                if stddev < 0.0 {
+                    return Err(math_error("the standard deviation should not be negative")?);
+                }
+                let radius = (-2.0 * (1.0 - generator().next_f64()).ln()).sqrt();
+                let angle = 2.0 * std::f64::consts::PI * generator().next_f64();
+                Value::from(mean + stddev * radius * angle.cos())
+            }
+            Self::EXPONENTIAL => {
+                let rate = get_val("rate", scope)?.get_f64()?;
+                if rate <= 0.0 {
+                    return Err(math_error("the rate should be positive")?);
+                }
+                Value::from(-(1.0 - generator().next_f64()).ln() / rate)
+            }
+            Self::SEED => {
+                let seed = get_val("seed", scope)?.get_i64()?;
+                unsafe { GENERATOR = Some(Generator::from_seed(seed as u64)) };
+                Value::EMPTY
+            }
+        };
+        return Ok(result);
+    }
+}
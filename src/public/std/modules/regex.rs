@@ -0,0 +1,360 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::public::error::syntax_error;
+use crate::public::run_time::build_in::BuildInFnIdenti;
+use crate::public::run_time::scope::Scope;
+use crate::public::std::utils::get_self_prop::get_self_prop;
+use crate::public::std::utils::regex::{Captures, PatternError, Regex};
+use crate::public::std::{ModuleClass, EMPTY_MODULE_CLASS};
+use crate::public::value::array::ArrayLiteral;
+use crate::public::value::function::{BuildInFnParam, BuildInFunction, Function};
+use crate::public::value::map::{InternalMap, RawMap};
+use crate::public::value::oop::class::{Class, Property};
+use crate::public::value::oop::object::Object;
+use crate::public::value::value::{Value, ValueType};
+
+use super::super::utils::get_val::get_val;
+use super::{BuildInFnCall, ClassModule};
+
+#[derive(PartialEq, Clone)]
+pub enum RegexModule {
+    COMPILE,
+    ISMATCH,
+    FIND,
+    FINDALL,
+    CAPTURES,
+    NAMEDCAPTURES,
+    REPLACE,
+    SPLIT,
+}
+
+// the max count of the cached patterns, the cache is cleared when it is full
+const MAX_CACHED_PATTERNS: usize = 64;
+
+thread_local! {
+    // compiled patterns, shared by the Regex objects with the same pattern
+    static PATTERN_CACHE: RefCell<HashMap<String, Rc<Regex>>> = RefCell::new(HashMap::new());
+}
+
+fn compile(pattern: &str) -> Result<Rc<Regex>, ()> {
+    if let Some(regex) = PATTERN_CACHE.with(|cache| cache.borrow().get(pattern).cloned()) {
+        return Ok(regex);
+    }
+    let regex = match Regex::new(pattern) {
+        Ok(regex) => Rc::new(regex),
+        Err(PatternError { position, msg }) => {
+            let msg = format!(
+                "invalid regex pattern `{}` at index {}: {}",
+                pattern, position, msg
+            );
+            return Err(syntax_error(&msg)?);
+        }
+    };
+    PATTERN_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.len() >= MAX_CACHED_PATTERNS {
+            cache.clear();
+        }
+        cache.insert(pattern.to_string(), regex.clone());
+    });
+    Ok(regex)
+}
+
+// the pattern is compiled when the Regex object is created
+fn check_pattern(obj: &Object) -> Result<(), ()> {
+    for (name, value) in obj.properties() {
+        if let ("v", Value::String(pattern)) = (name.as_str(), value) {
+            compile(&pattern.borrow())?;
+        }
+    }
+    Ok(())
+}
+
+fn slice(text: &[char], (start, end): (usize, usize)) -> String {
+    text[start..end].iter().collect()
+}
+
+// the optional Number param, `default` if omitted
+fn get_optional_count(scope: &mut Scope, name: &str, default: i64) -> Result<usize, ()> {
+    let count = match get_val(name, scope)? {
+        Value::Void(_) => default,
+        val => val.get_i64()?,
+    };
+    Ok(count.max(0) as usize)
+}
+
+// non-overlapping matches, at most `limit` matches if it is not ZERO,
+// the empty match right after the last match is skipped.
+fn find_matches(regex: &Regex, text: &[char], limit: usize) -> Result<Vec<Captures>, ()> {
+    let mut matches = vec![];
+    let mut position = 0;
+    let mut last_end = None;
+
+    while position <= text.len() && (limit == 0 || matches.len() < limit) {
+        let Some(captures) = regex.search(text, position)? else {
+            break;
+        };
+        let (start, end) = captures[0].unwrap();
+        if start == end {
+            position = end + 1;
+            if last_end == Some(end) {
+                continue;
+            }
+        } else {
+            position = end;
+        }
+        last_end = Some(end);
+        matches.push(captures);
+    }
+    Ok(matches)
+}
+
+// expand replacement like: `$1` | `${name}` | `$$`
+fn expand(
+    regex: &Regex,
+    template: &[char],
+    text: &[char],
+    captures: &Captures,
+) -> Result<String, ()> {
+    let mut result = String::new();
+    let mut index = 0;
+
+    while index < template.len() {
+        let ch = template[index];
+        index += 1;
+        if ch != '$' {
+            result.push(ch);
+            continue;
+        }
+
+        let group_name: String = match template.get(index) {
+            Some('$') => {
+                index += 1;
+                result.push('$');
+                continue;
+            }
+            Some('{') => {
+                let Some(length) = template[index..].iter().position(|ch| *ch == '}') else {
+                    return Err(syntax_error("unclosed '${' in regex replacement")?);
+                };
+                let name = template[index + 1..index + length].iter().collect();
+                index += length + 1;
+                name
+            }
+            Some(ch) if ch.is_ascii_digit() => {
+                let start = index;
+                while template.get(index).is_some_and(|ch| ch.is_ascii_digit()) {
+                    index += 1;
+                }
+                template[start..index].iter().collect()
+            }
+            _ => {
+                // single `$` as literal
+                result.push('$');
+                continue;
+            }
+        };
+
+        let group_index = match group_name.parse::<usize>() {
+            Ok(group_index) => Some(group_index).filter(|i| *i < regex.group_count()),
+            Err(_) => regex.group_index(&group_name),
+        };
+        let Some(group_index) = group_index else {
+            let msg = format!("invalid group `{}` in regex replacement", group_name);
+            return Err(syntax_error(&msg)?);
+        };
+        if let Some(range) = captures[group_index] {
+            result.push_str(&slice(text, range));
+        }
+    }
+    Ok(result)
+}
+
+// --- --- --- --- --- ---
+
+static mut MODULE_CLASS: ModuleClass = EMPTY_MODULE_CLASS;
+impl ClassModule for RegexModule {
+    fn __static_class__() -> Class {
+        let compile = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Object, "self")],
+            identi: BuildInFnIdenti::Regex(Self::COMPILE),
+        };
+
+        // methods with text, `is_match` as function template
+        let is_match = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::String, "text"),
+            ],
+            identi: BuildInFnIdenti::Regex(Self::ISMATCH),
+        };
+        let mut find_all = is_match.clone();
+        find_all.identi = BuildInFnIdenti::Regex(Self::FINDALL);
+
+        // methods with text and start index, `find` as function template
+        let find = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::String, "text"),
+                BuildInFnParam(ValueType::Void, "start?"),
+            ],
+            identi: BuildInFnIdenti::Regex(Self::FIND),
+        };
+        let mut captures = find.clone();
+        let mut named_captures = find.clone();
+        captures.identi = BuildInFnIdenti::Regex(Self::CAPTURES);
+        named_captures.identi = BuildInFnIdenti::Regex(Self::NAMEDCAPTURES);
+
+        let replace = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::String, "text"),
+                BuildInFnParam(ValueType::String, "to"),
+                BuildInFnParam(ValueType::Void, "count?"),
+            ],
+            identi: BuildInFnIdenti::Regex(Self::REPLACE),
+        };
+        let split = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::String, "text"),
+                BuildInFnParam(ValueType::Void, "limit?"),
+            ],
+            identi: BuildInFnIdenti::Regex(Self::SPLIT),
+        };
+
+        let class = Class::new(
+            vec![Property(ValueType::String, String::from("v"))],
+            vec![
+                (String::from("compile"), Function::from(compile)),
+                (String::from("is_match"), Function::from(is_match)),
+                (String::from("find"), Function::from(find)),
+                (String::from("find_all"), Function::from(find_all)),
+                (String::from("captures"), Function::from(captures)),
+                (
+                    String::from("named_captures"),
+                    Function::from(named_captures),
+                ),
+                (String::from("replace"), Function::from(replace)),
+                (String::from("split"), Function::from(split)),
+            ],
+        );
+        return class.with_instance_check(check_pattern);
+    }
+
+    fn module_class() -> Rc<Class> {
+        let class = unsafe {
+            MODULE_CLASS.none_to_init(Self::__static_class__);
+            MODULE_CLASS.unwrap()
+        };
+        return class;
+    }
+}
+
+impl BuildInFnCall for RegexModule {
+    fn call(&self, scope: &mut Scope) -> Result<Value, ()> {
+        let self_value = get_val("self", scope)?;
+        let pattern_value = get_self_prop(&self_value, "v")?;
+        let regex = compile(&pattern_value.get_str()?)?;
+
+        if *self == Self::COMPILE {
+            // check the pattern only
+            return Ok(self_value);
+        }
+
+        let text_value = get_val("text", scope)?;
+        let text: Vec<char> = text_value.get_str()?.chars().collect();
+
+        let result = match self {
+            Self::ISMATCH => Value::from(regex.search(&text, 0)?.is_some()),
+
+            Self::FIND | Self::CAPTURES | Self::NAMEDCAPTURES => {
+                // `Void` if not found
+                let start = get_optional_count(scope, "start", 0)?.min(text.len());
+                let Some(captures) = regex.search(&text, start)? else {
+                    return Ok(Value::EMPTY);
+                };
+                let group_value = |index: usize| match captures[index] {
+                    Some(range) => Value::from(slice(&text, range)),
+                    None => Value::EMPTY,
+                };
+
+                match self {
+                    Self::FIND => {
+                        // matched text with its char indices, the end is exclusive
+                        let (start, end) = captures[0].unwrap();
+                        let mut match_map = InternalMap::new();
+                        match_map.insert(String::from("text"), group_value(0));
+                        match_map.insert(String::from("start"), Value::from(start as i64));
+                        match_map.insert(String::from("end"), Value::from(end as i64));
+                        Value::from(RawMap::new(match_map))
+                    }
+                    Self::CAPTURES => {
+                        let groups: ArrayLiteral =
+                            (0..regex.group_count()).map(group_value).collect();
+                        Value::from(groups)
+                    }
+                    Self::NAMEDCAPTURES => {
+                        let mut groups = InternalMap::new();
+                        for (name, index) in regex.group_names() {
+                            groups.insert(name.clone(), group_value(*index));
+                        }
+                        Value::from(RawMap::new(groups))
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            Self::FINDALL => {
+                let matches: ArrayLiteral = find_matches(&regex, &text, 0)?
+                    .into_iter()
+                    .map(|captures| Value::from(slice(&text, captures[0].unwrap())))
+                    .collect();
+                Value::from(matches)
+            }
+
+            Self::REPLACE => {
+                // replace all matches if `count` is omitted or ZERO
+                let to_value = get_val("to", scope)?;
+                let template: Vec<char> = to_value.get_str()?.chars().collect();
+                let count = get_optional_count(scope, "count", 0)?;
+
+                let mut result = String::new();
+                let mut last_end = 0;
+                for captures in find_matches(&regex, &text, count)? {
+                    let (start, end) = captures[0].unwrap();
+                    result.push_str(&slice(&text, (last_end, start)));
+                    result.push_str(&expand(&regex, &template, &text, &captures)?);
+                    last_end = end;
+                }
+                result.push_str(&slice(&text, (last_end, text.len())));
+                Value::from(result)
+            }
+            Self::SPLIT => {
+                // at most `limit` parts if it is not ZERO
+                let limit = get_optional_count(scope, "limit", 0)?;
+                let match_limit = match limit {
+                    0 => 0,
+                    1 => {
+                        let whole = Value::from(slice(&text, (0, text.len())));
+                        return Ok(Value::from(ArrayLiteral::from([whole])));
+                    }
+                    _ => limit - 1,
+                };
+
+                let mut parts = ArrayLiteral::new();
+                let mut last_end = 0;
+                for captures in find_matches(&regex, &text, match_limit)? {
+                    let (start, end) = captures[0].unwrap();
+                    parts.push_back(Value::from(slice(&text, (last_end, start))));
+                    last_end = end;
+                }
+                parts.push_back(Value::from(slice(&text, (last_end, text.len()))));
+                Value::from(parts)
+            }
+            Self::COMPILE => unreachable!(),
+        };
+        Ok(result)
+    }
+}
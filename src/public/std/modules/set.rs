@@ -0,0 +1,224 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::computer::resolvers::invocation::invocation_resolve::value_invoke;
+use crate::public::error::type_error;
+use crate::public::run_time::build_in::BuildInFnIdenti;
+use crate::public::run_time::scope::Scope;
+use crate::public::std::utils::get_self_prop::get_self_prop;
+use crate::public::std::{ModuleClass, EMPTY_MODULE_CLASS};
+use crate::public::value::array::ArrayLiteral;
+use crate::public::value::function::{BuildInFnParam, BuildInFunction, Function};
+use crate::public::value::into_rc_refcell;
+use crate::public::value::oop::class::{Class, Property};
+use crate::public::value::set::RawSet;
+use crate::public::value::value::{Value, ValueType};
+
+use super::super::utils::get_callee::get_callee;
+use super::super::utils::get_val::get_val;
+use super::{BuildInFnCall, ClassModule};
+
+#[derive(PartialEq, Clone)]
+pub enum SetModule {
+    INSERT,
+    REMOVE,
+    HAS,
+    LEN,
+    CLEAR,
+    TOARRAY,
+
+    UNION,
+    INTERSECTION,
+    DIFFERENCE,
+    SYMMETRICDIFFERENCE,
+
+    FOREACH,
+    FILTER,
+}
+
+// raw Set, Set object or Array
+fn get_other_set(value: &Value, fn_name: &str) -> Result<Rc<RefCell<RawSet>>, ()> {
+    let set_value = match value {
+        Value::Object(_) => get_self_prop(value, "v")?,
+        _ => value.clone(),
+    };
+    match set_value {
+        Value::Set(set) => Ok(set),
+        Value::Array(arr) => {
+            let elements: ArrayLiteral = arr.borrow().iter().cloned().collect();
+            Ok(into_rc_refcell(RawSet::from_values(elements)?))
+        }
+        _ => Err(type_error(
+            Some(fn_name),
+            vec![ValueType::Set, ValueType::Array],
+            set_value.get_type(),
+        )?),
+    }
+}
+
+// methods which call back functions,
+// the set is not borrowed when calling back.
+fn callback_call(
+    module_fn: &SetModule,
+    set: &Rc<RefCell<RawSet>>,
+    scope: &mut Scope,
+) -> Result<Value, ()> {
+    let elements: ArrayLiteral = set.borrow().iter().cloned().collect();
+    let callee = get_callee("f", scope)?;
+
+    let mut results = RawSet::new();
+    for element in elements {
+        let result = value_invoke(&callee, vec![element.clone()], scope)?;
+        if *module_fn == SetModule::FILTER && result.get_bool() {
+            results.insert(element)?;
+        }
+    }
+
+    let result = match module_fn {
+        SetModule::FILTER => Value::from(results),
+        _ => Value::EMPTY,
+    };
+    Ok(result)
+}
+
+pub static mut MODULE_CLASS: ModuleClass = EMPTY_MODULE_CLASS;
+impl ClassModule for SetModule {
+    fn __static_class__() -> Class {
+        // methods with element, `insert` as function template
+        let insert = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "element"),
+            ],
+            identi: BuildInFnIdenti::Set(Self::INSERT),
+        };
+        let mut remove = insert.clone();
+        let mut has = insert.clone();
+        remove.identi = BuildInFnIdenti::Set(Self::REMOVE);
+        has.identi = BuildInFnIdenti::Set(Self::HAS);
+
+        let len = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Object, "self")],
+            identi: BuildInFnIdenti::Set(Self::LEN),
+        };
+        let mut clear = len.clone();
+        let mut to_array = len.clone();
+        clear.identi = BuildInFnIdenti::Set(Self::CLEAR);
+        to_array.identi = BuildInFnIdenti::Set(Self::TOARRAY);
+
+        // methods with other Set, `union` as function template
+        let union = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "other"),
+            ],
+            identi: BuildInFnIdenti::Set(Self::UNION),
+        };
+        let mut intersection = union.clone();
+        let mut difference = union.clone();
+        let mut symmetric_difference = union.clone();
+        intersection.identi = BuildInFnIdenti::Set(Self::INTERSECTION);
+        difference.identi = BuildInFnIdenti::Set(Self::DIFFERENCE);
+        symmetric_difference.identi = BuildInFnIdenti::Set(Self::SYMMETRICDIFFERENCE);
+
+        // methods with callback
+        let for_each = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "f"),
+            ],
+            identi: BuildInFnIdenti::Set(Self::FOREACH),
+        };
+        let mut filter = for_each.clone();
+        filter.identi = BuildInFnIdenti::Set(Self::FILTER);
+
+        // --- --- --- --- --- ---
+
+        return Class::new(
+            vec![Property(ValueType::Set, String::from("v"))],
+            vec![
+                (String::from("insert"), Function::from(insert)),
+                (String::from("remove"), Function::from(remove)),
+                (String::from("has"), Function::from(has)),
+                (String::from("len"), Function::from(len)),
+                (String::from("clear"), Function::from(clear)),
+                (String::from("to_array"), Function::from(to_array)),
+                (String::from("union"), Function::from(union)),
+                (String::from("intersection"), Function::from(intersection)),
+                (String::from("difference"), Function::from(difference)),
+                (
+                    String::from("symmetric_difference"),
+                    Function::from(symmetric_difference),
+                ),
+                (String::from("for_each"), Function::from(for_each)),
+                (String::from("filter"), Function::from(filter)),
+            ],
+        );
+    }
+
+    fn module_class() -> Rc<Class> {
+        let class = unsafe {
+            MODULE_CLASS.none_to_init(Self::__static_class__);
+            MODULE_CLASS.unwrap()
+        };
+        return class;
+    }
+}
+
+impl BuildInFnCall for SetModule {
+    fn call(&self, scope: &mut Scope) -> Result<Value, ()> {
+        let self_value = get_val("self", scope)?;
+        let set_value = get_self_prop(&self_value, "v")?;
+        let Value::Set(set) = set_value else {
+            unreachable!()
+        };
+        if let Self::FOREACH | Self::FILTER = self {
+            return callback_call(self, &set, scope);
+        }
+
+        let result = match self {
+            Self::INSERT => {
+                let element_value = get_val("element", scope)?;
+                Value::from(set.borrow_mut().insert(element_value)?)
+            }
+            Self::REMOVE => {
+                let element_value = get_val("element", scope)?;
+                Value::from(set.borrow_mut().remove(&element_value)?)
+            }
+            Self::HAS => {
+                let element_value = get_val("element", scope)?;
+                Value::from(set.borrow().has(&element_value)?)
+            }
+            Self::LEN => Value::from(set.borrow().len() as i64),
+            Self::CLEAR => {
+                set.borrow_mut().clear();
+                Value::EMPTY
+            }
+            Self::TOARRAY => {
+                let elements: ArrayLiteral = set.borrow().iter().cloned().collect();
+                Value::from(elements)
+            }
+
+            Self::UNION | Self::INTERSECTION | Self::DIFFERENCE | Self::SYMMETRICDIFFERENCE => {
+                let fn_name = match self {
+                    Self::UNION => "Set.union",
+                    Self::INTERSECTION => "Set.intersection",
+                    Self::DIFFERENCE => "Set.difference",
+                    _ => "Set.symmetric_difference",
+                };
+                let other = get_other_set(&get_val("other", scope)?, fn_name)?;
+                let (set_ref, other_ref) = (set.borrow(), other.borrow());
+                let result_set = match self {
+                    Self::UNION => set_ref.union(&other_ref),
+                    Self::INTERSECTION => set_ref.intersection(&other_ref),
+                    Self::DIFFERENCE => set_ref.difference(&other_ref),
+                    _ => set_ref.symmetric_difference(&other_ref),
+                };
+                Value::from(result_set)
+            }
+
+            Self::FOREACH | Self::FILTER => unreachable!(),
+        };
+        return Ok(result);
+    }
+}
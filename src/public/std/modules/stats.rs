@@ -0,0 +1,358 @@
+use std::cmp::Ordering;
+
+use crate::public::error::{math_error, range_error, type_error};
+use crate::public::run_time::build_in::BuildInFnIdenti;
+use crate::public::run_time::scope::Scope;
+use crate::public::value::array::ArrayLiteral;
+use crate::public::value::function::{BuildInFnParam, BuildInFunction};
+use crate::public::value::map::{InternalMap, RawMap};
+use crate::public::value::number::Number;
+use crate::public::value::oop::object::Object;
+use crate::public::value::value::{Value, ValueType};
+
+use super::super::utils::get_val::get_val;
+use super::{BuildInFnCall, ObjectModule};
+
+#[derive(PartialEq, Clone)]
+pub enum StatsModule {
+    SUM,
+    MEAN,
+    MEDIAN,
+    MODE,
+    VARIANCE,
+    STDDEV,
+    PVARIANCE,
+    PSTDDEV,
+    MIN,
+    MAX,
+
+    PERCENTILE,
+    QUANTILE,
+    HISTOGRAM,
+    CORRELATION,
+    REGRESSION,
+}
+
+// limit of the bins count for histogram
+const MAX_BINS: i64 = 10_000;
+
+// get Numbers from the Array param,
+// non-numeric elements is reported as type error.
+fn get_numbers(param: &str, scope: &mut Scope) -> Result<Vec<Number>, ()> {
+    let Value::Array(arr) = get_val(param, scope)? else {
+        unreachable!()
+    };
+    let mut numbers = vec![];
+    for item in arr.borrow().iter() {
+        let Value::Number(num) = item else {
+            return Err(type_error(
+                Some("Stats element"),
+                vec![ValueType::Number],
+                item.get_type(),
+            )?);
+        };
+        numbers.push(num.clone());
+    }
+    if numbers.is_empty() {
+        return Err(math_error("the input Array should not be empty")?);
+    }
+    Ok(numbers)
+}
+
+// sorted Numbers for order-based statistics
+fn sorted(mut numbers: Vec<Number>) -> Result<Vec<Number>, ()> {
+    let is_ordered = numbers
+        .iter()
+        .all(|num| !matches!(num, Number::Complex(_, _) | Number::NotANumber));
+    if !is_ordered {
+        return Err(math_error(
+            "the elements should be real Numbers for ordering",
+        )?);
+    }
+    numbers.sort_by(|num1, num2| num1.partial_cmp(num2).unwrap_or(Ordering::Equal));
+    Ok(numbers)
+}
+
+fn sum(numbers: &[Number]) -> Number {
+    numbers
+        .iter()
+        .fold(Number::Int(0), |total, num| total + num.clone())
+        .simplify()
+}
+fn mean(numbers: &[Number]) -> Number {
+    Number::exact_div(sum(numbers), Number::Int(numbers.len() as i64))
+}
+// sum of the squared deviations
+fn deviation_square_sum(numbers: &[Number]) -> Number {
+    let average = mean(numbers);
+    let squares: Vec<Number> = numbers
+        .iter()
+        .map(|num| {
+            let deviation = num.clone() - average.clone();
+            deviation.clone() * deviation
+        })
+        .collect();
+    sum(&squares)
+}
+fn variance(numbers: &[Number], is_sample: bool) -> Result<Number, ()> {
+    let count = if is_sample {
+        if numbers.len() < 2 {
+            return Err(math_error("sample variance requires at least 2 elements")?);
+        }
+        numbers.len() - 1
+    } else {
+        numbers.len()
+    };
+    Ok(Number::exact_div(
+        deviation_square_sum(numbers),
+        Number::Int(count as i64),
+    ))
+}
+// sum of the products of paired deviations
+fn co_deviation_sum(xs: &[Number], ys: &[Number]) -> Number {
+    let x_mean = mean(xs);
+    let y_mean = mean(ys);
+    let products: Vec<Number> = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (x.clone() - x_mean.clone()) * (y.clone() - y_mean.clone()))
+        .collect();
+    sum(&products)
+}
+
+// linear interpolation between the closest ranks, `position` is in [0, 1]
+fn quantile(numbers: Vec<Number>, position: Number) -> Result<Number, ()> {
+    let position_f = position.float_value();
+    if !(0.0..=1.0).contains(&position_f) {
+        return Err(math_error("the quantile should be in range [0, 1]")?);
+    }
+    let numbers = sorted(numbers)?;
+    let rank = position * Number::Int(numbers.len() as i64 - 1);
+    let lower_index = rank.float_value().floor() as usize;
+    let lower = numbers[lower_index].clone();
+    if lower_index + 1 == numbers.len() {
+        return Ok(lower);
+    }
+
+    let upper = numbers[lower_index + 1].clone();
+    let weight = rank - Number::Int(lower_index as i64);
+    Ok((lower.clone() + (upper - lower) * weight).simplify())
+}
+
+fn check_paired(xs: &[Number], ys: &[Number]) -> Result<(), ()> {
+    if xs.len() != ys.len() {
+        let msg = format!(
+            "paired Arrays should have the same length, found {} and {}",
+            xs.len(),
+            ys.len()
+        );
+        return Err(math_error(&msg)?);
+    }
+    if xs.len() < 2 {
+        return Err(math_error("paired Arrays require at least 2 elements")?);
+    }
+    Ok(())
+}
+
+impl ObjectModule for StatsModule {
+    fn module_object() -> Object {
+        let sum = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Array, "input"),
+            ],
+            identi: BuildInFnIdenti::Stats(Self::SUM),
+        };
+        let mut mean = sum.clone();
+        let mut median = sum.clone();
+        let mut mode = sum.clone();
+        let mut variance = sum.clone();
+        let mut stddev = sum.clone();
+        let mut pvariance = sum.clone();
+        let mut pstddev = sum.clone();
+        let mut min = sum.clone();
+        let mut max = sum.clone();
+
+        mean.identi = BuildInFnIdenti::Stats(Self::MEAN);
+        median.identi = BuildInFnIdenti::Stats(Self::MEDIAN);
+        mode.identi = BuildInFnIdenti::Stats(Self::MODE);
+        variance.identi = BuildInFnIdenti::Stats(Self::VARIANCE);
+        stddev.identi = BuildInFnIdenti::Stats(Self::STDDEV);
+        pvariance.identi = BuildInFnIdenti::Stats(Self::PVARIANCE);
+        pstddev.identi = BuildInFnIdenti::Stats(Self::PSTDDEV);
+        min.identi = BuildInFnIdenti::Stats(Self::MIN);
+        max.identi = BuildInFnIdenti::Stats(Self::MAX);
+
+        let percentile = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Array, "input"),
+                BuildInFnParam(ValueType::Number, "position"),
+            ],
+            identi: BuildInFnIdenti::Stats(Self::PERCENTILE),
+        };
+        let mut quantile = percentile.clone();
+        quantile.identi = BuildInFnIdenti::Stats(Self::QUANTILE);
+
+        let histogram = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Array, "input"),
+                BuildInFnParam(ValueType::Number, "bins"),
+            ],
+            identi: BuildInFnIdenti::Stats(Self::HISTOGRAM),
+        };
+
+        let correlation = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Array, "xs"),
+                BuildInFnParam(ValueType::Array, "ys"),
+            ],
+            identi: BuildInFnIdenti::Stats(Self::CORRELATION),
+        };
+        let mut regression = correlation.clone();
+        regression.identi = BuildInFnIdenti::Stats(Self::REGRESSION);
+
+        let module_obj_props = vec![
+            (String::from("sum"), Value::from(sum)),
+            (String::from("mean"), Value::from(mean)),
+            (String::from("median"), Value::from(median)),
+            (String::from("mode"), Value::from(mode)),
+            (String::from("variance"), Value::from(variance)),
+            (String::from("stddev"), Value::from(stddev)),
+            (String::from("pvariance"), Value::from(pvariance)),
+            (String::from("pstddev"), Value::from(pstddev)),
+            (String::from("min"), Value::from(min)),
+            (String::from("max"), Value::from(max)),
+            (String::from("percentile"), Value::from(percentile)),
+            (String::from("quantile"), Value::from(quantile)),
+            (String::from("histogram"), Value::from(histogram)),
+            (String::from("correlation"), Value::from(correlation)),
+            (String::from("regression"), Value::from(regression)),
+        ];
+        return Object::new(module_obj_props, None);
+    }
+}
+
+impl BuildInFnCall for StatsModule {
+    fn call(&self, scope: &mut Scope) -> Result<Value, ()> {
+        if let Self::CORRELATION | Self::REGRESSION = self {
+            let xs = get_numbers("xs", scope)?;
+            let ys = get_numbers("ys", scope)?;
+            check_paired(&xs, &ys)?;
+
+            let xx_sum = deviation_square_sum(&xs);
+            let yy_sum = deviation_square_sum(&ys);
+            let xy_sum = co_deviation_sum(&xs, &ys);
+            if xx_sum.is_zero() {
+                return Err(math_error("the `xs` should not be constant")?);
+            }
+            // Pearson correlation coefficient
+            let correlation = || -> Result<Number, ()> {
+                if yy_sum.is_zero() {
+                    return Err(math_error("the `ys` should not be constant")?);
+                }
+                let denominator = (xx_sum.clone() * yy_sum.clone()).sqrt();
+                Ok(xy_sum.clone() / denominator)
+            };
+
+            if *self == Self::CORRELATION {
+                return Ok(Value::Number(correlation()?));
+            }
+
+            // least squares line: `y = slope * x + intercept`
+            let slope = Number::exact_div(xy_sum.clone(), xx_sum.clone());
+            let intercept = (mean(&ys) - slope.clone() * mean(&xs)).simplify();
+            let mut result_map = InternalMap::new();
+            result_map.insert(String::from("slope"), Value::Number(slope));
+            result_map.insert(String::from("intercept"), Value::Number(intercept));
+            if !yy_sum.is_zero() {
+                result_map.insert(String::from("r"), Value::Number(correlation()?));
+            }
+            return Ok(Value::from(RawMap::new(result_map)));
+        }
+
+        let numbers = get_numbers("input", scope)?;
+        let result = match self {
+            Self::SUM => sum(&numbers),
+            Self::MEAN => mean(&numbers),
+            Self::MEDIAN => quantile(numbers, Number::fraction(1.into(), 2.into()))?,
+            Self::VARIANCE | Self::PVARIANCE => variance(&numbers, *self == Self::VARIANCE)?,
+            Self::STDDEV | Self::PSTDDEV => variance(&numbers, *self == Self::STDDEV)?.sqrt(),
+            Self::MIN => sorted(numbers)?.remove(0),
+            Self::MAX => sorted(numbers)?.pop().unwrap(),
+
+            Self::PERCENTILE | Self::QUANTILE => {
+                let Value::Number(position) = get_val("position", scope)? else {
+                    unreachable!()
+                };
+                let position = if *self == Self::PERCENTILE {
+                    let position_f = position.float_value();
+                    if !(0.0..=100.0).contains(&position_f) {
+                        return Err(math_error("the percentile should be in range [0, 100]")?);
+                    }
+                    Number::exact_div(position, Number::Int(100))
+                } else {
+                    position
+                };
+                quantile(numbers, position)?
+            }
+            Self::MODE => {
+                // all the most frequent values in ascending order
+                let numbers = sorted(numbers)?;
+                let mut groups: Vec<(Number, usize)> = vec![];
+                for num in numbers {
+                    match groups.last_mut() {
+                        Some((last, count)) if *last == num => *count += 1,
+                        _ => groups.push((num, 1)),
+                    }
+                }
+                let max_count = groups.iter().map(|(_, count)| *count).max().unwrap();
+                let modes: ArrayLiteral = groups
+                    .into_iter()
+                    .filter(|(_, count)| *count == max_count)
+                    .map(|(num, _)| Value::Number(num))
+                    .collect();
+                return Ok(Value::from(modes));
+            }
+            Self::HISTOGRAM => {
+                // equal-width bins between min and max,
+                // the last bin includes the max value.
+                let bins = get_val("bins", scope)?.get_i64()?;
+                if bins < 1 {
+                    return Err(math_error("the bins count should be positive")?);
+                }
+                if bins > MAX_BINS {
+                    let expected = format!("1..={}", MAX_BINS);
+                    return Err(range_error("histogram bins", expected, bins as usize)?);
+                }
+                let numbers = sorted(numbers)?;
+                let min_f = numbers.first().unwrap().float_value();
+                let max_f = numbers.last().unwrap().float_value();
+                let width = (max_f - min_f) / bins as f64;
+
+                let mut counts = vec![0_i64; bins as usize];
+                for num in &numbers {
+                    let index = if width == 0.0 {
+                        0
+                    } else {
+                        ((num.float_value() - min_f) / width) as usize
+                    };
+                    counts[index.min(bins as usize - 1)] += 1;
+                }
+
+                let edges: ArrayLiteral = (0..=bins)
+                    .map(|index| Value::from(min_f + width * index as f64))
+                    .collect();
+                let counts: ArrayLiteral = counts.into_iter().map(Value::from).collect();
+                let mut result_map = InternalMap::new();
+                result_map.insert(String::from("edges"), Value::from(edges));
+                result_map.insert(String::from("counts"), Value::from(counts));
+                return Ok(Value::from(RawMap::new(result_map)));
+            }
+            Self::CORRELATION | Self::REGRESSION => unreachable!(),
+        };
+        return Ok(Value::Number(result));
+    }
+}
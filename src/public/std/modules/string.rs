@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 use std::rc::Rc;
 
+use crate::public::error::range_error;
 use crate::public::run_time::build_in::BuildInFnIdenti;
 use crate::public::run_time::scope::Scope;
 use crate::public::std::{ModuleClass, EMPTY_MODULE_CLASS};
@@ -19,6 +20,73 @@ pub enum StringModule {
     REPEAT,
     STARTWITH,
     ENDWITH,
+
+    TRIM,
+    TRIMSTART,
+    TRIMEND,
+    UPPER,
+    LOWER,
+    TITLE,
+    FIND,
+    RFIND,
+    INDEXOF,
+    CONTAINS,
+    PADLEFT,
+    PADRIGHT,
+    CENTER,
+    CHARS,
+    LINES,
+    REVERSE,
+    COUNT,
+    SUBSTRING,
+    ISDIGIT,
+    ISALPHA,
+}
+
+// the max width of `pad_left`, `pad_right` and `center`
+const MAX_PAD_WIDTH: i64 = 1_000_000;
+
+// byte index -> char index
+fn char_index(str_ref: &str, byte_index: usize) -> i64 {
+    str_ref[..byte_index].chars().count() as i64
+}
+
+// negative index counts from the end, out of range index is clamped
+fn normalize_index(index: i64, len: usize) -> usize {
+    let len = len as i64;
+    let index = if index < 0 { index + len } else { index };
+    index.clamp(0, len) as usize
+}
+
+fn get_fill(scope: &mut Scope, fn_name: &str) -> Result<char, ()> {
+    let fill_value = get_val("fill", scope)?;
+    if let Value::Void(_) = fill_value {
+        return Ok(' ');
+    }
+    let fill_ref = fill_value.get_str()?;
+    let mut fill_chars = fill_ref.chars();
+    match (fill_chars.next(), fill_chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(range_error(
+            fn_name,
+            "1 character",
+            fill_ref.chars().count(),
+        )?),
+    }
+}
+
+fn title_case(str_ref: &str) -> String {
+    let mut result = String::with_capacity(str_ref.len());
+    let mut is_word_start = true;
+    for c in str_ref.chars() {
+        if is_word_start {
+            result.extend(c.to_uppercase());
+        } else {
+            result.extend(c.to_lowercase());
+        }
+        is_word_start = c.is_whitespace();
+    }
+    result
 }
 
 static mut MODULE_CLASS: ModuleClass = EMPTY_MODULE_CLASS;
@@ -60,6 +128,75 @@ impl ClassModule for StringModule {
             ],
             identi: BuildInFnIdenti::String(Self::ENDWITH),
         };
+
+        // methods with only `self`
+        let trim = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Object, "self")],
+            identi: BuildInFnIdenti::String(Self::TRIM),
+        };
+        let mut trim_start = trim.clone();
+        let mut trim_end = trim.clone();
+        let mut upper = trim.clone();
+        let mut lower = trim.clone();
+        let mut title = trim.clone();
+        let mut chars = trim.clone();
+        let mut lines = trim.clone();
+        let mut reverse = trim.clone();
+        let mut is_digit = trim.clone();
+        let mut is_alpha = trim.clone();
+        trim_start.identi = BuildInFnIdenti::String(Self::TRIMSTART);
+        trim_end.identi = BuildInFnIdenti::String(Self::TRIMEND);
+        upper.identi = BuildInFnIdenti::String(Self::UPPER);
+        lower.identi = BuildInFnIdenti::String(Self::LOWER);
+        title.identi = BuildInFnIdenti::String(Self::TITLE);
+        chars.identi = BuildInFnIdenti::String(Self::CHARS);
+        lines.identi = BuildInFnIdenti::String(Self::LINES);
+        reverse.identi = BuildInFnIdenti::String(Self::REVERSE);
+        is_digit.identi = BuildInFnIdenti::String(Self::ISDIGIT);
+        is_alpha.identi = BuildInFnIdenti::String(Self::ISALPHA);
+
+        // methods with pattern, `start_with` as function template
+        let find = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::String, "pat"),
+                BuildInFnParam(ValueType::Void, "start?"),
+            ],
+            identi: BuildInFnIdenti::String(Self::FIND),
+        };
+        let mut rfind = start_with.clone();
+        let mut index_of = start_with.clone();
+        let mut contains = start_with.clone();
+        let mut count = start_with.clone();
+        rfind.identi = BuildInFnIdenti::String(Self::RFIND);
+        index_of.identi = BuildInFnIdenti::String(Self::INDEXOF);
+        contains.identi = BuildInFnIdenti::String(Self::CONTAINS);
+        count.identi = BuildInFnIdenti::String(Self::COUNT);
+
+        let pad_left = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "width"),
+                BuildInFnParam(ValueType::Void, "fill?"),
+            ],
+            identi: BuildInFnIdenti::String(Self::PADLEFT),
+        };
+        let mut pad_right = pad_left.clone();
+        let mut center = pad_left.clone();
+        pad_right.identi = BuildInFnIdenti::String(Self::PADRIGHT);
+        center.identi = BuildInFnIdenti::String(Self::CENTER);
+
+        let substring = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "start"),
+                BuildInFnParam(ValueType::Void, "end?"),
+            ],
+            identi: BuildInFnIdenti::String(Self::SUBSTRING),
+        };
+
+        // --- --- --- --- --- ---
+
         return Class::new(
             vec![Property(ValueType::String, String::from("v"))],
             vec![
@@ -68,6 +205,26 @@ impl ClassModule for StringModule {
                 (String::from("repeat"), Function::from(repeat)),
                 (String::from("start_with"), Function::from(start_with)),
                 (String::from("end_with"), Function::from(end_with)),
+                (String::from("trim"), Function::from(trim)),
+                (String::from("trim_start"), Function::from(trim_start)),
+                (String::from("trim_end"), Function::from(trim_end)),
+                (String::from("upper"), Function::from(upper)),
+                (String::from("lower"), Function::from(lower)),
+                (String::from("title"), Function::from(title)),
+                (String::from("find"), Function::from(find)),
+                (String::from("rfind"), Function::from(rfind)),
+                (String::from("index_of"), Function::from(index_of)),
+                (String::from("contains"), Function::from(contains)),
+                (String::from("pad_left"), Function::from(pad_left)),
+                (String::from("pad_right"), Function::from(pad_right)),
+                (String::from("center"), Function::from(center)),
+                (String::from("chars"), Function::from(chars)),
+                (String::from("lines"), Function::from(lines)),
+                (String::from("reverse"), Function::from(reverse)),
+                (String::from("count"), Function::from(count)),
+                (String::from("substring"), Function::from(substring)),
+                (String::from("is_digit"), Function::from(is_digit)),
+                (String::from("is_alpha"), Function::from(is_alpha)),
             ],
         );
     }
@@ -84,7 +241,8 @@ impl BuildInFnCall for StringModule {
     fn call(&self, scope: &mut Scope) -> Result<Value, ()> {
         let self_value = get_val("self", scope)?;
         let str_value = get_self_prop(&self_value, "v")?;
-        let str_ref = str_value.get_str()?;
+        // cloned, so that `self.v` can also be passed as an argument
+        let str_ref = str_value.get_str()?.clone();
 
         let result = match self {
             Self::SPLIT => {
@@ -131,6 +289,132 @@ impl BuildInFnCall for StringModule {
                 };
                 Value::from(result)
             }
+
+            Self::TRIM => Value::from(String::from(str_ref.trim())),
+            Self::TRIMSTART => Value::from(String::from(str_ref.trim_start())),
+            Self::TRIMEND => Value::from(String::from(str_ref.trim_end())),
+            Self::UPPER => Value::from(str_ref.to_uppercase()),
+            Self::LOWER => Value::from(str_ref.to_lowercase()),
+            Self::TITLE => Value::from(title_case(&str_ref)),
+
+            Self::FIND | Self::RFIND | Self::INDEXOF => {
+                // char index of the pattern, `-1` if not found
+                let pat_value = get_val("pat", scope)?;
+                let pat_ref = pat_value.get_str()?;
+                let found = match self {
+                    Self::FIND => {
+                        let start = match get_val("start", scope)? {
+                            Value::Void(_) => 0,
+                            val => val.get_i64()?,
+                        };
+                        let start = normalize_index(start, str_ref.chars().count());
+                        let start_byte = str_ref
+                            .char_indices()
+                            .nth(start)
+                            .map_or(str_ref.len(), |(i, _)| i);
+                        str_ref[start_byte..]
+                            .find(&*pat_ref)
+                            .map(|i| i + start_byte)
+                    }
+                    Self::RFIND => str_ref.rfind(&*pat_ref),
+                    Self::INDEXOF => str_ref.find(&*pat_ref),
+                    _ => unreachable!(),
+                };
+                match found {
+                    Some(byte_index) => Value::from(char_index(&str_ref, byte_index)),
+                    None => Value::from(-1_i64),
+                }
+            }
+            Self::CONTAINS => {
+                let pat_value = get_val("pat", scope)?;
+                let pat_ref = pat_value.get_str()?;
+                Value::from(str_ref.contains(&*pat_ref))
+            }
+            Self::COUNT => {
+                // non-overlapping occurrences
+                let pat_value = get_val("pat", scope)?;
+                let pat_ref = pat_value.get_str()?;
+                let count = if pat_ref.is_empty() {
+                    str_ref.chars().count() + 1
+                } else {
+                    str_ref.matches(&*pat_ref).count()
+                };
+                Value::from(count as i64)
+            }
+
+            Self::PADLEFT | Self::PADRIGHT | Self::CENTER => {
+                let width = get_val("width", scope)?.get_i64()?.max(0);
+                let fn_name = match self {
+                    Self::PADLEFT => "String.pad_left",
+                    Self::PADRIGHT => "String.pad_right",
+                    Self::CENTER => "String.center",
+                    _ => unreachable!(),
+                };
+                if width > MAX_PAD_WIDTH {
+                    return Err(range_error(
+                        &format!("{} width", fn_name),
+                        format!("0..={}", MAX_PAD_WIDTH),
+                        width as usize,
+                    )?);
+                }
+                let width = width as usize;
+                let fill = get_fill(scope, fn_name)?;
+                let padding = width.saturating_sub(str_ref.chars().count());
+                let (left, right) = match self {
+                    Self::PADLEFT => (padding, 0),
+                    Self::PADRIGHT => (0, padding),
+                    Self::CENTER => (padding / 2, padding - padding / 2),
+                    _ => unreachable!(),
+                };
+                let fill = fill.to_string();
+                let padded = fill.repeat(left) + &str_ref + &fill.repeat(right);
+                Value::from(padded)
+            }
+
+            Self::CHARS => {
+                let chars = str_ref
+                    .chars()
+                    .map(|c| Value::from(c.to_string()))
+                    .collect::<VecDeque<Value>>();
+                Value::from(chars)
+            }
+            Self::LINES => {
+                let lines = str_ref
+                    .lines()
+                    .map(|line| Value::from(String::from(line)))
+                    .collect::<VecDeque<Value>>();
+                Value::from(lines)
+            }
+            Self::REVERSE => Value::from(str_ref.chars().rev().collect::<String>()),
+
+            Self::SUBSTRING => {
+                // char indices, the end is exclusive
+                let char_count = str_ref.chars().count();
+                let start = get_val("start", scope)?.get_i64()?;
+                let end = match get_val("end", scope)? {
+                    Value::Void(_) => char_count as i64,
+                    val => val.get_i64()?,
+                };
+                let start = normalize_index(start, char_count);
+                let end = normalize_index(end, char_count);
+                let substring = str_ref
+                    .chars()
+                    .skip(start)
+                    .take(end.saturating_sub(start))
+                    .collect::<String>();
+                Value::from(substring)
+            }
+
+            Self::ISDIGIT | Self::ISALPHA => {
+                // `false` for empty string
+                let predicate = match self {
+                    Self::ISDIGIT => char::is_numeric,
+                    Self::ISALPHA => char::is_alphabetic,
+                    _ => unreachable!(),
+                };
+                let result = !str_ref.is_empty() && str_ref.chars().all(predicate);
+                Value::from(result)
+            }
         };
         return Ok(result);
     }
@@ -0,0 +1,751 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::public::error::{math_error, syntax_error, type_error};
+use crate::public::run_time::build_in::BuildInFnIdenti;
+use crate::public::run_time::scope::Scope;
+use crate::public::std::utils::date_time::{
+    duration_text, parse_offset, DateTime, Fields, ParseError, MAX_OFFSET, NANOS_PER_SEC,
+};
+use crate::public::std::utils::get_self_prop::get_self_prop;
+use crate::public::std::{ModuleClass, EMPTY_MODULE_CLASS};
+use crate::public::value::array::ArrayLiteral;
+use crate::public::value::function::{BuildInFnParam, BuildInFunction, Function};
+use crate::public::value::number::Number;
+use crate::public::value::oop::class::{Class, Property};
+use crate::public::value::oop::object::Object;
+use crate::public::value::symbols::Symbols;
+use crate::public::value::value::{Value, ValueType};
+use crate::public::value::GetAddr;
+
+use super::super::utils::get_val::get_val;
+use super::{BuildInFnCall, ObjectModule};
+
+#[derive(PartialEq, Clone)]
+pub enum TimeModule {
+    // module object functions
+    NOW,
+    TIMESTAMP,
+    FROMTIMESTAMP,
+    DATE,
+    PARSE,
+    DAYS,
+    HOURS,
+    MINUTES,
+    SECONDS,
+    MILLIS,
+    STOPWATCH,
+    SLEEP,
+
+    // DateTime methods
+    FORMAT,
+    ISO,
+    TOTIMESTAMP,
+    WEEKDAY,
+    YEARDAY,
+    TOOFFSET,
+
+    // Duration methods
+    TOTALSECONDS,
+    TOTALMILLIS,
+    ABS,
+
+    // Stopwatch methods
+    ELAPSED,
+    LAP,
+    RESET,
+}
+
+const DATETIME_FIELDS: [&str; 8] = [
+    "year",
+    "month",
+    "day",
+    "hour",
+    "minute",
+    "second",
+    "nanosecond",
+    "offset",
+];
+
+static mut DATETIME_CLASS: ModuleClass = EMPTY_MODULE_CLASS;
+static mut DURATION_CLASS: ModuleClass = EMPTY_MODULE_CLASS;
+static mut STOPWATCH_CLASS: ModuleClass = EMPTY_MODULE_CLASS;
+
+// the base of monotonic clock, set at the first stopwatch
+static mut CLOCK_BASE: Option<Instant> = None;
+
+fn monotonic_nanos() -> i64 {
+    unsafe {
+        let base = CLOCK_BASE.get_or_insert_with(Instant::now);
+        base.elapsed().as_nanos() as i64
+    }
+}
+
+// nanoseconds since the unix epoch
+fn now_nanos() -> i128 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_nanos() as i128,
+        Err(err) => -(err.duration().as_nanos() as i128),
+    }
+}
+
+fn checked<T>(result: Result<T, String>) -> Result<T, ()> {
+    match result {
+        Ok(value) => Ok(value),
+        Err(msg) => Err(math_error(&msg)?),
+    }
+}
+
+// --- --- --- --- --- ---
+
+fn datetime_class() -> Rc<Class> {
+    fn static_class() -> Class {
+        let iso = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Object, "self")],
+            identi: BuildInFnIdenti::Time(TimeModule::ISO),
+        };
+        // `iso` as function template
+        let mut timestamp = iso.clone();
+        let mut weekday = iso.clone();
+        let mut yearday = iso.clone();
+        timestamp.identi = BuildInFnIdenti::Time(TimeModule::TOTIMESTAMP);
+        weekday.identi = BuildInFnIdenti::Time(TimeModule::WEEKDAY);
+        yearday.identi = BuildInFnIdenti::Time(TimeModule::YEARDAY);
+
+        let format = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::String, "pattern"),
+            ],
+            identi: BuildInFnIdenti::Time(TimeModule::FORMAT),
+        };
+        let to_offset = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "offset?"),
+            ],
+            identi: BuildInFnIdenti::Time(TimeModule::TOOFFSET),
+        };
+
+        let properties = DATETIME_FIELDS
+            .iter()
+            .map(|name| Property(ValueType::Number, String::from(*name)))
+            .collect();
+        return Class::new(
+            properties,
+            vec![
+                (String::from("format"), Function::from(format)),
+                (String::from("iso"), Function::from(iso)),
+                (String::from("timestamp"), Function::from(timestamp)),
+                (String::from("weekday"), Function::from(weekday)),
+                (String::from("yearday"), Function::from(yearday)),
+                (String::from("to_offset"), Function::from(to_offset)),
+            ],
+        );
+    }
+    unsafe {
+        DATETIME_CLASS.none_to_init(static_class);
+        DATETIME_CLASS.unwrap()
+    }
+}
+
+fn duration_class() -> Rc<Class> {
+    fn static_class() -> Class {
+        let total_seconds = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Object, "self")],
+            identi: BuildInFnIdenti::Time(TimeModule::TOTALSECONDS),
+        };
+        // `total_seconds` as function template
+        let mut total_millis = total_seconds.clone();
+        let mut abs = total_seconds.clone();
+        total_millis.identi = BuildInFnIdenti::Time(TimeModule::TOTALMILLIS);
+        abs.identi = BuildInFnIdenti::Time(TimeModule::ABS);
+
+        return Class::new(
+            vec![
+                Property(ValueType::Number, String::from("seconds")),
+                Property(ValueType::Number, String::from("nanos")),
+            ],
+            vec![
+                (String::from("total_seconds"), Function::from(total_seconds)),
+                (String::from("total_millis"), Function::from(total_millis)),
+                (String::from("abs"), Function::from(abs)),
+            ],
+        );
+    }
+    unsafe {
+        DURATION_CLASS.none_to_init(static_class);
+        DURATION_CLASS.unwrap()
+    }
+}
+
+fn stopwatch_class() -> Rc<Class> {
+    fn static_class() -> Class {
+        let elapsed = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Object, "self")],
+            identi: BuildInFnIdenti::Time(TimeModule::ELAPSED),
+        };
+        // `elapsed` as function template
+        let mut lap = elapsed.clone();
+        let mut reset = elapsed.clone();
+        lap.identi = BuildInFnIdenti::Time(TimeModule::LAP);
+        reset.identi = BuildInFnIdenti::Time(TimeModule::RESET);
+
+        return Class::new(
+            vec![
+                Property(ValueType::Number, String::from("start")),
+                Property(ValueType::Number, String::from("last_lap")),
+            ],
+            vec![
+                (String::from("elapsed"), Function::from(elapsed)),
+                (String::from("lap"), Function::from(lap)),
+                (String::from("reset"), Function::from(reset)),
+            ],
+        );
+    }
+    unsafe {
+        STOPWATCH_CLASS.none_to_init(static_class);
+        STOPWATCH_CLASS.unwrap()
+    }
+}
+
+fn is_instance_of(obj: &Object, class: Rc<Class>) -> bool {
+    let Some(proto) = obj.get_proto() else {
+        return false;
+    };
+    return proto.get_addr() == class.get_addr();
+}
+
+// --- --- --- --- --- ---
+
+fn int_prop(obj: &Object, name: &str) -> Result<i64, String> {
+    match obj.get(name) {
+        Ok(Value::Number(Number::Int(i))) => Ok(i),
+        _ => Err(format!("the property `{}` should be an Int", name)),
+    }
+}
+
+fn object_datetime(obj: &Object) -> Result<DateTime, String> {
+    let mut values = [0; DATETIME_FIELDS.len()];
+    for (value, name) in values.iter_mut().zip(DATETIME_FIELDS) {
+        *value = int_prop(obj, name)?;
+    }
+    // the negative and too large fields are invalid
+    let field = |i: usize| u32::try_from(values[i]).unwrap_or(u32::MAX);
+    let fields = Fields {
+        year: values[0],
+        month: field(1),
+        day: field(2),
+        hour: field(3),
+        minute: field(4),
+        second: field(5),
+        nanosecond: field(6),
+    };
+    let offset = i32::try_from(values[7]).unwrap_or(i32::MAX);
+    DateTime::from_fields(&fields, offset)
+}
+
+fn object_duration(obj: &Object) -> Result<i128, String> {
+    let seconds = int_prop(obj, "seconds")?;
+    let nanos = int_prop(obj, "nanos")?;
+    Ok(seconds as i128 * NANOS_PER_SEC + nanos as i128)
+}
+
+fn datetime_value(date_time: DateTime) -> Result<Value, ()> {
+    let fields = date_time.fields();
+    let params = ArrayLiteral::from([
+        Value::from(fields.year),
+        Value::from(fields.month as i64),
+        Value::from(fields.day as i64),
+        Value::from(fields.hour as i64),
+        Value::from(fields.minute as i64),
+        Value::from(fields.second as i64),
+        Value::from(fields.nanosecond as i64),
+        Value::from(date_time.offset as i64),
+    ]);
+    let datetime_obj = Class::instantiate(datetime_class(), params)?;
+    return Ok(Value::from(datetime_obj));
+}
+
+fn duration_value(nanos: i128) -> Result<Value, ()> {
+    let seconds = nanos.div_euclid(NANOS_PER_SEC);
+    let Ok(seconds) = i64::try_from(seconds) else {
+        return Err(math_error("Duration out of range")?);
+    };
+    let params = ArrayLiteral::from([
+        Value::from(seconds),
+        Value::from(nanos.rem_euclid(NANOS_PER_SEC) as i64),
+    ]);
+    let duration_obj = Class::instantiate(duration_class(), params)?;
+    return Ok(Value::from(duration_obj));
+}
+
+fn read_datetime(value: &Value) -> Result<DateTime, ()> {
+    let Value::Object(obj) = value else {
+        unreachable!()
+    };
+    let result = object_datetime(&obj.borrow());
+    checked(result)
+}
+
+fn read_duration(value: &Value) -> Result<i128, ()> {
+    let Value::Object(obj) = value else {
+        unreachable!()
+    };
+    let result = object_duration(&obj.borrow());
+    checked(result)
+}
+
+// the Number of `unit` as nanoseconds, like: `1.5` seconds
+fn number_nanos(num: &Number, unit: i128) -> Result<i128, ()> {
+    if let Number::Complex(_, _) = num {
+        return Err(math_error("real number is expected, found Complex number")?);
+    }
+    if let Number::Int(i) = num {
+        return Ok(*i as i128 * unit);
+    }
+    let nanos = num.float_value() * unit as f64;
+    if !nanos.is_finite() || nanos.abs() > i64::MAX as f64 * NANOS_PER_SEC as f64 {
+        return Err(math_error("Duration out of range")?);
+    }
+    Ok(nanos.round() as i128)
+}
+
+// Number for seconds, or Duration
+fn time_nanos(value: &Value) -> Result<i128, ()> {
+    match value {
+        Value::Number(num) => number_nanos(num, NANOS_PER_SEC),
+        _ if TimeModule::is_duration(value) => read_duration(value),
+        _ => Err(type_error(
+            Some("time"),
+            vec![ValueType::Number, ValueType::Object],
+            value.get_type(),
+        )?),
+    }
+}
+
+// UTC offset in minutes, or text like `+08:00`, UTC if omitted
+fn get_offset(scope: &mut Scope) -> Result<i32, ()> {
+    let offset = match get_val("offset", scope)? {
+        Value::Void(_) => 0,
+        Value::Number(num) => num.int_value(),
+        Value::String(str_ref) => match parse_offset(&str_ref.borrow()) {
+            Some(offset) => offset as i64,
+            None => {
+                let msg = format!("invalid UTC offset `{}`", str_ref.borrow());
+                return Err(syntax_error(&msg)?);
+            }
+        },
+        val => {
+            return Err(type_error(
+                Some("UTC offset"),
+                vec![ValueType::Number, ValueType::String],
+                val.get_type(),
+            )?)
+        }
+    };
+    if offset.abs() > MAX_OFFSET as i64 {
+        let msg = format!("UTC offset {} minutes out of range", offset);
+        return Err(math_error(&msg)?);
+    }
+    Ok(offset as i32)
+}
+
+// optional integer field of `Time.date`, ZERO if omitted
+fn get_field(scope: &mut Scope, name: &str) -> Result<u32, ()> {
+    let value = match get_val(name, scope)? {
+        Value::Void(_) => 0,
+        val => val.get_i64()?,
+    };
+    Ok(u32::try_from(value).unwrap_or(u32::MAX))
+}
+
+// --- --- --- --- --- ---
+
+impl ObjectModule for TimeModule {
+    fn module_object() -> Object {
+        let now = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "offset?"),
+            ],
+            identi: BuildInFnIdenti::Time(Self::NOW),
+        };
+        let timestamp = BuildInFunction {
+            params: vec![BuildInFnParam(ValueType::Object, "self")],
+            identi: BuildInFnIdenti::Time(Self::TIMESTAMP),
+        };
+        let mut stopwatch = timestamp.clone();
+        stopwatch.identi = BuildInFnIdenti::Time(Self::STOPWATCH);
+
+        let from_timestamp = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "seconds"),
+                BuildInFnParam(ValueType::Void, "offset?"),
+            ],
+            identi: BuildInFnIdenti::Time(Self::FROMTIMESTAMP),
+        };
+        let date = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "year"),
+                BuildInFnParam(ValueType::Number, "month"),
+                BuildInFnParam(ValueType::Number, "day"),
+                BuildInFnParam(ValueType::Void, "hour?"),
+                BuildInFnParam(ValueType::Void, "minute?"),
+                BuildInFnParam(ValueType::Void, "second?"),
+                BuildInFnParam(ValueType::Void, "offset?"),
+            ],
+            identi: BuildInFnIdenti::Time(Self::DATE),
+        };
+        let parse = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::String, "text"),
+                BuildInFnParam(ValueType::Void, "pattern?"),
+            ],
+            identi: BuildInFnIdenti::Time(Self::PARSE),
+        };
+
+        // Duration constructors, `seconds` as function template
+        let seconds = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Number, "count"),
+            ],
+            identi: BuildInFnIdenti::Time(Self::SECONDS),
+        };
+        let mut days = seconds.clone();
+        let mut hours = seconds.clone();
+        let mut minutes = seconds.clone();
+        let mut millis = seconds.clone();
+        days.identi = BuildInFnIdenti::Time(Self::DAYS);
+        hours.identi = BuildInFnIdenti::Time(Self::HOURS);
+        minutes.identi = BuildInFnIdenti::Time(Self::MINUTES);
+        millis.identi = BuildInFnIdenti::Time(Self::MILLIS);
+
+        let sleep = BuildInFunction {
+            params: vec![
+                BuildInFnParam(ValueType::Object, "self"),
+                BuildInFnParam(ValueType::Void, "time"),
+            ],
+            identi: BuildInFnIdenti::Time(Self::SLEEP),
+        };
+
+        let module_obj_props = vec![
+            (String::from("now"), Value::from(now)),
+            (String::from("timestamp"), Value::from(timestamp)),
+            (String::from("from_timestamp"), Value::from(from_timestamp)),
+            (String::from("date"), Value::from(date)),
+            (String::from("parse"), Value::from(parse)),
+            (String::from("days"), Value::from(days)),
+            (String::from("hours"), Value::from(hours)),
+            (String::from("minutes"), Value::from(minutes)),
+            (String::from("seconds"), Value::from(seconds)),
+            (String::from("millis"), Value::from(millis)),
+            (String::from("stopwatch"), Value::from(stopwatch)),
+            (String::from("sleep"), Value::from(sleep)),
+        ];
+        return Object::new(module_obj_props, None);
+    }
+}
+
+enum Operand {
+    DateTime(DateTime),
+    Duration(i128),
+    Number(Number),
+    Other(ValueType),
+}
+
+impl Operand {
+    fn from(value: &Value) -> Result<Self, ()> {
+        let operand = match value {
+            Value::Number(num) => Self::Number(num.clone()),
+            _ if TimeModule::is_datetime(value) => Self::DateTime(read_datetime(value)?),
+            _ if TimeModule::is_duration(value) => Self::Duration(read_duration(value)?),
+            _ => Self::Other(value.get_type()),
+        };
+        Ok(operand)
+    }
+
+    fn type_name(&self) -> String {
+        match self {
+            Self::DateTime(_) => String::from("DateTime"),
+            Self::Duration(_) => String::from("Duration"),
+            Self::Number(_) => String::from("Number"),
+            Self::Other(value_type) => value_type.to_string(),
+        }
+    }
+}
+
+impl TimeModule {
+    fn is_datetime(value: &Value) -> bool {
+        let Value::Object(obj) = value else {
+            return false;
+        };
+        return is_instance_of(&obj.borrow(), datetime_class());
+    }
+    fn is_duration(value: &Value) -> bool {
+        let Value::Object(obj) = value else {
+            return false;
+        };
+        return is_instance_of(&obj.borrow(), duration_class());
+    }
+
+    // DateTime or Duration
+    pub fn is_time(value: &Value) -> bool {
+        Self::is_datetime(value) || Self::is_duration(value)
+    }
+    pub fn is_time_object(obj: &Object) -> bool {
+        is_instance_of(obj, datetime_class()) || is_instance_of(obj, duration_class())
+    }
+
+    // computing and comparing of DateTime and Duration
+    pub fn operate(val1: &Value, val2: &Value, operator: Symbols) -> Result<Value, ()> {
+        let operand1 = Operand::from(val1)?;
+        let operand2 = Operand::from(val2)?;
+
+        let ordering = match (&operand1, &operand2) {
+            (Operand::DateTime(dt1), Operand::DateTime(dt2)) => Some(dt1.instant.cmp(&dt2.instant)),
+            (Operand::Duration(d1), Operand::Duration(d2)) => Some(d1.cmp(d2)),
+            _ => None,
+        };
+        let compared = match (ordering, operator) {
+            (Some(ordering), Symbols::LessThan) => Some(ordering == Ordering::Less),
+            (Some(ordering), Symbols::MoreThan) => Some(ordering == Ordering::Greater),
+            (Some(ordering), Symbols::LessThanEqual) => Some(ordering != Ordering::Greater),
+            (Some(ordering), Symbols::MoreThanEqual) => Some(ordering != Ordering::Less),
+            (ordering, Symbols::CompareEqual) => Some(ordering == Some(Ordering::Equal)),
+            (ordering, Symbols::NotEqual) => Some(ordering != Some(Ordering::Equal)),
+            _ => None,
+        };
+        if let Some(compared) = compared {
+            return Ok(Value::from(compared));
+        }
+
+        let result = match (&operand1, &operand2, operator) {
+            (Operand::DateTime(date_time), Operand::Duration(nanos), Symbols::Plus)
+            | (Operand::Duration(nanos), Operand::DateTime(date_time), Symbols::Plus) => {
+                let instant = date_time.instant + nanos;
+                datetime_value(checked(DateTime::new(instant, date_time.offset))?)?
+            }
+            (Operand::DateTime(date_time), Operand::Duration(nanos), Symbols::Minus) => {
+                let instant = date_time.instant - nanos;
+                datetime_value(checked(DateTime::new(instant, date_time.offset))?)?
+            }
+            (Operand::DateTime(dt1), Operand::DateTime(dt2), Symbols::Minus) => {
+                duration_value(dt1.instant - dt2.instant)?
+            }
+
+            (Operand::Duration(d1), Operand::Duration(d2), Symbols::Plus) => {
+                duration_value(d1 + d2)?
+            }
+            (Operand::Duration(d1), Operand::Duration(d2), Symbols::Minus) => {
+                duration_value(d1 - d2)?
+            }
+            (Operand::Duration(d1), Operand::Duration(d2), Symbols::Divide) => {
+                if *d2 == 0 {
+                    return Err(math_error("the divisor should not to be ZERO")?);
+                }
+                Value::from(*d1 as f64 / *d2 as f64)
+            }
+            (Operand::Duration(nanos), Operand::Number(num), Symbols::Multiply)
+            | (Operand::Number(num), Operand::Duration(nanos), Symbols::Multiply) => {
+                let scaled = match num {
+                    Number::Int(i) => nanos.checked_mul(*i as i128),
+                    _ => Some((*nanos as f64 * num.float_value()).round() as i128),
+                };
+                match scaled {
+                    Some(scaled) => duration_value(scaled)?,
+                    None => return Err(math_error("Duration out of range")?),
+                }
+            }
+            (Operand::Duration(nanos), Operand::Number(num), Symbols::Divide) => {
+                if num.is_zero() {
+                    return Err(math_error("the divisor should not to be ZERO")?);
+                }
+                let scaled = match num {
+                    Number::Int(i) => nanos / *i as i128,
+                    _ => (*nanos as f64 / num.float_value()).round() as i128,
+                };
+                duration_value(scaled)?
+            }
+            _ => {
+                let msg = format!(
+                    "the operation {} is not available for {} and {}",
+                    operator,
+                    operand1.type_name(),
+                    operand2.type_name()
+                );
+                return Err(math_error(&msg)?);
+            }
+        };
+        Ok(result)
+    }
+
+    pub fn negate(value: &Value) -> Result<Value, ()> {
+        if !Self::is_duration(value) {
+            return Err(math_error("the operation - is not available for DateTime")?);
+        }
+        duration_value(-read_duration(value)?)
+    }
+
+    // DateTime in RFC 3339, Duration like: `1h 30m`
+    pub fn display(f: &mut fmt::Formatter<'_>, obj: &Object) -> Option<fmt::Result> {
+        if is_instance_of(obj, datetime_class()) {
+            let date_time = object_datetime(obj).ok()?;
+            return Some(write!(f, "{}", date_time.iso()));
+        }
+        let nanos = object_duration(obj).ok()?;
+        Some(write!(f, "{}", duration_text(nanos)))
+    }
+}
+
+impl BuildInFnCall for TimeModule {
+    fn call(&self, scope: &mut Scope) -> Result<Value, ()> {
+        let self_value = get_val("self", scope)?;
+
+        let result = match self {
+            Self::NOW => {
+                let offset = get_offset(scope)?;
+                datetime_value(checked(DateTime::new(now_nanos(), offset))?)?
+            }
+            Self::TIMESTAMP => Value::from(now_nanos() as f64 / NANOS_PER_SEC as f64),
+            Self::FROMTIMESTAMP => {
+                let seconds = time_nanos(&get_val("seconds", scope)?)?;
+                let offset = get_offset(scope)?;
+                datetime_value(checked(DateTime::new(seconds, offset))?)?
+            }
+            Self::DATE => {
+                // the second may have fraction part
+                let second_nanos = match get_val("second", scope)? {
+                    Value::Void(_) => 0,
+                    val => time_nanos(&val)?,
+                };
+                let fields = Fields {
+                    year: get_val("year", scope)?.get_i64()?,
+                    month: get_field(scope, "month")?,
+                    day: get_field(scope, "day")?,
+                    hour: get_field(scope, "hour")?,
+                    minute: get_field(scope, "minute")?,
+                    second: u32::try_from(second_nanos / NANOS_PER_SEC).unwrap_or(u32::MAX),
+                    nanosecond: (second_nanos % NANOS_PER_SEC) as u32,
+                };
+                let offset = get_offset(scope)?;
+                datetime_value(checked(DateTime::from_fields(&fields, offset))?)?
+            }
+            Self::PARSE => {
+                // RFC 3339 if pattern is omitted
+                let text_value = get_val("text", scope)?;
+                let text = text_value.get_str()?;
+                let parsed = match get_val("pattern", scope)? {
+                    Value::Void(_) => DateTime::parse_iso(&text),
+                    val => DateTime::parse(&text, &val.get_str()?),
+                };
+                match parsed {
+                    Ok(date_time) => datetime_value(date_time)?,
+                    Err(ParseError::Syntax(msg)) => {
+                        let msg = format!("invalid time text `{}`: {}", text, msg);
+                        return Err(syntax_error(&msg)?);
+                    }
+                    // the date or time does not exist
+                    Err(ParseError::Value(msg)) => {
+                        let msg = format!("invalid time `{}`: {}", text, msg);
+                        return Err(math_error(&msg)?);
+                    }
+                }
+            }
+            Self::DAYS | Self::HOURS | Self::MINUTES | Self::SECONDS | Self::MILLIS => {
+                let unit = match self {
+                    Self::DAYS => 86_400 * NANOS_PER_SEC,
+                    Self::HOURS => 3600 * NANOS_PER_SEC,
+                    Self::MINUTES => 60 * NANOS_PER_SEC,
+                    Self::SECONDS => NANOS_PER_SEC,
+                    Self::MILLIS => NANOS_PER_SEC / 1000,
+                    _ => unreachable!(),
+                };
+                let Value::Number(count) = get_val("count", scope)? else {
+                    unreachable!()
+                };
+                duration_value(number_nanos(&count, unit)?)?
+            }
+            Self::STOPWATCH => {
+                let now = Value::from(monotonic_nanos());
+                let params = ArrayLiteral::from([now.clone(), now]);
+                Value::from(Class::instantiate(stopwatch_class(), params)?)
+            }
+            Self::SLEEP => {
+                let nanos = time_nanos(&get_val("time", scope)?)?;
+                if nanos < 0 {
+                    return Err(math_error("the sleep time should not be negative")?);
+                }
+                thread::sleep(Duration::from_nanos(nanos.min(u64::MAX as i128) as u64));
+                Value::EMPTY
+            }
+
+            Self::FORMAT => {
+                let date_time = read_datetime(&self_value)?;
+                let pattern_value = get_val("pattern", scope)?;
+                let formatted = date_time.format(&pattern_value.get_str()?);
+                match formatted {
+                    Ok(text) => Value::from(text),
+                    Err(msg) => return Err(syntax_error(&msg)?),
+                }
+            }
+            Self::ISO => Value::from(read_datetime(&self_value)?.iso()),
+            Self::TOTIMESTAMP => {
+                let date_time = read_datetime(&self_value)?;
+                Value::from(date_time.instant as f64 / NANOS_PER_SEC as f64)
+            }
+            Self::WEEKDAY => Value::from(read_datetime(&self_value)?.weekday() as i64),
+            Self::YEARDAY => Value::from(read_datetime(&self_value)?.yearday() as i64),
+            Self::TOOFFSET => {
+                let date_time = read_datetime(&self_value)?;
+                let offset = get_offset(scope)?;
+                datetime_value(checked(DateTime::new(date_time.instant, offset))?)?
+            }
+
+            Self::TOTALSECONDS => {
+                let nanos = read_duration(&self_value)?;
+                Value::from(nanos as f64 / NANOS_PER_SEC as f64)
+            }
+            Self::TOTALMILLIS => {
+                let millis = read_duration(&self_value)? / (NANOS_PER_SEC / 1000);
+                Value::from(millis as i64)
+            }
+            Self::ABS => duration_value(read_duration(&self_value)?.abs())?,
+
+            Self::ELAPSED | Self::LAP => {
+                let prop = if *self == Self::ELAPSED {
+                    "start"
+                } else {
+                    "last_lap"
+                };
+                let since = get_self_prop(&self_value, prop)?.get_i64()?;
+                let now = monotonic_nanos();
+                if *self == Self::LAP {
+                    let Value::Object(obj) = &self_value else {
+                        unreachable!()
+                    };
+                    obj.borrow_mut().set("last_lap", Value::from(now))?;
+                }
+                duration_value((now - since) as i128)?
+            }
+            Self::RESET => {
+                let Value::Object(obj) = &self_value else {
+                    unreachable!()
+                };
+                let now = monotonic_nanos();
+                let mut obj_ref = obj.borrow_mut();
+                obj_ref.set("start", Value::from(now))?;
+                obj_ref.set("last_lap", Value::from(now))?;
+                Value::EMPTY
+            }
+        };
+        Ok(result)
+    }
+}
@@ -0,0 +1,641 @@
+// Calendar computing for the proleptic Gregorian calendar with fixed UTC offsets,
+// the instant is stored as nanoseconds since the unix epoch.
+
+pub const NANOS_PER_SEC: i128 = 1_000_000_000;
+const NANOS_PER_MINUTE: i128 = 60 * NANOS_PER_SEC;
+const NANOS_PER_DAY: i128 = 86_400 * NANOS_PER_SEC;
+
+pub const MIN_YEAR: i64 = 1;
+pub const MAX_YEAR: i64 = 9999;
+// less than one day, in minutes
+pub const MAX_OFFSET: i32 = 24 * 60 - 1;
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+// the broken-down local time
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Fields {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DateTime {
+    // nanoseconds since 1970-01-01T00:00:00Z
+    pub instant: i128,
+    // minutes east of UTC
+    pub offset: i32,
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// days since 1970-01-01, Howard Hinnant's `days_from_civil`
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+// inverse of `days_from_civil`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+// like: `+08:00` | `+0800`
+pub fn offset_text(offset: i32, with_colon: bool) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let (hours, minutes) = (offset.abs() / 60, offset.abs() % 60);
+    match with_colon {
+        true => format!("{}{:02}:{:02}", sign, hours, minutes),
+        false => format!("{}{:02}{:02}", sign, hours, minutes),
+    }
+}
+
+// like: `Z` | `UTC` | `+08:00` | `-0530` | `+08`
+pub fn parse_offset(text: &str) -> Option<i32> {
+    let mut cursor = Cursor::new(text);
+    let offset = cursor.offset()?;
+    cursor.is_end().then_some(offset)
+}
+
+// errors of parsing the time text
+pub enum ParseError {
+    // the text does not match the pattern
+    Syntax(String),
+    // the fields are matched but invalid, like: `2023-02-29`
+    Value(String),
+}
+impl From<String> for ParseError {
+    fn from(msg: String) -> Self {
+        Self::Syntax(msg)
+    }
+}
+
+// --- --- --- --- --- ---
+
+impl DateTime {
+    // check the local time range
+    pub fn new(instant: i128, offset: i32) -> Result<Self, String> {
+        if offset.abs() > MAX_OFFSET {
+            return Err(format!("UTC offset {} minutes out of range", offset));
+        }
+        let date_time = Self { instant, offset };
+        let local_days = date_time.local().div_euclid(NANOS_PER_DAY);
+        let min_days = days_from_civil(MIN_YEAR, 1, 1) as i128;
+        let max_days = days_from_civil(MAX_YEAR, 12, 31) as i128;
+        if local_days < min_days || local_days > max_days {
+            return Err(format!(
+                "DateTime out of range, the year should be in {}..={}",
+                MIN_YEAR, MAX_YEAR
+            ));
+        }
+        Ok(date_time)
+    }
+
+    pub fn from_fields(fields: &Fields, offset: i32) -> Result<Self, String> {
+        let Fields {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond,
+        } = *fields;
+
+        let invalid = |name: &str, value: i64| Err(format!("invalid {} `{}`", name, value));
+        if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+            return invalid("year", year);
+        }
+        if !(1..=12).contains(&month) {
+            return invalid("month", month as i64);
+        }
+        if day < 1 || day > days_in_month(year, month) {
+            return invalid("day", day as i64);
+        }
+        if hour > 23 {
+            return invalid("hour", hour as i64);
+        }
+        if minute > 59 {
+            return invalid("minute", minute as i64);
+        }
+        if second > 59 {
+            return invalid("second", second as i64);
+        }
+        if nanosecond as i128 >= NANOS_PER_SEC {
+            return invalid("nanosecond", nanosecond as i64);
+        }
+
+        let days = days_from_civil(year, month, day) as i128;
+        let seconds = (hour * 3600 + minute * 60 + second) as i128;
+        let local = days * NANOS_PER_DAY + seconds * NANOS_PER_SEC + nanosecond as i128;
+        Self::new(local - offset as i128 * NANOS_PER_MINUTE, offset)
+    }
+
+    // nanoseconds since the local epoch
+    fn local(&self) -> i128 {
+        self.instant + self.offset as i128 * NANOS_PER_MINUTE
+    }
+
+    fn days(&self) -> i64 {
+        self.local().div_euclid(NANOS_PER_DAY) as i64
+    }
+
+    pub fn fields(&self) -> Fields {
+        let (year, month, day) = civil_from_days(self.days());
+        let day_nanos = self.local().rem_euclid(NANOS_PER_DAY);
+        let seconds = (day_nanos / NANOS_PER_SEC) as u32;
+        Fields {
+            year,
+            month,
+            day,
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+            nanosecond: (day_nanos % NANOS_PER_SEC) as u32,
+        }
+    }
+
+    // ISO weekday, Monday is 1 and Sunday is 7
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 is Thursday
+        ((self.days() + 3).rem_euclid(7) + 1) as u32
+    }
+
+    // 1 for January 1st
+    pub fn yearday(&self) -> u32 {
+        let Fields { year, .. } = self.fields();
+        (self.days() - days_from_civil(year, 1, 1) + 1) as u32
+    }
+
+    pub fn unix_seconds(&self) -> i64 {
+        self.instant.div_euclid(NANOS_PER_SEC) as i64
+    }
+
+    // RFC 3339, like: `2024-03-01T08:30:00Z` | `2024-03-01T16:30:00.250+08:00`
+    pub fn iso(&self) -> String {
+        let fields = self.fields();
+        let mut result = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            fields.year, fields.month, fields.day, fields.hour, fields.minute, fields.second
+        );
+        if fields.nanosecond > 0 {
+            let mut fraction = format!("{:09}", fields.nanosecond);
+            while fraction.ends_with("000") {
+                fraction.truncate(fraction.len() - 3);
+            }
+            result.push('.');
+            result.push_str(&fraction);
+        }
+        match self.offset {
+            0 => result.push('Z'),
+            offset => result.push_str(&offset_text(offset, true)),
+        }
+        result
+    }
+
+    // strftime-like pattern, like: `%Y-%m-%d %H:%M:%S`
+    pub fn format(&self, pattern: &str) -> Result<String, String> {
+        let fields = self.fields();
+        let mut result = String::new();
+        let mut chars = pattern.chars();
+
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                result.push(ch);
+                continue;
+            }
+            let Some(spec) = chars.next() else {
+                return Err(String::from("incomplete format specifier at the end"));
+            };
+            let hour12 = match fields.hour % 12 {
+                0 => 12,
+                hour => hour,
+            };
+            let text = match spec {
+                'Y' => format!("{:04}", fields.year),
+                'y' => format!("{:02}", fields.year % 100),
+                'm' => format!("{:02}", fields.month),
+                'd' => format!("{:02}", fields.day),
+                'e' => format!("{:>2}", fields.day),
+                'H' => format!("{:02}", fields.hour),
+                'I' => format!("{:02}", hour12),
+                'M' => format!("{:02}", fields.minute),
+                'S' => format!("{:02}", fields.second),
+                'p' => String::from(if fields.hour < 12 { "AM" } else { "PM" }),
+                'f' => format!("{:06}", fields.nanosecond / 1000),
+                // `%3f` | `%6f` | `%9f`
+                '3' | '6' | '9' if chars.clone().next() == Some('f') => {
+                    chars.next();
+                    let digits = spec.to_digit(10).unwrap();
+                    let fraction = fields.nanosecond / 10_u32.pow(9 - digits);
+                    format!("{:0width$}", fraction, width = digits as usize)
+                }
+                'j' => format!("{:03}", self.yearday()),
+                'u' => self.weekday().to_string(),
+                'w' => (self.weekday() % 7).to_string(),
+                'a' => WEEKDAY_NAMES[self.weekday() as usize - 1][..3].to_string(),
+                'A' => WEEKDAY_NAMES[self.weekday() as usize - 1].to_string(),
+                'b' => MONTH_NAMES[fields.month as usize - 1][..3].to_string(),
+                'B' => MONTH_NAMES[fields.month as usize - 1].to_string(),
+                'z' => offset_text(self.offset, false),
+                ':' if chars.clone().next() == Some('z') => {
+                    chars.next();
+                    offset_text(self.offset, true)
+                }
+                'Z' => match self.offset {
+                    0 => String::from("UTC"),
+                    offset => offset_text(offset, true),
+                },
+                's' => self.unix_seconds().to_string(),
+                'F' => self.format("%Y-%m-%d")?,
+                'T' => self.format("%H:%M:%S")?,
+                'n' => String::from("\n"),
+                't' => String::from("\t"),
+                '%' => String::from("%"),
+                _ => return Err(format!("unknown format specifier `%{}`", spec)),
+            };
+            result.push_str(&text);
+        }
+        Ok(result)
+    }
+
+    // RFC 3339 and its common variants, like:
+    // `2024-03-01` | `2024-03-01 08:30` | `2024-03-01T08:30:00.5+08:00`
+    pub fn parse_iso(text: &str) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(text);
+        let year = cursor.number(4, 4, "year")? as i64;
+        cursor.expect('-')?;
+        let month = cursor.number(2, 2, "month")?;
+        cursor.expect('-')?;
+        let day = cursor.number(2, 2, "day")?;
+        let mut fields = Fields {
+            year,
+            month,
+            day,
+            ..Fields::default()
+        };
+
+        let mut offset = 0;
+        if cursor.eat('T') || cursor.eat('t') || cursor.eat(' ') {
+            fields.hour = cursor.number(2, 2, "hour")?;
+            cursor.expect(':')?;
+            fields.minute = cursor.number(2, 2, "minute")?;
+            if cursor.eat(':') {
+                fields.second = cursor.number(2, 2, "second")?;
+                if cursor.eat('.') || cursor.eat(',') {
+                    fields.nanosecond = cursor.fraction()?;
+                }
+            }
+            if !cursor.is_end() {
+                offset = cursor.expect_offset()?;
+            }
+        }
+        if !cursor.is_end() {
+            return Err(cursor.unexpected().into());
+        }
+        Self::from_fields(&fields, offset).map_err(ParseError::Value)
+    }
+
+    // strptime-like pattern, the same specifiers as `format`,
+    // the omitted fields are from `1970-01-01T00:00:00Z`.
+    pub fn parse(text: &str, pattern: &str) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(text);
+        let mut fields = Fields::default();
+        let mut offset = 0;
+        let mut unix_seconds = None;
+        let mut yearday = None;
+        // (hour in 12-hour clock, is PM)
+        let mut hour12 = None;
+        let mut is_pm = None;
+
+        // expand the composed specifiers first
+        let pattern = pattern.replace("%F", "%Y-%m-%d").replace("%T", "%H:%M:%S");
+        let mut chars = pattern.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                cursor.expect(ch)?;
+                continue;
+            }
+            let Some(spec) = chars.next() else {
+                return Err(String::from("incomplete format specifier at the end").into());
+            };
+            match spec {
+                'Y' => fields.year = cursor.number(1, 4, "year")? as i64,
+                'y' => {
+                    // POSIX: 69-99 for 19xx, 00-68 for 20xx
+                    let year = cursor.number(1, 2, "year")? as i64;
+                    fields.year = if year >= 69 { 1900 + year } else { 2000 + year };
+                }
+                'm' => fields.month = cursor.number(1, 2, "month")?,
+                'd' => fields.day = cursor.number(1, 2, "day")?,
+                'e' => {
+                    cursor.eat(' ');
+                    fields.day = cursor.number(1, 2, "day")?;
+                }
+                'H' => fields.hour = cursor.number(1, 2, "hour")?,
+                'I' => hour12 = Some(cursor.number(1, 2, "hour")?),
+                'M' => fields.minute = cursor.number(1, 2, "minute")?,
+                'S' => fields.second = cursor.number(1, 2, "second")?,
+                'p' => is_pm = Some(cursor.meridiem()?),
+                'f' => fields.nanosecond = cursor.fraction()?,
+                '3' | '6' | '9' if chars.clone().next() == Some('f') => {
+                    chars.next();
+                    fields.nanosecond = cursor.fraction()?;
+                }
+                'j' => yearday = Some(cursor.number(1, 3, "day of year")?),
+                'a' | 'A' => {
+                    cursor.name(&WEEKDAY_NAMES, "weekday")?;
+                }
+                'b' | 'B' => fields.month = cursor.name(&MONTH_NAMES, "month")? as u32 + 1,
+                'z' | 'Z' => offset = cursor.expect_offset()?,
+                ':' if chars.clone().next() == Some('z') => {
+                    chars.next();
+                    offset = cursor.expect_offset()?;
+                }
+                's' => unix_seconds = Some(cursor.signed_number("unix timestamp")?),
+                'n' => cursor.expect('\n')?,
+                't' => cursor.expect('\t')?,
+                '%' => cursor.expect('%')?,
+                _ => return Err(format!("unknown format specifier `%{}`", spec).into()),
+            }
+        }
+        if !cursor.is_end() {
+            return Err(cursor.unexpected().into());
+        }
+
+        if let Some(seconds) = unix_seconds {
+            return Self::new(seconds as i128 * NANOS_PER_SEC, offset).map_err(ParseError::Value);
+        }
+        if let Some(hour) = hour12 {
+            if !(1..=12).contains(&hour) {
+                let msg = format!("invalid hour `{}` for 12-hour clock", hour);
+                return Err(ParseError::Value(msg));
+            }
+            fields.hour = hour % 12 + if is_pm == Some(true) { 12 } else { 0 };
+        }
+        if let Some(yearday) = yearday {
+            let year_days = if is_leap_year(fields.year) { 366 } else { 365 };
+            if yearday < 1 || yearday > year_days {
+                let msg = format!("invalid day of year `{}`", yearday);
+                return Err(ParseError::Value(msg));
+            }
+            let days = days_from_civil(fields.year, 1, 1) + yearday as i64 - 1;
+            let (_, month, day) = civil_from_days(days);
+            (fields.month, fields.day) = (month, day);
+        }
+        Self::from_fields(&fields, offset).map_err(ParseError::Value)
+    }
+}
+
+impl Default for Fields {
+    fn default() -> Self {
+        Self {
+            year: 1970,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            nanosecond: 0,
+        }
+    }
+}
+
+// like: `1d 2h 3m 4.5s` | `-250ms` | `0s`
+pub fn duration_text(nanos: i128) -> String {
+    if nanos == 0 {
+        return String::from("0s");
+    }
+    let sign = if nanos < 0 { "-" } else { "" };
+    let nanos = nanos.abs();
+    let (days, rest) = (nanos / NANOS_PER_DAY, nanos % NANOS_PER_DAY);
+    let (hours, rest) = (
+        rest / (60 * NANOS_PER_MINUTE),
+        rest % (60 * NANOS_PER_MINUTE),
+    );
+    let (minutes, rest) = (rest / NANOS_PER_MINUTE, rest % NANOS_PER_MINUTE);
+
+    let mut parts = vec![];
+    if days > 0 {
+        parts.push(format!("{}d", days));
+    }
+    if hours > 0 {
+        parts.push(format!("{}h", hours));
+    }
+    if minutes > 0 {
+        parts.push(format!("{}m", minutes));
+    }
+    if rest > 0 {
+        let seconds = rest / NANOS_PER_SEC;
+        let fraction = format!("{:09}", rest % NANOS_PER_SEC);
+        let fraction = fraction.trim_end_matches('0');
+        match fraction.is_empty() {
+            true => parts.push(format!("{}s", seconds)),
+            false => parts.push(format!("{}.{}s", seconds, fraction)),
+        }
+    }
+    format!("{}{}", sign, parts.join(" "))
+}
+
+// --- --- --- --- --- ---
+
+struct Cursor {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl Cursor {
+    fn new(text: &str) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            index: 0,
+        }
+    }
+
+    fn is_end(&self) -> bool {
+        self.index >= self.chars.len()
+    }
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+    fn unexpected(&self) -> String {
+        match self.peek() {
+            Some(ch) => format!("unexpected character `{}` at index {}", ch, self.index),
+            None => String::from("unexpected end of text"),
+        }
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.index += 1;
+            return true;
+        }
+        false
+    }
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.eat(expected) {
+            true => Ok(()),
+            false => Err(self.unexpected()),
+        }
+    }
+
+    // unsigned decimal with `min..=max` digits
+    fn number(&mut self, min: usize, max: usize, name: &str) -> Result<u32, String> {
+        let start = self.index;
+        while self.index - start < max && self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            self.index += 1;
+        }
+        if self.index - start < min {
+            self.index = start;
+            return Err(format!("expected {} at index {}", name, start));
+        }
+        let digits: String = self.chars[start..self.index].iter().collect();
+        Ok(digits.parse().unwrap())
+    }
+
+    fn signed_number(&mut self, name: &str) -> Result<i64, String> {
+        let is_negative = self.eat('-');
+        if !is_negative {
+            self.eat('+');
+        }
+        let start = self.index;
+        while self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            self.index += 1;
+        }
+        let digits: String = self.chars[start..self.index].iter().collect();
+        match digits.parse::<i64>() {
+            Ok(num) if is_negative => Ok(-num),
+            Ok(num) => Ok(num),
+            Err(_) => Err(format!("expected {} at index {}", name, start)),
+        }
+    }
+
+    // fractional digits of second as nanoseconds, the digits beyond nanosecond are ignored
+    fn fraction(&mut self) -> Result<u32, String> {
+        let start = self.index;
+        let mut nanos = 0;
+        while let Some(digit) = self.peek().and_then(|ch| ch.to_digit(10)) {
+            if self.index - start < 9 {
+                nanos = nanos * 10 + digit;
+            }
+            self.index += 1;
+        }
+        let count = self.index - start;
+        if count == 0 {
+            return Err(format!("expected fraction of second at index {}", start));
+        }
+        Ok(nanos * 10_u32.pow(9 - count.min(9) as u32))
+    }
+
+    // `AM` | `PM`, case insensitive, returns is PM or not
+    fn meridiem(&mut self) -> Result<bool, String> {
+        let index = self.name(&["AM", "PM"], "AM/PM")?;
+        Ok(index == 1)
+    }
+
+    // full or abbreviated name, case insensitive, returns the index of name
+    fn name(&mut self, names: &[&str], kind: &str) -> Result<usize, String> {
+        let rest: String = self.chars[self.index..].iter().collect();
+        let rest = rest.to_lowercase();
+        for (index, name) in names.iter().enumerate() {
+            let name = name.to_lowercase();
+            let abbreviation = &name[..name.len().min(3)];
+            for candidate in [name.as_str(), abbreviation] {
+                if rest.starts_with(candidate) {
+                    self.index += candidate.chars().count();
+                    return Ok(index);
+                }
+            }
+        }
+        Err(format!("expected {} name at index {}", kind, self.index))
+    }
+
+    fn offset(&mut self) -> Option<i32> {
+        if self.eat('Z') || self.eat('z') {
+            return Some(0);
+        }
+        if self.chars[self.index..].starts_with(&['U', 'T', 'C']) {
+            self.index += 3;
+            return Some(0);
+        }
+        let sign = match self.peek()? {
+            '+' => 1,
+            '-' => -1,
+            _ => return None,
+        };
+        self.index += 1;
+        let hours = self.number(2, 2, "hours").ok()? as i32;
+        let has_colon = self.eat(':');
+        let minutes = match self.number(2, 2, "minutes") {
+            Ok(minutes) => minutes as i32,
+            Err(_) if !has_colon => 0,
+            Err(_) => return None,
+        };
+        let offset = hours * 60 + minutes;
+        (minutes < 60 && offset <= MAX_OFFSET).then_some(sign * offset)
+    }
+    fn expect_offset(&mut self) -> Result<i32, String> {
+        let start = self.index;
+        match self.offset() {
+            Some(offset) => Ok(offset),
+            None => Err(format!("expected UTC offset at index {}", start)),
+        }
+    }
+}
@@ -0,0 +1,434 @@
+use crate::public::error::{math_error, range_error, syntax_error, type_error};
+use crate::public::value::big_int::BigInt;
+use crate::public::value::decimal::MAX_DECIMAL_PRECISION;
+use crate::public::value::number::Number;
+use crate::public::value::value::{Value, ValueType};
+
+#[derive(PartialEq, Clone, Copy)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Sign {
+    Minus,
+    Plus,
+    Space,
+}
+
+// format spec like: `[[fill]align][sign][#][0][width][.precision][type]`
+struct FormatSpec {
+    fill: char,
+    align: Option<Align>,
+    sign: Sign,
+    alternate: bool,
+    zero_pad: bool,
+    width: usize,
+    precision: Option<usize>,
+    kind: Option<char>,
+}
+
+const FORMAT_TYPES: &str = "dfeExXbosq%";
+const MAX_FORMAT_WIDTH: usize = 10_000;
+// default max denominator of the float to fraction conversion
+const MAX_DENOMINATOR: i64 = 1_000_000;
+
+fn invalid_spec(spec: &str) -> Result<FormatSpec, ()> {
+    let msg = format!("invalid format spec `{}`", spec);
+    Err(syntax_error(&msg)?)
+}
+
+fn parse_spec(spec: &str) -> Result<FormatSpec, ()> {
+    fn to_align(ch: Option<&char>) -> Option<Align> {
+        match ch {
+            Some('<') => Some(Align::Left),
+            Some('>') => Some(Align::Right),
+            Some('^') => Some(Align::Center),
+            _ => None,
+        }
+    }
+    fn read_digits(chars: &[char], position: &mut usize) -> Option<usize> {
+        let start = *position;
+        while chars.get(*position).is_some_and(|ch| ch.is_ascii_digit()) {
+            *position += 1;
+        }
+        if start == *position {
+            return None;
+        }
+        // too many digits are saturated, and rejected by the range checking
+        let digits: String = chars[start..*position].iter().collect();
+        Some(digits.parse::<usize>().unwrap_or(usize::MAX))
+    }
+
+    let chars: Vec<char> = spec.chars().collect();
+    let mut result = FormatSpec {
+        fill: ' ',
+        align: None,
+        sign: Sign::Minus,
+        alternate: false,
+        zero_pad: false,
+        width: 0,
+        precision: None,
+        kind: None,
+    };
+    let mut position = 0;
+
+    if let Some(align) = to_align(chars.get(1)) {
+        result.fill = chars[0];
+        result.align = Some(align);
+        position = 2;
+    } else if let Some(align) = to_align(chars.first()) {
+        result.align = Some(align);
+        position = 1;
+    }
+    let sign = match chars.get(position) {
+        Some('+') => Some(Sign::Plus),
+        Some(' ') => Some(Sign::Space),
+        Some('-') => Some(Sign::Minus),
+        _ => None,
+    };
+    if let Some(sign) = sign {
+        result.sign = sign;
+        position += 1;
+    }
+    if chars.get(position) == Some(&'#') {
+        result.alternate = true;
+        position += 1;
+    }
+    if chars.get(position) == Some(&'0') {
+        result.zero_pad = true;
+        position += 1;
+    }
+    if let Some(width) = read_digits(&chars, &mut position) {
+        if width > MAX_FORMAT_WIDTH {
+            let expected = format!("0..={}", MAX_FORMAT_WIDTH);
+            return Err(range_error("format width", expected, width)?);
+        }
+        result.width = width;
+    }
+    if chars.get(position) == Some(&'.') {
+        position += 1;
+        let Some(precision) = read_digits(&chars, &mut position) else {
+            return invalid_spec(spec);
+        };
+        if precision > MAX_DECIMAL_PRECISION as usize {
+            let expected = format!("0..={}", MAX_DECIMAL_PRECISION);
+            return Err(range_error("format precision", expected, precision)?);
+        }
+        result.precision = Some(precision);
+    }
+    if let Some(kind) = chars.get(position).filter(|ch| FORMAT_TYPES.contains(**ch)) {
+        result.kind = Some(*kind);
+        position += 1;
+    }
+
+    if position != chars.len() {
+        return invalid_spec(spec);
+    }
+    Ok(result)
+}
+
+// --- --- --- --- --- ---
+
+fn ten_pow(exponent: usize) -> BigInt {
+    BigInt::from(10).pow(exponent as u32)
+}
+
+// exact `upper / lower` with fixed digits,
+// rounded half to even, the same as the Float formatting.
+fn fixed_rational(upper: &BigInt, lower: &BigInt, precision: usize) -> String {
+    let (upper, lower) = if lower.is_negative() {
+        (-upper, -lower)
+    } else {
+        (upper.clone(), lower.clone())
+    };
+    let scaled = &upper.abs() * &ten_pow(precision);
+    let (mut quotient, remainder) = scaled.div_rem(&lower);
+    let half_cmp = (&remainder * &BigInt::from(2)).cmp(&lower);
+    let is_odd = !quotient.div_rem(&BigInt::from(2)).1.is_zero();
+    if half_cmp.is_gt() || (half_cmp.is_eq() && is_odd) {
+        quotient = &quotient + &BigInt::one();
+    }
+
+    let digits = format!("{:0>width$}", quotient.to_string(), width = precision + 1);
+    let (int_part, fraction_part) = digits.split_at(digits.len() - precision);
+    let sign = if upper.is_negative() && !quotient.is_zero() {
+        "-"
+    } else {
+        ""
+    };
+    match precision {
+        0 => format!("{}{}", sign, int_part),
+        _ => format!("{}{}.{}", sign, int_part, fraction_part),
+    }
+}
+
+// (upper, lower) of the real number, the Float is not included
+fn rational_pair(num: &Number) -> Option<(BigInt, BigInt)> {
+    match num {
+        Number::Int(_) | Number::BigInt(_) => Some((num.big_value(), BigInt::one())),
+        Number::Fraction(upper, lower) => Some((upper.clone(), lower.clone())),
+        Number::Decimal(decimal) => Some(decimal.fraction_pair()),
+        _ => None,
+    }
+}
+
+// the closest fraction with limited denominator, by continued fraction
+fn float_to_fraction(value: f64, max_denominator: i64) -> (i64, i64) {
+    let target = value.abs();
+    let (mut upper_prev, mut lower_prev) = (0_i64, 1_i64);
+    let (mut upper, mut lower) = (1_i64, 0_i64);
+    let mut rest = target;
+    loop {
+        let term = rest.floor();
+        if term > i64::MAX as f64 {
+            break;
+        }
+        let term = term as i64;
+        let (Some(next_upper), Some(next_lower)) = (
+            term.checked_mul(upper)
+                .and_then(|n| n.checked_add(upper_prev)),
+            term.checked_mul(lower)
+                .and_then(|n| n.checked_add(lower_prev)),
+        ) else {
+            break;
+        };
+        if next_lower > max_denominator {
+            break;
+        }
+        (upper_prev, lower_prev) = (upper, lower);
+        (upper, lower) = (next_upper, next_lower);
+
+        let fractional = rest - term as f64;
+        let error = (upper as f64 / lower as f64 - target).abs();
+        if fractional < f64::EPSILON || error <= f64::EPSILON * target {
+            break;
+        }
+        rest = 1.0 / fractional;
+    }
+    if lower == 0 {
+        return (0, 1);
+    }
+    let upper = if value.is_sign_negative() {
+        -upper
+    } else {
+        upper
+    };
+    (upper, lower)
+}
+
+fn radix_text(value: &BigInt, radix: u32, is_upper: bool) -> String {
+    let radix_big = BigInt::from(radix as i64);
+    let mut rest = value.abs();
+    let mut digits = Vec::<char>::new();
+    loop {
+        let (quotient, remainder) = rest.div_rem(&radix_big);
+        let digit = remainder.to_i64().unwrap() as u32;
+        digits.push(std::char::from_digit(digit, radix).unwrap());
+        rest = quotient;
+        if rest.is_zero() {
+            break;
+        }
+    }
+    let text: String = digits.into_iter().rev().collect();
+    match is_upper {
+        true => text.to_uppercase(),
+        false => text,
+    }
+}
+
+// (is_negative, prefix, digits)
+fn number_text(num: &Number, spec: &FormatSpec) -> Result<(bool, String, String), ()> {
+    let kind = spec.kind.unwrap_or(match spec.precision {
+        Some(_) => 'f',
+        None => ' ',
+    });
+    let text = match kind {
+        'd' | 'x' | 'X' | 'b' | 'o' => {
+            if !num.is_integer() {
+                let msg = format!("format type '{}' expects an integer", kind);
+                return Err(math_error(&msg)?);
+            }
+            let value = num.big_value();
+            let (radix, prefix) = match kind {
+                'x' => (16, "0x"),
+                'X' => (16, "0X"),
+                'b' => (2, "0b"),
+                'o' => (8, "0o"),
+                _ => (10, ""),
+            };
+            let prefix = if spec.alternate { prefix } else { "" };
+            let digits = radix_text(&value, radix, kind == 'X');
+            return Ok((value.is_negative(), prefix.to_string(), digits));
+        }
+        'f' | '%' => {
+            let precision = spec.precision.unwrap_or(6);
+            let percent = if kind == '%' { "%" } else { "" };
+            match (rational_pair(num), num) {
+                (Some((upper, lower)), _) => {
+                    let upper = match kind {
+                        '%' => &upper * &BigInt::from(100),
+                        _ => upper,
+                    };
+                    fixed_rational(&upper, &lower, precision) + percent
+                }
+                (None, Number::Float(f)) => {
+                    let f = if kind == '%' { f * 100.0 } else { *f };
+                    format!("{:.*}{}", precision, f, percent)
+                }
+                _ => {
+                    let msg = format!("format type '{}' expects a real number", kind);
+                    return Err(math_error(&msg)?);
+                }
+            }
+        }
+        'e' | 'E' => {
+            if let Number::Complex(_, _) | Number::NotANumber = num {
+                let msg = format!("format type '{}' expects a real number", kind);
+                return Err(math_error(&msg)?);
+            }
+            let precision = spec.precision.unwrap_or(6);
+            let text = format!("{:.*e}", precision, num.float_value());
+            match kind {
+                'E' => text.to_uppercase(),
+                _ => text,
+            }
+        }
+        'q' => match num {
+            Number::Int(_) | Number::BigInt(_) => num.to_string(),
+            Number::Fraction(upper, lower) => format!("{}/{}", upper, lower),
+            Number::Decimal(_) | Number::Float(_) => {
+                let (upper, lower) = match num {
+                    Number::Decimal(decimal) => {
+                        let (upper, lower) = decimal.fraction_pair();
+                        let divisor = upper.gcd(&lower);
+                        (upper.div_rem(&divisor).0, lower.div_rem(&divisor).0)
+                    }
+                    _ => {
+                        let max_denominator = match spec.precision {
+                            Some(precision) => 10_i64.saturating_pow(precision as u32),
+                            None => MAX_DENOMINATOR,
+                        };
+                        let (upper, lower) = float_to_fraction(num.float_value(), max_denominator);
+                        (BigInt::from(upper), BigInt::from(lower))
+                    }
+                };
+                if lower == BigInt::one() {
+                    upper.to_string()
+                } else {
+                    format!("{}/{}", upper, lower)
+                }
+            }
+            _ => return Err(math_error("format type 'q' expects a real number")?),
+        },
+        _ => num.to_string(),
+    };
+
+    match text.strip_prefix('-') {
+        Some(rest) => Ok((true, String::new(), rest.to_string())),
+        None => Ok((false, String::new(), text)),
+    }
+}
+
+fn pad(text: String, spec: &FormatSpec, default_align: Align) -> String {
+    let padding = spec.width.saturating_sub(text.chars().count());
+    let (left, right) = match spec.align.unwrap_or(default_align) {
+        Align::Left => (0, padding),
+        Align::Right => (padding, 0),
+        Align::Center => (padding / 2, padding - padding / 2),
+    };
+    let fill = spec.fill.to_string();
+    fill.repeat(left) + &text + &fill.repeat(right)
+}
+
+fn format_value(value: &Value, spec: &FormatSpec) -> Result<String, ()> {
+    let num = match value {
+        Value::Number(num) if spec.kind != Some('s') => num,
+        _ => {
+            if spec.kind.is_some_and(|kind| kind != 's') {
+                return Err(type_error(
+                    Some("format"),
+                    vec![ValueType::Number],
+                    value.get_type(),
+                )?);
+            }
+            // precision truncates the text
+            let text = value.to_raw_string();
+            let text = match spec.precision {
+                Some(precision) => text.chars().take(precision).collect(),
+                None => text,
+            };
+            return Ok(pad(text, spec, Align::Left));
+        }
+    };
+
+    let (is_negative, prefix, digits) = number_text(num, spec)?;
+    let sign = match (is_negative, spec.sign) {
+        (true, _) => "-",
+        (false, Sign::Plus) => "+",
+        (false, Sign::Space) => " ",
+        (false, Sign::Minus) => "",
+    };
+    if spec.zero_pad && spec.align.is_none() {
+        // ZEROs are between the sign and the digits: `-0042`
+        let head = format!("{}{}", sign, prefix);
+        let width = spec.width.saturating_sub(head.chars().count());
+        return Ok(format!("{}{:0>width$}", head, digits, width = width));
+    }
+    Ok(pad(
+        format!("{}{}{}", sign, prefix, digits),
+        spec,
+        Align::Right,
+    ))
+}
+
+// format template like: `"x = {:.3}, y = {1:>8}, {{escaped}}"`
+pub fn format_values(template: &str, args: &[Value]) -> Result<String, ()> {
+    let mut result = String::new();
+    let mut chars = template.chars().peekable();
+    let mut auto_index = 0;
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                result.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                result.push('}');
+            }
+            '}' => return Err(syntax_error("single '}' in format template")?),
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(ch) => field.push(ch),
+                        None => return Err(syntax_error("unclosed '{' in format template")?),
+                    }
+                }
+                let (index_part, spec_part) = field.split_once(':').unwrap_or((&field, ""));
+                let index = if index_part.trim().is_empty() {
+                    auto_index += 1;
+                    auto_index - 1
+                } else {
+                    let Ok(index) = index_part.trim().parse::<usize>() else {
+                        let msg = format!("invalid format argument index `{}`", index_part);
+                        return Err(syntax_error(&msg)?);
+                    };
+                    index
+                };
+                let Some(arg) = args.get(index) else {
+                    return Err(range_error("format arguments", index + 1, args.len())?);
+                };
+                let spec = parse_spec(spec_part)?;
+                result.push_str(&format_value(arg, &spec)?);
+            }
+            _ => result.push(ch),
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,18 @@
+use crate::public::error::type_error;
+use crate::public::run_time::scope::Scope;
+use crate::public::value::value::{Value, ValueType};
+
+use super::get_val::get_val;
+
+// used to get the callable param, which is Function or LazyExpression.
+pub fn get_callee(val_name: &str, scope: &mut Scope) -> Result<Value, ()> {
+    let callee = get_val(val_name, scope)?;
+    match callee {
+        Value::Function(_) | Value::LazyExpression(_) => Ok(callee),
+        _ => Err(type_error(
+            Some(val_name),
+            vec![ValueType::Function, ValueType::LazyExpression],
+            callee.get_type(),
+        )?),
+    }
+}
@@ -1,3 +1,7 @@
+pub mod date_time;
+pub mod formatter;
+pub mod get_callee;
 pub mod get_self_prop;
 pub mod get_val;
+pub mod regex;
 pub mod str_to_num;
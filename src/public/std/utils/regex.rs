@@ -0,0 +1,880 @@
+use crate::public::error::range_error;
+
+// backtracking regular expression engine, the indexes are character based.
+// supported syntax:
+// literals and escapes: `a` | `\.` | `\n` | `\t` | `\x41` | `\u{4e2d}`
+// classes: `.` | `[a-z]` | `[^0-9_]` | `\d` | `\D` | `\w` | `\W` | `\s` | `\S`
+// anchors: `^` | `$` | `\b` | `\B`
+// groups: `(...)` | `(?:...)` | `(?<name>...)` | `(?P<name>...)`
+// back-references: `\1` | `\k<name>`
+// quantifiers: `*` | `+` | `?` | `{n}` | `{n,}` | `{n,m}`, lazy with a `?` suffix
+// alternation: `a|b`
+// leading inline flags: `(?i)` ignore case | `(?m)` multi-line | `(?s)` dot matches newline
+
+// max repetition count of `{n,m}`
+const MAX_REPEAT: usize = 1000;
+// max instructions of the compiled program, nested repetitions multiply the size
+const MAX_PROGRAM: usize = 100_000;
+// max backtracking steps for matching at one position
+const MAX_STEPS: usize = 1_000_000;
+// the steps before the visited table is used
+const FAST_STEPS: usize = 10_000;
+// max bits of the visited (instruction, position) table
+const MAX_VISITED: usize = 1 << 25;
+
+pub struct PatternError {
+    // character index in the pattern
+    pub position: usize,
+    pub msg: &'static str,
+}
+
+#[derive(Clone, Copy)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+impl ClassItem {
+    fn contains(&self, ch: char) -> bool {
+        match *self {
+            ClassItem::Range(start, end) => start <= ch && ch <= end,
+            ClassItem::Digit(negated) => ch.is_ascii_digit() != negated,
+            ClassItem::Word(negated) => is_word_char(ch) != negated,
+            ClassItem::Space(negated) => ch.is_whitespace() != negated,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CharClass {
+    negated: bool,
+    items: Vec<ClassItem>,
+}
+impl CharClass {
+    fn single(item: ClassItem) -> Self {
+        Self {
+            negated: false,
+            items: vec![item],
+        }
+    }
+    fn contains(&self, ch: char, ignore_case: bool) -> bool {
+        let is_in = |ch: char| self.items.iter().any(|item| item.contains(ch));
+        let mut result = is_in(ch);
+        if ignore_case && !result {
+            result = is_in(lower(ch)) || ch.to_uppercase().any(is_in);
+        }
+        result != self.negated
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Assertion {
+    Start,
+    End,
+    WordBoundary(bool),
+}
+
+#[derive(Clone)]
+enum Node {
+    Empty,
+    Char(char),
+    Any,
+    Class(CharClass),
+    Assert(Assertion),
+    BackRef(usize),
+    Group(Box<Node>, Option<usize>),
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+        greedy: bool,
+    },
+}
+
+impl Node {
+    // count of the compiled instructions, saturated on overflow
+    fn program_size(&self) -> usize {
+        match self {
+            Self::Empty => 0,
+            Self::Char(_) | Self::Any | Self::Class(_) | Self::Assert(_) | Self::BackRef(_) => 1,
+            Self::Group(inner, index) => {
+                let saves = if index.is_some() { 2 } else { 0 };
+                inner.program_size().saturating_add(saves)
+            }
+            Self::Concat(nodes) => nodes
+                .iter()
+                .fold(0, |size, node| size.saturating_add(node.program_size())),
+            Self::Alternate(branches) => branches.iter().fold(0, |size, branch| {
+                // split and jump for each branch except the last one
+                size.saturating_add(branch.program_size()).saturating_add(2)
+            }),
+            Self::Repeat { node, min, max, .. } => {
+                let size = node.program_size();
+                let optional = match max {
+                    None => size.saturating_add(4),
+                    Some(max) => (max - min).saturating_mul(size.saturating_add(1)),
+                };
+                min.saturating_mul(size).saturating_add(optional)
+            }
+        }
+    }
+}
+
+enum Inst {
+    Char(char),
+    Any,
+    Class(CharClass),
+    Assert(Assertion),
+    BackRef(usize),
+    // try the first branch, then the second one when backtracking
+    Split(usize, usize),
+    Jump(usize),
+    // record the current position into the slot
+    Save(usize),
+    // fail when the loop made no progress since the slot is saved
+    Progress(usize),
+    Match,
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+fn lower(ch: char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+// --- --- --- --- --- ---
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    group_count: usize,
+    names: Vec<(String, usize)>,
+    max_backref: usize,
+    has_backref: bool,
+}
+
+impl Parser {
+    fn error<T>(&self, msg: &'static str) -> Result<T, PatternError> {
+        Err(PatternError {
+            position: self.position,
+            msg,
+        })
+    }
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+    fn eat(&mut self, ch: char) -> bool {
+        if self.peek() == Some(ch) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+    fn read_number(&mut self) -> Option<usize> {
+        let start = self.position;
+        while self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            self.position += 1;
+        }
+        let digits: String = self.chars[start..self.position].iter().collect();
+        digits.parse::<usize>().ok()
+    }
+
+    fn alternation(&mut self) -> Result<Node, PatternError> {
+        let mut branches = vec![self.concat()?];
+        while self.eat('|') {
+            branches.push(self.concat()?);
+        }
+        if branches.len() == 1 {
+            return Ok(branches.pop().unwrap());
+        }
+        Ok(Node::Alternate(branches))
+    }
+
+    fn concat(&mut self) -> Result<Node, PatternError> {
+        let mut nodes = vec![];
+        while let Some(ch) = self.peek() {
+            if ch == '|' || ch == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantifier(atom)?);
+        }
+        match nodes.len() {
+            0 => Ok(Node::Empty),
+            1 => Ok(nodes.pop().unwrap()),
+            _ => Ok(Node::Concat(nodes)),
+        }
+    }
+
+    // quantifier like: `*` | `+?` | `{2,3}`
+    fn quantifier(&mut self, atom: Node) -> Result<Node, PatternError> {
+        let start = self.position;
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.position += 1;
+                let Some(min) = self.read_number() else {
+                    // not a quantifier, `{` as literal
+                    self.position = start;
+                    return Ok(atom);
+                };
+                let max = if self.eat(',') {
+                    match self.peek() {
+                        Some('}') => None,
+                        _ => match self.read_number() {
+                            Some(max) => Some(max),
+                            None => {
+                                self.position = start;
+                                return Ok(atom);
+                            }
+                        },
+                    }
+                } else {
+                    Some(min)
+                };
+                if self.peek() != Some('}') {
+                    self.position = start;
+                    return Ok(atom);
+                }
+                if max.is_some_and(|max| max < min) {
+                    return self.error("invalid repetition range");
+                }
+                if min.max(max.unwrap_or(0)) > MAX_REPEAT {
+                    return self.error("repetition count is too large");
+                }
+                (min, max)
+            }
+            _ => return Ok(atom),
+        };
+        self.position += 1;
+
+        if matches!(atom, Node::Assert(_) | Node::Empty) {
+            self.position = start;
+            return self.error("nothing to repeat");
+        }
+        let greedy = !self.eat('?');
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+            greedy,
+        })
+    }
+
+    fn atom(&mut self) -> Result<Node, PatternError> {
+        let ch = self.peek().unwrap();
+        self.position += 1;
+
+        let node = match ch {
+            '(' => self.group()?,
+            '[' => Node::Class(self.class()?),
+            '.' => Node::Any,
+            '^' => Node::Assert(Assertion::Start),
+            '$' => Node::Assert(Assertion::End),
+            '\\' => self.escape()?,
+            '*' | '+' | '?' => {
+                self.position -= 1;
+                return self.error("nothing to repeat");
+            }
+            _ => Node::Char(ch),
+        };
+        Ok(node)
+    }
+
+    fn group(&mut self) -> Result<Node, PatternError> {
+        let mut capture = true;
+        let mut name = None::<String>;
+
+        if self.eat('?') {
+            if self.eat(':') {
+                capture = false;
+            } else if self.eat('<') || (self.eat('P') && self.eat('<')) {
+                let start = self.position;
+                while self.peek().is_some_and(is_word_char) {
+                    self.position += 1;
+                }
+                let group_name: String = self.chars[start..self.position].iter().collect();
+                if group_name.is_empty() || !self.eat('>') {
+                    return self.error("invalid group name");
+                }
+                if self.names.iter().any(|(existed, _)| *existed == group_name) {
+                    self.position = start;
+                    return self.error("duplicated group name");
+                }
+                name = Some(group_name);
+            } else {
+                return self.error("unsupported group syntax");
+            }
+        }
+
+        let index = if capture {
+            self.group_count += 1;
+            if let Some(name) = name {
+                self.names.push((name, self.group_count));
+            }
+            Some(self.group_count)
+        } else {
+            None
+        };
+
+        let inner = self.alternation()?;
+        if !self.eat(')') {
+            return self.error("unclosed group");
+        }
+        Ok(Node::Group(Box::new(inner), index))
+    }
+
+    // character class like: `[a-z_]` | `[^\d]`
+    fn class(&mut self) -> Result<CharClass, PatternError> {
+        let negated = self.eat('^');
+        let mut items = vec![];
+
+        let mut is_first = true;
+        loop {
+            let Some(ch) = self.peek() else {
+                return self.error("unclosed character class");
+            };
+            self.position += 1;
+
+            if ch == ']' && !is_first {
+                break;
+            }
+            is_first = false;
+
+            let start = match ch {
+                '\\' => match self.class_escape()? {
+                    Ok(item) => {
+                        items.push(item);
+                        continue;
+                    }
+                    Err(ch) => ch,
+                },
+                _ => ch,
+            };
+
+            // range like: `a-z`
+            let is_range = self.peek() == Some('-')
+                && self
+                    .chars
+                    .get(self.position + 1)
+                    .is_some_and(|ch| *ch != ']');
+            if !is_range {
+                items.push(ClassItem::Range(start, start));
+                continue;
+            }
+            self.position += 1;
+            let range_position = self.position;
+            let end = match self.chars[self.position] {
+                '\\' => {
+                    self.position += 1;
+                    match self.class_escape()? {
+                        Ok(_) => return self.error("invalid character range"),
+                        Err(ch) => ch,
+                    }
+                }
+                ch => {
+                    self.position += 1;
+                    ch
+                }
+            };
+            if end < start {
+                self.position = range_position;
+                return self.error("invalid character range");
+            }
+            items.push(ClassItem::Range(start, end));
+        }
+        Ok(CharClass { negated, items })
+    }
+
+    // the escape inside character class, `Ok` for class and `Err` for single character
+    fn class_escape(&mut self) -> Result<Result<ClassItem, char>, PatternError> {
+        match self.escape()? {
+            Node::Char(ch) => Ok(Err(ch)),
+            Node::Class(class) if class.items.len() == 1 => Ok(Ok(class.items[0])),
+            Node::Assert(Assertion::WordBoundary(false)) => Ok(Err('\u{8}')),
+            _ => self.error("invalid escape in character class"),
+        }
+    }
+
+    fn escape(&mut self) -> Result<Node, PatternError> {
+        let Some(ch) = self.peek() else {
+            return self.error("trailing backslash");
+        };
+        self.position += 1;
+
+        let node = match ch {
+            'd' | 'D' => Node::Class(CharClass::single(ClassItem::Digit(ch == 'D'))),
+            'w' | 'W' => Node::Class(CharClass::single(ClassItem::Word(ch == 'W'))),
+            's' | 'S' => Node::Class(CharClass::single(ClassItem::Space(ch == 'S'))),
+            'b' => Node::Assert(Assertion::WordBoundary(false)),
+            'B' => Node::Assert(Assertion::WordBoundary(true)),
+            'n' => Node::Char('\n'),
+            't' => Node::Char('\t'),
+            'r' => Node::Char('\r'),
+            'f' => Node::Char('\u{c}'),
+            'v' => Node::Char('\u{b}'),
+            '0' => Node::Char('\0'),
+            '1'..='9' => {
+                self.position -= 1;
+                let index = self.read_number().unwrap();
+                self.max_backref = self.max_backref.max(index);
+                self.has_backref = true;
+                Node::BackRef(index)
+            }
+            'k' => {
+                if !self.eat('<') {
+                    return self.error("expected '<' after `\\k`");
+                }
+                let start = self.position;
+                while self.peek().is_some_and(|ch| ch != '>') {
+                    self.position += 1;
+                }
+                let name: String = self.chars[start..self.position].iter().collect();
+                let found = self.names.iter().find(|(existed, _)| *existed == name);
+                let Some((_, index)) = found else {
+                    self.position = start;
+                    return self.error("unknown group name");
+                };
+                let index = *index;
+                if !self.eat('>') {
+                    return self.error("invalid group name");
+                }
+                self.has_backref = true;
+                Node::BackRef(index)
+            }
+            'x' => {
+                let digits: String = self.chars.iter().skip(self.position).take(2).collect();
+                let is_valid = digits.len() == 2 && digits.chars().all(|ch| ch.is_ascii_hexdigit());
+                if !is_valid {
+                    return self.error("invalid hex escape");
+                }
+                self.position += 2;
+                let code = u32::from_str_radix(&digits, 16).unwrap();
+                Node::Char(char::from_u32(code).unwrap())
+            }
+            'u' => {
+                if !self.eat('{') {
+                    return self.error("expected '{' after `\\u`");
+                }
+                let start = self.position;
+                while self.peek().is_some_and(|ch| ch.is_ascii_hexdigit()) {
+                    self.position += 1;
+                }
+                let digits: String = self.chars[start..self.position].iter().collect();
+                let code_point = u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|_| digits.len() <= 6)
+                    .and_then(char::from_u32);
+                match code_point {
+                    Some(ch) if self.eat('}') => Node::Char(ch),
+                    _ => return self.error("invalid unicode escape"),
+                }
+            }
+            _ if ch.is_ascii_alphanumeric() => {
+                self.position -= 1;
+                return self.error("unknown escape sequence");
+            }
+            // escaped punctuation and other characters
+            _ => Node::Char(ch),
+        };
+        Ok(node)
+    }
+}
+
+// --- --- --- --- --- ---
+
+struct Compiler {
+    program: Vec<Inst>,
+    // the next slot for loop progress checking
+    slot_count: usize,
+}
+
+impl Compiler {
+    fn emit(&mut self, inst: Inst) -> usize {
+        self.program.push(inst);
+        self.program.len() - 1
+    }
+    // set the jump target of the `Split` or `Jump` instruction
+    fn patch(&mut self, index: usize, target: usize, is_second: bool) {
+        match &mut self.program[index] {
+            Inst::Split(first, second) => {
+                if is_second {
+                    *second = target;
+                } else {
+                    *first = target;
+                }
+            }
+            Inst::Jump(to) => *to = target,
+            _ => unreachable!(),
+        }
+    }
+
+    fn compile(&mut self, node: &Node) {
+        match node {
+            Node::Empty => {}
+            Node::Char(ch) => {
+                self.emit(Inst::Char(*ch));
+            }
+            Node::Any => {
+                self.emit(Inst::Any);
+            }
+            Node::Class(class) => {
+                self.emit(Inst::Class(class.clone()));
+            }
+            Node::Assert(assertion) => {
+                self.emit(Inst::Assert(*assertion));
+            }
+            Node::BackRef(index) => {
+                self.emit(Inst::BackRef(*index));
+            }
+            Node::Group(inner, index) => match index {
+                Some(index) => {
+                    self.emit(Inst::Save(index * 2));
+                    self.compile(inner);
+                    self.emit(Inst::Save(index * 2 + 1));
+                }
+                None => self.compile(inner),
+            },
+            Node::Concat(nodes) => {
+                for node in nodes {
+                    self.compile(node);
+                }
+            }
+            Node::Alternate(branches) => {
+                let mut jumps = vec![];
+                for (index, branch) in branches.iter().enumerate() {
+                    if index == branches.len() - 1 {
+                        self.compile(branch);
+                        break;
+                    }
+                    let split = self.emit(Inst::Split(0, 0));
+                    self.patch(split, split + 1, false);
+                    self.compile(branch);
+                    jumps.push(self.emit(Inst::Jump(0)));
+                    let next = self.program.len();
+                    self.patch(split, next, true);
+                }
+                let end = self.program.len();
+                for jump in jumps {
+                    self.patch(jump, end, false);
+                }
+            }
+            Node::Repeat {
+                node,
+                min,
+                max,
+                greedy,
+            } => {
+                for _ in 0..*min {
+                    self.compile(node);
+                }
+                match max {
+                    None => {
+                        // loop: split -> save -> node -> progress -> jump to split
+                        let slot = self.slot_count;
+                        self.slot_count += 1;
+
+                        let split = self.emit(Inst::Split(0, 0));
+                        self.patch(split, split + 1, !greedy);
+                        self.emit(Inst::Save(slot));
+                        self.compile(node);
+                        self.emit(Inst::Progress(slot));
+                        self.emit(Inst::Jump(split));
+                        let end = self.program.len();
+                        self.patch(split, end, *greedy);
+                    }
+                    Some(max) => {
+                        // optional copies, any failed copy skips the rest
+                        let mut splits = vec![];
+                        for _ in *min..*max {
+                            let split = self.emit(Inst::Split(0, 0));
+                            self.patch(split, split + 1, !greedy);
+                            splits.push(split);
+                            self.compile(node);
+                        }
+                        let end = self.program.len();
+                        for split in splits {
+                            self.patch(split, end, *greedy);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// --- --- --- --- --- ---
+
+// the (start, end) of the whole match and each group, `None` for unmatched group
+pub type Captures = Vec<Option<(usize, usize)>>;
+
+pub struct Regex {
+    program: Vec<Inst>,
+    // count of capture groups, including the whole match
+    group_count: usize,
+    slot_count: usize,
+    names: Vec<(String, usize)>,
+    has_backref: bool,
+
+    ignore_case: bool,
+    multi_line: bool,
+    dot_all: bool,
+}
+
+enum Job {
+    Thread(usize, usize),
+    Restore(usize, Option<usize>),
+}
+
+enum Outcome {
+    Matched(Captures),
+    Failed,
+    // the backtracking steps exceeded
+    Exceeded(usize),
+}
+
+impl Regex {
+    pub fn new(pattern: &str) -> Result<Self, PatternError> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            position: 0,
+            group_count: 0,
+            names: vec![],
+            max_backref: 0,
+            has_backref: false,
+        };
+
+        // leading inline flags: `(?i)` | `(?ms)`
+        let (mut ignore_case, mut multi_line, mut dot_all) = (false, false, false);
+        let flag_end = parser.chars.iter().position(|ch| *ch == ')');
+        let is_flags = parser.chars.starts_with(&['(', '?'])
+            && flag_end.is_some_and(|end| {
+                end > 2 && parser.chars[2..end].iter().all(|ch| "ims".contains(*ch))
+            });
+        if is_flags {
+            let end = flag_end.unwrap();
+            for flag in &parser.chars[2..end] {
+                match flag {
+                    'i' => ignore_case = true,
+                    'm' => multi_line = true,
+                    _ => dot_all = true,
+                }
+            }
+            parser.position = end + 1;
+        }
+
+        let node = parser.alternation()?;
+        if parser.position < parser.chars.len() {
+            // only unmatched `)` stops the top-level alternation
+            return parser.error("unmatched ')'");
+        }
+        if parser.max_backref > parser.group_count {
+            return Err(PatternError {
+                position: parser.chars.len(),
+                msg: "back-reference to undefined group",
+            });
+        }
+
+        if node.program_size() > MAX_PROGRAM {
+            return Err(PatternError {
+                position: parser.chars.len(),
+                msg: "pattern too large",
+            });
+        }
+
+        let group_count = parser.group_count + 1;
+        let mut compiler = Compiler {
+            program: vec![],
+            slot_count: group_count * 2,
+        };
+        compiler.emit(Inst::Save(0));
+        compiler.compile(&node);
+        compiler.emit(Inst::Save(1));
+        compiler.emit(Inst::Match);
+
+        Ok(Self {
+            program: compiler.program,
+            group_count,
+            slot_count: compiler.slot_count,
+            names: parser.names,
+            has_backref: parser.has_backref,
+            ignore_case,
+            multi_line,
+            dot_all,
+        })
+    }
+
+    pub fn group_count(&self) -> usize {
+        self.group_count
+    }
+    pub fn group_names(&self) -> &[(String, usize)] {
+        &self.names
+    }
+    pub fn group_index(&self, name: &str) -> Option<usize> {
+        let found = self.names.iter().find(|(existed, _)| existed == name);
+        found.map(|(_, index)| *index)
+    }
+
+    // find the leftmost match starting from `start`
+    pub fn search(&self, text: &[char], start: usize) -> Result<Option<Captures>, ()> {
+        // without back-references, the failed (instruction, position) always fails again,
+        // so that each of them is tried at most once after the fast steps exceeded.
+        let table_size = self.program.len() * (text.len() + 1);
+        let is_memorable = !self.has_backref && table_size <= MAX_VISITED;
+        let mut visited = None::<Vec<u64>>;
+
+        for position in start..=text.len() {
+            let max_steps = match (&visited, is_memorable) {
+                (Some(_), _) => usize::MAX,
+                (None, true) => FAST_STEPS,
+                (None, false) => MAX_STEPS,
+            };
+            let mut outcome = self.match_at(text, position, &mut visited, max_steps);
+            if is_memorable && visited.is_none() && matches!(outcome, Outcome::Exceeded(_)) {
+                visited = Some(vec![0; table_size.div_ceil(64)]);
+                outcome = self.match_at(text, position, &mut visited, usize::MAX);
+            }
+
+            match outcome {
+                Outcome::Matched(captures) => return Ok(Some(captures)),
+                Outcome::Failed => {}
+                Outcome::Exceeded(steps) => {
+                    return Err(range_error("regex backtracking steps", MAX_STEPS, steps)?)
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn check_assertion(&self, assertion: Assertion, text: &[char], position: usize) -> bool {
+        match assertion {
+            Assertion::Start => position == 0 || (self.multi_line && text[position - 1] == '\n'),
+            Assertion::End => position == text.len() || (self.multi_line && text[position] == '\n'),
+            Assertion::WordBoundary(negated) => {
+                let before = position > 0 && is_word_char(text[position - 1]);
+                let after = position < text.len() && is_word_char(text[position]);
+                (before != after) != negated
+            }
+        }
+    }
+
+    fn char_eq(&self, ch1: char, ch2: char) -> bool {
+        ch1 == ch2 || (self.ignore_case && lower(ch1) == lower(ch2))
+    }
+
+    fn match_at(
+        &self,
+        text: &[char],
+        start: usize,
+        visited: &mut Option<Vec<u64>>,
+        max_steps: usize,
+    ) -> Outcome {
+        let mut slots = vec![None::<usize>; self.slot_count];
+        let mut stack = vec![Job::Thread(0, start)];
+        let mut steps = 0;
+
+        while let Some(job) = stack.pop() {
+            let (mut pc, mut position) = match job {
+                Job::Thread(pc, position) => (pc, position),
+                Job::Restore(slot, value) => {
+                    slots[slot] = value;
+                    continue;
+                }
+            };
+
+            loop {
+                steps += 1;
+                if steps > max_steps {
+                    return Outcome::Exceeded(steps);
+                }
+                if let Some(visited) = visited {
+                    let bit = pc * (text.len() + 1) + position;
+                    if visited[bit / 64] & (1 << (bit % 64)) != 0 {
+                        break;
+                    }
+                    visited[bit / 64] |= 1 << (bit % 64);
+                }
+
+                let current = text.get(position).copied();
+                let is_passed = match &self.program[pc] {
+                    Inst::Char(ch) => current.is_some_and(|current| self.char_eq(current, *ch)),
+                    Inst::Any => current.is_some_and(|current| self.dot_all || current != '\n'),
+                    Inst::Class(class) => {
+                        current.is_some_and(|current| class.contains(current, self.ignore_case))
+                    }
+                    Inst::Assert(assertion) => {
+                        if !self.check_assertion(*assertion, text, position) {
+                            break;
+                        }
+                        pc += 1;
+                        continue;
+                    }
+                    Inst::BackRef(index) => {
+                        let (Some(group_start), Some(group_end)) =
+                            (slots[index * 2], slots[index * 2 + 1])
+                        else {
+                            break;
+                        };
+                        let length = group_end - group_start;
+                        let is_matched = position + length <= text.len()
+                            && (0..length).all(|offset| {
+                                self.char_eq(text[group_start + offset], text[position + offset])
+                            });
+                        if !is_matched {
+                            break;
+                        }
+                        pc += 1;
+                        position += length;
+                        continue;
+                    }
+                    Inst::Split(first, second) => {
+                        stack.push(Job::Thread(*second, position));
+                        pc = *first;
+                        continue;
+                    }
+                    Inst::Jump(to) => {
+                        pc = *to;
+                        continue;
+                    }
+                    Inst::Save(slot) => {
+                        stack.push(Job::Restore(*slot, slots[*slot]));
+                        slots[*slot] = Some(position);
+                        pc += 1;
+                        continue;
+                    }
+                    Inst::Progress(slot) => {
+                        // the visited table also stops the empty loop
+                        if visited.is_none() && slots[*slot] == Some(position) {
+                            break;
+                        }
+                        pc += 1;
+                        continue;
+                    }
+                    Inst::Match => {
+                        let captures = (0..self.group_count)
+                            .map(|index| match (slots[index * 2], slots[index * 2 + 1]) {
+                                (Some(start), Some(end)) => Some((start, end)),
+                                _ => None,
+                            })
+                            .collect();
+                        return Outcome::Matched(captures);
+                    }
+                };
+
+                // single character matching instructions
+                if !is_passed {
+                    break;
+                }
+                pc += 1;
+                position += 1;
+            }
+        }
+        Outcome::Failed
+    }
+}
@@ -1,6 +1,8 @@
 use std::{cell::Ref, str::FromStr};
 
 use crate::public::error::syntax_error;
+use crate::public::value::big_int::BigInt;
+use crate::public::value::number::Number;
 
 pub fn str_to_num<T: FromStr>(str: Ref<String>) -> Result<T, ()> {
     // i64 || f64
@@ -9,3 +11,34 @@ pub fn str_to_num<T: FromStr>(str: Ref<String>) -> Result<T, ()> {
         Err(_) => Err(syntax_error("invalid string parse")?),
     }
 }
+
+// Int, BigInt or Float without error output, like: `"42"` | `"-1.5"` | `"6.02e23"`,
+// words like `inf` and `NaN` are not numbers here,
+// and digits with leading zeros are kept as text, like: `"02134"`.
+pub fn parse_number(str: &str) -> Option<Number> {
+    let is_numeric = str.chars().any(|ch| ch.is_ascii_digit())
+        && str
+            .chars()
+            .all(|ch| ch.is_ascii_digit() || matches!(ch, '+' | '-' | '.' | 'e' | 'E'));
+    if !is_numeric {
+        return None;
+    }
+
+    let (is_negative, digits) = match str.as_bytes()[0] {
+        b'-' => (true, &str[1..]),
+        b'+' => (false, &str[1..]),
+        _ => (false, str),
+    };
+    let mut digit_chars = digits.chars();
+    if digit_chars.next() == Some('0') && digit_chars.next().is_some_and(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+
+    if digits.chars().all(|ch| ch.is_ascii_digit()) {
+        // integers beyond i64 are BigInt, without precision lost
+        let magnitude = BigInt::from_str_radix(digits, 10)?;
+        let value = if is_negative { -&magnitude } else { magnitude };
+        return Some(Number::from_big(value));
+    }
+    str.parse::<f64>().ok().map(Number::Float)
+}
@@ -0,0 +1,472 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// arbitrary-precision integer,
+// stored as sign and magnitude in little-endian base 2^32.
+// the magnitude has no trailing zero digit,
+// and ZERO is always non-negative with empty magnitude.
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+const DIGIT_BITS: u32 = 32;
+const DIGIT_BASE: u64 = 1 << DIGIT_BITS;
+
+impl BigInt {
+    pub fn one() -> Self {
+        Self::from(1)
+    }
+
+    fn new(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        let negative = negative && !magnitude.is_empty();
+        Self {
+            negative,
+            magnitude,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+    pub fn abs(&self) -> Self {
+        Self::new(false, self.magnitude.clone())
+    }
+
+    // count of significant bits of the magnitude
+    pub fn bits(&self) -> u64 {
+        match self.magnitude.last() {
+            Some(top) => {
+                let top_bits = (DIGIT_BITS - top.leading_zeros()) as u64;
+                (self.magnitude.len() as u64 - 1) * DIGIT_BITS as u64 + top_bits
+            }
+            None => 0,
+        }
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let mut abs: u64 = 0;
+        for (index, digit) in self.magnitude.iter().enumerate() {
+            abs |= (*digit as u64) << (index as u32 * DIGIT_BITS);
+        }
+
+        if self.negative {
+            if abs <= i64::MAX as u64 + 1 {
+                Some((abs as i64).wrapping_neg())
+            } else {
+                None
+            }
+        } else {
+            i64::try_from(abs).ok()
+        }
+    }
+    pub fn to_f64(&self) -> f64 {
+        let mut result = 0.0;
+        for digit in self.magnitude.iter().rev() {
+            result = result * DIGIT_BASE as f64 + *digit as f64;
+        }
+        if self.negative {
+            -result
+        } else {
+            result
+        }
+    }
+    // truncate the float toward ZERO
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        let truncated = value.trunc();
+        if truncated.abs() < i64::MAX as f64 {
+            return Some(Self::from(truncated as i64));
+        }
+
+        // value = mantissa * 2^exponent, with 53 bits mantissa
+        let bits = truncated.abs().to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as u32 - 1075;
+        let mantissa = (bits & ((1 << 52) - 1)) | (1 << 52);
+        let result = &Self::from(mantissa as i64) * &Self::from(2).pow(exponent);
+        if value < 0.0 {
+            Some(-&result)
+        } else {
+            Some(result)
+        }
+    }
+
+    // parse digits (without sign and prefix) in specified radix
+    pub fn from_str_radix(digits: &str, radix: u32) -> Option<Self> {
+        if digits.is_empty() {
+            return None;
+        }
+        let mut magnitude = Vec::<u32>::new();
+        for ch in digits.chars() {
+            let digit = ch.to_digit(radix)?;
+            // magnitude = magnitude * radix + digit
+            let mut carry = digit as u64;
+            for item in magnitude.iter_mut() {
+                let current = (*item as u64) * (radix as u64) + carry;
+                *item = current as u32;
+                carry = current >> DIGIT_BITS;
+            }
+            if carry > 0 {
+                magnitude.push(carry as u32);
+            }
+        }
+        Some(Self::new(false, magnitude))
+    }
+
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut result = Self::one();
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    // truncated division, same as `/` and `%` for i64
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        let (quotient, remainder) = divmod_magnitude(&self.magnitude, &other.magnitude);
+        (
+            Self::new(self.negative != other.negative, quotient),
+            Self::new(self.negative, remainder),
+        )
+    }
+    // division rounded toward negative infinity
+    pub fn div_floor(&self, other: &Self) -> Self {
+        let (quotient, remainder) = self.div_rem(other);
+        if !remainder.is_zero() && (self.negative != other.negative) {
+            &quotient - &Self::one()
+        } else {
+            quotient
+        }
+    }
+
+    // greatest common divisor, always non-negative
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut larger = self.abs();
+        let mut smaller = other.abs();
+        while !smaller.is_zero() {
+            let (_, remainder) = larger.div_rem(&smaller);
+            larger = smaller;
+            smaller = remainder;
+        }
+        larger
+    }
+
+    // bitwise operators on the infinite two's complement form, like i64
+    pub fn bitwise(&self, other: &Self, operator: fn(u32, u32) -> u32) -> Self {
+        // one more digit for the sign bit
+        let len = self.magnitude.len().max(other.magnitude.len()) + 1;
+        let digits1 = self.twos_complement(len);
+        let digits2 = other.twos_complement(len);
+        let mut digits: Vec<u32> = digits1
+            .iter()
+            .zip(&digits2)
+            .map(|(digit1, digit2)| operator(*digit1, *digit2))
+            .collect();
+
+        let negative = digits[len - 1] >> (DIGIT_BITS - 1) == 1;
+        if negative {
+            negate_digits(&mut digits);
+        }
+        Self::new(negative, digits)
+    }
+    // `!x` is `-x - 1` in two's complement
+    pub fn bit_not(&self) -> Self {
+        &(-self) - &Self::one()
+    }
+    fn twos_complement(&self, len: usize) -> Vec<u32> {
+        let mut digits = self.magnitude.clone();
+        digits.resize(len, 0);
+        if self.negative {
+            negate_digits(&mut digits);
+        }
+        digits
+    }
+}
+
+// --- --- --- --- --- ---
+
+// invert all bits and add one
+fn negate_digits(digits: &mut [u32]) {
+    let mut carry = 1;
+    for digit in digits.iter_mut() {
+        let sum = (!*digit) as u64 + carry;
+        *digit = sum as u32;
+        carry = sum >> DIGIT_BITS;
+    }
+}
+
+fn cmp_magnitude(mag1: &[u32], mag2: &[u32]) -> Ordering {
+    if mag1.len() != mag2.len() {
+        return mag1.len().cmp(&mag2.len());
+    }
+    for (digit1, digit2) in mag1.iter().rev().zip(mag2.iter().rev()) {
+        if digit1 != digit2 {
+            return digit1.cmp(digit2);
+        }
+    }
+    Ordering::Equal
+}
+
+fn add_magnitude(mag1: &[u32], mag2: &[u32]) -> Vec<u32> {
+    let (longer, shorter) = if mag1.len() >= mag2.len() {
+        (mag1, mag2)
+    } else {
+        (mag2, mag1)
+    };
+    let mut result = Vec::with_capacity(longer.len() + 1);
+    let mut carry = 0_u64;
+    for (index, digit) in longer.iter().enumerate() {
+        let other = *shorter.get(index).unwrap_or(&0) as u64;
+        let current = *digit as u64 + other + carry;
+        result.push(current as u32);
+        carry = current >> DIGIT_BITS;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+// expected `mag1` >= `mag2`
+fn sub_magnitude(mag1: &[u32], mag2: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(mag1.len());
+    let mut borrow = 0_i64;
+    for (index, digit) in mag1.iter().enumerate() {
+        let other = *mag2.get(index).unwrap_or(&0) as i64;
+        let mut current = *digit as i64 - other - borrow;
+        if current < 0 {
+            current += DIGIT_BASE as i64;
+            borrow = 1;
+        } else {
+            borrow = 0;
+        }
+        result.push(current as u32);
+    }
+    result
+}
+
+fn mul_magnitude(mag1: &[u32], mag2: &[u32]) -> Vec<u32> {
+    if mag1.is_empty() || mag2.is_empty() {
+        return Vec::new();
+    }
+    let mut result = vec![0_u32; mag1.len() + mag2.len()];
+    for (i, digit1) in mag1.iter().enumerate() {
+        let mut carry = 0_u64;
+        for (j, digit2) in mag2.iter().enumerate() {
+            let current = (*digit1 as u64) * (*digit2 as u64) + result[i + j] as u64 + carry;
+            result[i + j] = current as u32;
+            carry = current >> DIGIT_BITS;
+        }
+        result[i + mag2.len()] = carry as u32;
+    }
+    result
+}
+
+// long division, Knuth's Algorithm D
+fn divmod_magnitude(dividend: &[u32], divisor: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if divisor.is_empty() {
+        panic!("BigInt division by ZERO");
+    }
+    if cmp_magnitude(dividend, divisor) == Ordering::Less {
+        return (Vec::new(), dividend.to_vec());
+    }
+
+    if divisor.len() == 1 {
+        // short division
+        let divisor = divisor[0] as u64;
+        let mut quotient = vec![0_u32; dividend.len()];
+        let mut remainder = 0_u64;
+        for index in (0..dividend.len()).rev() {
+            let current = (remainder << DIGIT_BITS) | dividend[index] as u64;
+            quotient[index] = (current / divisor) as u32;
+            remainder = current % divisor;
+        }
+        return (quotient, vec![remainder as u32]);
+    }
+
+    let n = divisor.len();
+    let m = dividend.len() - n;
+
+    // normalize to make the top digit of divisor no less than `DIGIT_BASE / 2`
+    let shift = divisor[n - 1].leading_zeros();
+    let shift_left = |digits: &[u32], extra: bool| -> Vec<u32> {
+        let mut result = Vec::with_capacity(digits.len() + 1);
+        let mut carry = 0_u32;
+        for digit in digits {
+            let current = ((*digit as u64) << shift) | carry as u64;
+            result.push(current as u32);
+            carry = (current >> DIGIT_BITS) as u32;
+        }
+        if extra {
+            result.push(carry);
+        }
+        result
+    };
+    let vn = shift_left(divisor, false);
+    let mut un = shift_left(dividend, true);
+
+    let mut quotient = vec![0_u32; m + 1];
+    for j in (0..=m).rev() {
+        // estimate the quotient digit
+        let numerator = ((un[j + n] as u64) << DIGIT_BITS) | un[j + n - 1] as u64;
+        let mut qhat = numerator / vn[n - 1] as u64;
+        let mut rhat = numerator % vn[n - 1] as u64;
+        while qhat >= DIGIT_BASE
+            || qhat * vn[n - 2] as u64 > ((rhat << DIGIT_BITS) | un[j + n - 2] as u64)
+        {
+            qhat -= 1;
+            rhat += vn[n - 1] as u64;
+            if rhat >= DIGIT_BASE {
+                break;
+            }
+        }
+
+        // multiply and subtract
+        let mut borrow = 0_i64;
+        for i in 0..n {
+            let product = qhat * vn[i] as u64;
+            let current = un[i + j] as i64 - borrow - (product & 0xffff_ffff) as i64;
+            un[i + j] = current as u32;
+            borrow = (product >> DIGIT_BITS) as i64 - (current >> DIGIT_BITS);
+        }
+        let current = un[j + n] as i64 - borrow;
+        un[j + n] = current as u32;
+
+        if current < 0 {
+            // the estimation is larger by one, add back
+            qhat -= 1;
+            let mut carry = 0_u64;
+            for i in 0..n {
+                let sum = un[i + j] as u64 + vn[i] as u64 + carry;
+                un[i + j] = sum as u32;
+                carry = sum >> DIGIT_BITS;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u32);
+        }
+        quotient[j] = qhat as u32;
+    }
+
+    // unnormalize the remainder
+    let mut remainder = vec![0_u32; n];
+    for i in 0..n {
+        let high = (un[i + 1] as u64) << DIGIT_BITS;
+        remainder[i] = ((high | un[i] as u64) >> shift) as u32;
+    }
+    (quotient, remainder)
+}
+
+// --- --- --- --- --- ---
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        let abs = value.unsigned_abs();
+        Self::new(value < 0, vec![abs as u32, (abs >> DIGIT_BITS) as u32])
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // split into decimal chunks by dividing 10^9 repeatedly
+        const CHUNK: u32 = 1_000_000_000;
+        let mut chunks = Vec::<u32>::new();
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            let (quotient, remainder) = divmod_magnitude(&magnitude, &[CHUNK]);
+            chunks.push(*remainder.first().unwrap_or(&0));
+            magnitude = Self::new(false, quotient).magnitude;
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+    fn add(self, other: Self) -> BigInt {
+        if self.negative == other.negative {
+            let magnitude = add_magnitude(&self.magnitude, &other.magnitude);
+            return BigInt::new(self.negative, magnitude);
+        }
+        // different sign, subtract the smaller magnitude from the larger
+        match cmp_magnitude(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::new(
+                other.negative,
+                sub_magnitude(&other.magnitude, &self.magnitude),
+            ),
+            _ => BigInt::new(
+                self.negative,
+                sub_magnitude(&self.magnitude, &other.magnitude),
+            ),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+    fn sub(self, other: Self) -> BigInt {
+        self + &(-other)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+    fn mul(self, other: Self) -> BigInt {
+        let magnitude = mul_magnitude(&self.magnitude, &other.magnitude);
+        BigInt::new(self.negative != other.negative, magnitude)
+    }
+}
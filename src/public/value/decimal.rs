@@ -0,0 +1,211 @@
+use std::fmt;
+
+use super::big_int::BigInt;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum RoundingMode {
+    HalfEven,
+    HalfUp,
+    HalfDown,
+    Up,
+    Down,
+    Ceiling,
+    Floor,
+}
+
+impl RoundingMode {
+    pub fn from_str(mode: &str) -> Option<Self> {
+        match mode {
+            "half_even" => Some(Self::HalfEven),
+            "half_up" => Some(Self::HalfUp),
+            "half_down" => Some(Self::HalfDown),
+            "up" => Some(Self::Up),
+            "down" => Some(Self::Down),
+            "ceiling" => Some(Self::Ceiling),
+            "floor" => Some(Self::Floor),
+            _ => None,
+        }
+    }
+}
+
+pub struct DecimalContext {
+    // max count of digits after the decimal point
+    pub precision: u32,
+    pub rounding: RoundingMode,
+}
+
+pub const MAX_DECIMAL_PRECISION: u32 = 1000;
+
+pub static mut DECIMAL_CONTEXT: DecimalContext = DecimalContext {
+    precision: 20,
+    rounding: RoundingMode::HalfEven,
+};
+
+// --- --- --- --- --- ---
+
+// base-10 number, value = mantissa * 10^(-scale)
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone)]
+pub struct Decimal {
+    pub mantissa: BigInt,
+    pub scale: u32,
+}
+
+fn ten_pow(exponent: u32) -> BigInt {
+    BigInt::from(10).pow(exponent)
+}
+
+impl Decimal {
+    pub fn from_integer(value: BigInt) -> Self {
+        Self {
+            mantissa: value,
+            scale: 0,
+        }
+    }
+
+    // parse decimal string exactly, like: `-12.50` | `1_000.5` | `1.5e-3`
+    pub fn parse(source: &str) -> Option<Self> {
+        let source = source.trim().replace('_', "");
+        let (number_part, exponent) = match source.split_once(['e', 'E']) {
+            Some((number_part, exponent_part)) => {
+                (number_part.to_string(), exponent_part.parse::<i64>().ok()?)
+            }
+            None => (source, 0),
+        };
+
+        let (is_negative, unsigned_part) = match number_part.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, number_part.strip_prefix('+').unwrap_or(&number_part)),
+        };
+        let (int_part, fraction_part) =
+            unsigned_part.split_once('.').unwrap_or((unsigned_part, ""));
+        if int_part.is_empty() && fraction_part.is_empty() {
+            return None;
+        }
+
+        let digits = format!("{}{}", int_part, fraction_part);
+        let mut mantissa = BigInt::from_str_radix(&digits, 10)?;
+        if is_negative {
+            mantissa = -&mantissa;
+        }
+
+        // avoid building a huge number from a literal like `1e999999999`
+        const MAX_EXPONENT: i64 = 100_000;
+        if exponent.abs() > MAX_EXPONENT {
+            return None;
+        }
+        let scale = fraction_part.len() as i64 - exponent;
+        if scale < 0 {
+            let factor = ten_pow(u32::try_from(-scale).ok()?);
+            return Some(Self::from_integer(&mantissa * &factor));
+        }
+        Some(Self {
+            mantissa,
+            scale: u32::try_from(scale).ok()?,
+        })
+    }
+
+    // convert `upper / lower` to Decimal with at least `min_scale` digits,
+    // the result is rounded with the decimal context if it is not exact.
+    pub fn from_fraction(upper: &BigInt, lower: &BigInt, min_scale: u32) -> Self {
+        let (precision, rounding) =
+            unsafe { (DECIMAL_CONTEXT.precision, DECIMAL_CONTEXT.rounding) };
+
+        let (upper, lower) = if lower.is_negative() {
+            (-upper, -lower)
+        } else {
+            (upper.clone(), lower.clone())
+        };
+        // reduce the fraction to get the exact scale
+        let divisor = upper.gcd(&lower);
+        let (upper, lower) = if divisor.is_zero() {
+            (upper, lower)
+        } else {
+            (upper.div_rem(&divisor).0, lower.div_rem(&divisor).0)
+        };
+
+        // the fraction is terminating in base 10
+        // if the lower has only factor 2 and 5.
+        let mut rest = lower.clone();
+        let mut factor_counts = [0_u32; 2];
+        for (index, factor) in [2, 5].into_iter().enumerate() {
+            let factor = BigInt::from(factor);
+            loop {
+                let (quotient, remainder) = rest.div_rem(&factor);
+                if !remainder.is_zero() || factor_counts[index] > precision {
+                    break;
+                }
+                rest = quotient;
+                factor_counts[index] += 1;
+            }
+        }
+        let scale = if rest == BigInt::one() {
+            factor_counts[0].max(factor_counts[1]).max(min_scale)
+        } else {
+            precision
+        };
+        let scale = scale.min(precision);
+
+        let scaled_upper = &upper * &ten_pow(scale);
+        let (quotient, remainder) = scaled_upper.div_rem(&lower);
+        if remainder.is_zero() {
+            return Self {
+                mantissa: quotient,
+                scale,
+            };
+        }
+
+        // round the quotient away from ZERO if needed
+        let is_negative = scaled_upper.is_negative();
+        let half_cmp = (&remainder.abs() * &BigInt::from(2)).cmp(&lower);
+        let is_odd = !quotient.div_rem(&BigInt::from(2)).1.is_zero();
+        let is_away = match rounding {
+            RoundingMode::Up => true,
+            RoundingMode::Down => false,
+            RoundingMode::Ceiling => !is_negative,
+            RoundingMode::Floor => is_negative,
+            RoundingMode::HalfUp => half_cmp.is_ge(),
+            RoundingMode::HalfDown => half_cmp.is_gt(),
+            RoundingMode::HalfEven => half_cmp.is_gt() || (half_cmp.is_eq() && is_odd),
+        };
+        let mantissa = match (is_away, is_negative) {
+            (false, _) => quotient,
+            (true, false) => &quotient + &BigInt::one(),
+            (true, true) => &quotient - &BigInt::one(),
+        };
+        Self { mantissa, scale }
+    }
+
+    // (upper, lower) pair, not reduced
+    pub fn fraction_pair(&self) -> (BigInt, BigInt) {
+        (self.mantissa.clone(), ten_pow(self.scale))
+    }
+    pub fn to_f64(&self) -> f64 {
+        // the standard float parsing is correctly rounded
+        self.to_string().parse::<f64>().unwrap()
+    }
+    pub fn abs(&self) -> Self {
+        Self {
+            mantissa: self.mantissa.abs(),
+            scale: self.scale,
+        }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.abs().to_string();
+        let scale = self.scale as usize;
+        if self.mantissa.is_negative() {
+            write!(f, "-")?;
+        }
+        if scale == 0 {
+            return write!(f, "{}", digits);
+        }
+
+        // pad ZEROs for the value less than 1
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (int_part, fraction_part) = digits.split_at(digits.len() - scale);
+        write!(f, "{}.{}", int_part, fraction_part)
+    }
+}
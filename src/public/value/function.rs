@@ -10,9 +10,12 @@ use crate::public::run_time::build_in::BuildInFnIdenti;
 use crate::public::run_time::scope::{LocalScope, Scope};
 use crate::public::Param;
 
+use super::array::ArrayLiteral;
 use super::value::{Value, ValueType};
 use super::{into_rc_refcell, GetAddr};
 
+// optional param is marked with `?` suffix, like: `"comparator?"`,
+// variadic param is marked with `...` prefix, like: `"...args"`.
 #[derive(PartialEq, Clone)]
 pub struct BuildInFnParam(pub ValueType, pub &'static str);
 
@@ -21,7 +24,13 @@ impl Param for BuildInFnParam {
         self.0
     }
     fn identi(&self) -> &str {
-        self.1
+        self.1.trim_start_matches("...").trim_end_matches('?')
+    }
+    fn is_optional(&self) -> bool {
+        self.1.ends_with('?')
+    }
+    fn is_variadic(&self) -> bool {
+        self.1.starts_with("...")
     }
 }
 
@@ -67,11 +76,12 @@ impl Function {
         local_scope: &mut LocalScope,
         expr_resolver: fn(&ExpressionNode, &mut Scope) -> Result<Value, ()>,
     ) -> Result<(), ()> {
-        if actual_params.len() < formal_params.len() {
+        let required_count = Self::required_count(formal_params);
+        if actual_params.len() < required_count {
             // if param missing
             return Err(range_error(
                 "function invocation",
-                formal_params.len(),
+                required_count,
                 actual_params.len(),
             )?);
         }
@@ -79,6 +89,22 @@ impl Function {
         let mut index = 0;
         while index < formal_params.len() {
             let formal_param = &formal_params[index];
+            if formal_param.is_variadic() {
+                let mut rest_values = Vec::<Value>::new();
+                for actual_param_node in &actual_params[index.min(actual_params.len())..] {
+                    rest_values.push(expr_resolver(actual_param_node, whole_scope)?);
+                }
+                Self::variadic_bind(formal_param, rest_values, local_scope)?;
+                break;
+            }
+            if index >= actual_params.len() {
+                // missing optional param
+                local_scope
+                    .variables
+                    .insert(formal_param.identi().to_string(), Value::EMPTY);
+                index += 1;
+                continue;
+            }
 
             // compute actual_param_value
             let actual_param_node = (&actual_params[index]).clone();
@@ -101,6 +127,84 @@ impl Function {
         }
         Ok(())
     }
+
+    // count of params before the first optional or variadic one
+    fn required_count(formal_params: &[impl Param]) -> usize {
+        formal_params
+            .iter()
+            .take_while(|param| !param.is_optional() && !param.is_variadic())
+            .count()
+    }
+
+    // bind the rest values as an Array
+    fn variadic_bind(
+        formal_param: &impl Param,
+        rest_values: Vec<Value>,
+        local_scope: &mut LocalScope,
+    ) -> Result<(), ()> {
+        let mut rest_arr = ArrayLiteral::new();
+        for value in rest_values {
+            if !value.check_type(formal_param.type__()) {
+                return Err(type_error(
+                    Some(formal_param.identi()),
+                    vec![formal_param.type__()],
+                    value.get_type(),
+                )?);
+            }
+            rest_arr.push_back(value);
+        }
+        local_scope
+            .variables
+            .insert(formal_param.identi().to_string(), Value::from(rest_arr));
+        Ok(())
+    }
+
+    // bind computed values as actual params,
+    // used when build-in functions call back functions.
+    pub fn param_bind(
+        formal_params: &[impl Param],
+        actual_values: Vec<Value>,
+        local_scope: &mut LocalScope,
+    ) -> Result<(), ()> {
+        let required_count = Self::required_count(formal_params);
+        if actual_values.len() < required_count {
+            return Err(range_error(
+                "function invocation",
+                required_count,
+                actual_values.len(),
+            )?);
+        }
+
+        let mut actual_values = actual_values;
+        if let Some(variadic_index) = formal_params.iter().position(|param| param.is_variadic()) {
+            let split_index = variadic_index.min(actual_values.len());
+            let rest_values = actual_values.split_off(split_index);
+            Self::variadic_bind(&formal_params[variadic_index], rest_values, local_scope)?;
+        }
+
+        for formal_param in formal_params
+            .iter()
+            .skip(actual_values.len())
+            .filter(|param| !param.is_variadic())
+        {
+            local_scope
+                .variables
+                .insert(formal_param.identi().to_string(), Value::EMPTY);
+        }
+        for (formal_param, actual_value) in formal_params.iter().zip(actual_values) {
+            if !actual_value.check_type(formal_param.type__()) {
+                return Err(type_error(
+                    Some(formal_param.identi()),
+                    vec![formal_param.type__()],
+                    actual_value.get_type(),
+                )?);
+            }
+            local_scope
+                .variables
+                .insert(formal_param.identi().to_string(), actual_value);
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for Function {
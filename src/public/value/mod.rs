@@ -1,13 +1,18 @@
 use std::{cell::RefCell, fmt, rc::Rc};
 
-use self::{array::RawArray, map::RawMap, oop::object::Object, value::Value};
+use self::{array::RawArray, map::RawMap, oop::object::Object, set::RawSet, value::Value};
 
 pub mod value;
 
 pub mod array;
+pub mod big_int;
+pub mod decimal;
 pub mod function;
 pub mod map;
 pub mod number;
+pub mod quantity;
+pub mod set;
+pub mod symbolic;
 pub mod symbols;
 pub mod unique;
 
@@ -33,6 +38,7 @@ pub trait ComplexStructure {
             Value::String(_) => write!(f, "{}", value.str_format().unwrap()),
             Value::Array(arr) => RawArray::display(f, arr, level),
             Value::Map(map) => RawMap::display(f, map, level),
+            Value::Set(set) => RawSet::display(f, set, level),
             Value::Object(obj) => Object::display(f, obj, level),
             _ => write!(f, "{}", value),
         }
@@ -42,6 +48,7 @@ pub trait ComplexStructure {
             Value::String(str) => str.borrow().clone().into(),
             Value::Array(arr) => RawArray::deep_clone(arr),
             Value::Map(map) => RawMap::deep_clone(map),
+            Value::Set(set) => RawSet::deep_clone(set),
             Value::Object(obj) => Object::deep_clone(obj),
             _ => value.deep_clone(),
         }
@@ -49,7 +56,7 @@ pub trait ComplexStructure {
 }
 
 #[inline]
-fn display_indent(level: usize) -> String {
+pub fn display_indent(level: usize) -> String {
     "  ".repeat(level)
 }
 
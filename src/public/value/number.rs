@@ -1,145 +1,471 @@
 use std::cmp::{self, PartialEq};
 use std::fmt;
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Not, Shl, Shr, Sub};
 
-use crate::public::error::{internal_error, InternalComponent, math_error};
+use crate::public::error::math_error;
+
+use super::big_int::BigInt;
+use super::decimal::Decimal;
 
 #[cfg_attr(debug_assertions, derive(Debug))]
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub enum Number {
     NotANumber,
 
     Int(i64),
+    // only for the integer out of i64 range
+    BigInt(BigInt),
     Float(f64),
-    Fraction(i64, i64),
+    // (upper, lower), the lower is always positive
+    Fraction(BigInt, BigInt),
+    // (real, imaginary), the imaginary part is never ZERO
+    Complex(f64, f64),
+    Decimal(Decimal),
 }
 
+pub type FractionPair = (BigInt, BigInt);
+pub type ComplexPair = (f64, f64);
+
+// limit of the bits count for BigInt results of power and shifting
+const MAX_BITS: u64 = 1 << 22;
+
 impl Number {
-    pub fn modulo(dividend: Self, divisor: Self) -> Self{
-        if let (Self::NotANumber, _) | (_, Self::NotANumber) = (dividend, divisor) {
-            return Self::NotANumber;
+    // Int is used if the `big` is in the i64 range
+    pub fn from_big(big: BigInt) -> Self {
+        match big.to_i64() {
+            Some(i) => Self::Int(i),
+            None => Self::BigInt(big),
         }
+    }
+    // create reduced fraction, the lower should not be ZERO
+    pub fn fraction(upper: BigInt, lower: BigInt) -> Self {
+        let gcd_result = upper.gcd(&lower);
+        let (mut upper, _) = upper.div_rem(&gcd_result);
+        let (mut lower, _) = lower.div_rem(&gcd_result);
+        if lower.is_negative() {
+            upper = -&upper;
+            lower = -&lower;
+        }
+        Self::Fraction(upper, lower)
+    }
 
-        // the divisor can not be ZERO
-        if divisor.float_value() == 0.0 {
-            math_error("modulo by zero").unwrap_err();
-            return Self::NotANumber;
+    // Int is used if the lower of Fraction is `1`
+    pub fn simplify(self) -> Self {
+        match self {
+            Self::Fraction(upper, lower) if lower == BigInt::one() => Self::from_big(upper),
+            _ => self,
+        }
+    }
+    // division without truncating for integers, like: `1 / 3` -> `(1 / 3)`
+    pub fn exact_div(dividend: Self, divisor: Self) -> Self {
+        let result = if dividend.is_integer() && divisor.is_integer() && !divisor.is_zero() {
+            Self::fraction(dividend.big_value(), divisor.big_value())
+        } else {
+            dividend / divisor
+        };
+        result.simplify()
+    }
+
+    // Float is used if the imaginary part is ZERO
+    pub fn complex(real: f64, imaginary: f64) -> Self {
+        if imaginary == 0.0 {
+            Self::Float(real)
+        } else {
+            Self::Complex(real, imaginary)
         }
+    }
 
-        if let (Self::Float(_), _) | (_, Self::Float(_)) = (dividend, divisor) {
-            let f1 = dividend.float_value();
-            let f2 = divisor.float_value();
-            return Number::Float(f1 % f2);
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Self::Int(_) | Self::BigInt(_))
+    }
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Self::Int(i) => *i == 0,
+            Self::BigInt(big) => big.is_zero(),
+            Self::Float(f) => *f == 0.0,
+            Self::Fraction(upper, _) => upper.is_zero(),
+            Self::Decimal(decimal) => decimal.mantissa.is_zero(),
+            Self::Complex(_, _) => false,
+            Self::NotANumber => false,
         }
+    }
 
-        match (dividend, divisor) {
-            (Self::Int(i1), Self::Int(i2)) => Number::Int(i1 % i2),
-            (Self::Int(i), Self::Fraction(upper, lower)) | (Self::Fraction(upper, lower), Self::Int(i)) => {
-                let temp1 = i * lower;
-                let temp2 = upper * lower;
-                Number::Fraction(temp1 % temp2, lower)
+    // BigInt value for Int and BigInt
+    pub fn big_value(&self) -> BigInt {
+        match self {
+            Self::Int(i) => BigInt::from(*i),
+            Self::BigInt(big) => big.clone(),
+            _ => unreachable!(),
+        }
+    }
+    // (upper, lower) pair for Int, BigInt and Fraction
+    fn fraction_pair(&self) -> FractionPair {
+        match self {
+            Self::Fraction(upper, lower) => (upper.clone(), lower.clone()),
+            Self::Decimal(decimal) => decimal.fraction_pair(),
+            _ => (self.big_value(), BigInt::one()),
+        }
+    }
+
+    // exact reduced (upper, lower) pair for real numbers,
+    // `None` for Complex, NotANumber and infinite Floats.
+    pub fn exact_fraction(&self) -> Option<FractionPair> {
+        let reduced = match self {
+            Self::NotANumber | Self::Complex(_, _) => return None,
+            Self::Float(f) if !f.is_finite() => return None,
+            Self::Float(f) => {
+                // a finite float is always a dyadic fraction
+                let mut upper = *f;
+                let mut shift = 0;
+                while upper.fract() != 0.0 {
+                    upper *= 2.0;
+                    shift += 1;
+                }
+                let upper = BigInt::from_f64(upper)?;
+                Self::fraction(upper, BigInt::from(2).pow(shift))
             }
-            (Self::Fraction(upper1, lower1), Self::Fraction(upper2, lower2)) => {
-                let lower_lcm = Self::lcm(lower1, lower2);
-                let multed_upper1 = upper1 * (lower_lcm / lower1);
-                let multed_upper2 = upper2 * (lower_lcm / lower2);
-                Number::Fraction(multed_upper1 % multed_upper2, lower_lcm).reduce().unwrap() 
+            _ => {
+                let (upper, lower) = self.fraction_pair();
+                Self::fraction(upper, lower)
             }
-            _ => unreachable!()
+        };
+        let Self::Fraction(upper, lower) = reduced else {
+            unreachable!()
+        };
+        Some((upper, lower))
+    }
+
+    // count of digits after the decimal point, ZERO for integers
+    fn decimal_scale(&self) -> u32 {
+        match self {
+            Self::Decimal(decimal) => decimal.scale,
+            _ => 0,
         }
     }
 
-    pub fn pow(base: Self, target: Self) -> Self {
-        match base {
-            Self::Int(num1) => match target {
-                Self::Int(num2) => {
-                    if num2 >= 0 {
-                        Self::Int(num1.pow(num2 as u32))
-                    } else {
-                        Self::Float((num1 as f64).powi(num2 as i32))
-                    }
-                }
-                Self::Float(num2) => Self::Float((num1 as f64).powf(num2)),
-                Self::Fraction(_, _) => Self::Float((num1 as f64).powf(target.float_value())),
-                _ => Self::NotANumber,
-            },
-            Self::Float(num1) => match target {
-                Self::Int(num2) => Self::Float(num1.powi(num2 as i32)),
-                Self::Float(num2) => Self::Float(num1.powf(num2)),
-                Self::Fraction(_, _) => Self::Float(num1.powf(target.float_value())),
-                _ => Self::NotANumber,
+    // (real, imaginary) pair for all number types
+    pub fn complex_pair(&self) -> ComplexPair {
+        match self {
+            Self::Complex(real, imaginary) => (*real, *imaginary),
+            _ => (self.float_value(), 0.0),
+        }
+    }
+
+    // operate two numbers with the operator for each number type,
+    // Int is promoted to BigInt when overflow.
+    // Decimal mixed with Int gets Decimal,
+    // with Fraction gets Fraction, with Float gets Float.
+    fn operate_with(
+        &self,
+        other: &Self,
+        int_operator: fn(i64, i64) -> Option<i64>,
+        big_operator: fn(&BigInt, &BigInt) -> BigInt,
+        fraction_operator: fn(FractionPair, FractionPair) -> FractionPair,
+        float_operator: fn(f64, f64) -> f64,
+        complex_operator: Option<fn(ComplexPair, ComplexPair) -> ComplexPair>,
+    ) -> Self {
+        match (self, other) {
+            (Self::NotANumber, _) | (_, Self::NotANumber) => Self::NotANumber,
+            (Self::Complex(_, _), _) | (_, Self::Complex(_, _)) => {
+                let Some(complex_operator) = complex_operator else {
+                    math_error("the operation is not available for Complex number").unwrap_err();
+                    return Self::NotANumber;
+                };
+                let (real, imaginary) = complex_operator(self.complex_pair(), other.complex_pair());
+                Self::complex(real, imaginary)
+            }
+            (Self::Float(_), _) | (_, Self::Float(_)) => {
+                let f1 = self.float_value();
+                let f2 = other.float_value();
+                Self::Float(float_operator(f1, f2))
+            }
+            (Self::Int(i1), Self::Int(i2)) => match int_operator(*i1, *i2) {
+                Some(result) => Self::Int(result),
+                None => Self::from_big(big_operator(&BigInt::from(*i1), &BigInt::from(*i2))),
             },
-            Self::Fraction(upper, lower) => match target {
-                Self::Int(num2) => {
-                    let upper_powed = upper.pow(num2 as u32);
-                    let lower_powed = lower.pow(num2 as u32);
-                    Self::Fraction(upper_powed, lower_powed).reduce().unwrap()
-                }
-                Self::Float(num2) => {
-                    let f_value = base.float_value();
-                    Self::Float(f_value.powf(num2))
-                }
-                Self::Fraction(_, _) => {
-                    let self_f = base.float_value();
-                    let target_f = target.float_value();
-                    Self::Float(self_f.powf(target_f))
-                }
-                _ => Self::NotANumber,
+            _ if self.is_integer() && other.is_integer() => {
+                Self::from_big(big_operator(&self.big_value(), &other.big_value()))
+            }
+            (Self::Decimal(_), Self::Decimal(_) | Self::Int(_) | Self::BigInt(_))
+            | (Self::Int(_) | Self::BigInt(_), Self::Decimal(_)) => {
+                // keep the larger scale of the operands
+                let (upper, lower) = fraction_operator(self.fraction_pair(), other.fraction_pair());
+                let min_scale = self.decimal_scale().max(other.decimal_scale());
+                Self::Decimal(Decimal::from_fraction(&upper, &lower, min_scale))
+            }
+            _ => {
+                let (upper, lower) = fraction_operator(self.fraction_pair(), other.fraction_pair());
+                Self::fraction(upper, lower)
+            }
+        }
+    }
+
+    pub fn modulo(dividend: Self, divisor: Self) -> Self {
+        // the divisor can not be ZERO
+        if divisor.is_zero() {
+            math_error("modulo by zero").unwrap_err();
+            return Self::NotANumber;
+        }
+
+        dividend.operate_with(
+            &divisor,
+            |i1, i2| i1.checked_rem(i2),
+            |b1, b2| b1.div_rem(b2).1,
+            |(upper1, lower1), (upper2, lower2)| {
+                // regard as (upper1 * lower2) % (upper2 * lower1) / (lower1 * lower2)
+                let (_, remainder) = (&upper1 * &lower2).div_rem(&(&upper2 * &lower1));
+                (remainder, &lower1 * &lower2)
             },
-            Self::NotANumber => Self::NotANumber,
+            |f1, f2| f1 % f2,
+            None,
+        )
+    }
+
+    pub fn floor_div(dividend: Self, divisor: Self) -> Self {
+        // the divisor can not be ZERO
+        if divisor.is_zero() {
+            math_error("the divisor should not to be ZERO").unwrap_err();
+            return Self::NotANumber;
+        }
+
+        match (&dividend, &divisor) {
+            (Self::Fraction(_, _) | Self::Decimal(_), _)
+            | (_, Self::Fraction(_, _) | Self::Decimal(_)) => {
+                // regard Int as Fraction with lower `1`
+                let (upper1, lower1) = dividend.fraction_pair();
+                let (upper2, lower2) = divisor.fraction_pair();
+                let upper = &upper1 * &lower2;
+                let lower = &lower1 * &upper2;
+                Self::from_big(upper.div_floor(&lower))
+            }
+            _ => dividend.operate_with(
+                &divisor,
+                |i1, i2| {
+                    // integer division rounded toward negative infinity
+                    let quotient = i1.checked_div(i2)?;
+                    if i1 % i2 != 0 && (i1 < 0) != (i2 < 0) {
+                        Some(quotient - 1)
+                    } else {
+                        Some(quotient)
+                    }
+                },
+                |b1, b2| b1.div_floor(b2),
+                |_, _| unreachable!(),
+                |f1, f2| (f1 / f2).floor(),
+                None,
+            ),
         }
     }
 
-    pub fn not(&self) -> Self {
-        match self {
-            Self::Int(i) => Self::Int(!(*i > 0) as i64),
-            Self::Float(f) => Self::Int(!(*f > 0.0) as i64),
-            Self::Fraction(upper, _) => {
-                if *upper == 0 {
-                    Self::Int(1)
-                } else {
-                    Self::Int(0)
+    pub fn pow(base: Self, target: Self) -> Self {
+        if let (Self::NotANumber, _) | (_, Self::NotANumber) = (&base, &target) {
+            return Self::NotANumber;
+        }
+
+        // negative base with non-integer exponent has complex result
+        let is_target_integer = match target {
+            Self::Float(f) => f.fract() == 0.0,
+            _ => target.is_integer(),
+        };
+        let is_complex = matches!(base, Self::Complex(_, _))
+            || matches!(target, Self::Complex(_, _))
+            || (base.float_value() < 0.0 && !is_target_integer);
+        if is_complex {
+            let (real, imaginary) = complex_pow(base.complex_pair(), &target);
+            return Self::complex(real, imaginary);
+        }
+
+        if let (Self::Float(_), _) | (_, Self::Float(_) | Self::Fraction(_, _) | Self::Decimal(_)) =
+            (&base, &target)
+        {
+            let base_f = base.float_value();
+            let target_f = target.float_value();
+            return Self::Float(base_f.powf(target_f));
+        }
+
+        // the target is integer from here
+        let target_big = target.big_value();
+        if base.is_integer() && target_big.is_negative() {
+            return Self::Float(base.float_value().powf(target.float_value()));
+        }
+
+        let Some(exponent) = target_big
+            .abs()
+            .to_i64()
+            .and_then(|i| u32::try_from(i).ok())
+        else {
+            math_error("the exponent is too large").unwrap_err();
+            return Self::NotANumber;
+        };
+        let (upper, lower) = base.fraction_pair();
+        if upper.bits().max(lower.bits()) * exponent as u64 > MAX_BITS {
+            math_error("the result of power is too large").unwrap_err();
+            return Self::NotANumber;
+        }
+
+        match (&base, target_big.is_negative()) {
+            (Self::Int(i), false) => match i.checked_pow(exponent) {
+                Some(result) => Self::Int(result),
+                None => Self::from_big(upper.pow(exponent)),
+            },
+            (Self::BigInt(_), false) => Self::from_big(upper.pow(exponent)),
+            (Self::Fraction(_, _), false) => {
+                Self::fraction(upper.pow(exponent), lower.pow(exponent))
+            }
+            (Self::Decimal(decimal), false) => {
+                let min_scale = decimal.scale.saturating_mul(exponent);
+                let decimal =
+                    Decimal::from_fraction(&upper.pow(exponent), &lower.pow(exponent), min_scale);
+                Self::Decimal(decimal)
+            }
+            (_, true) => {
+                if upper.is_zero() {
+                    math_error("the divisor should not to be ZERO").unwrap_err();
+                    return Self::NotANumber;
                 }
+                if let Self::Decimal(_) = base {
+                    let decimal =
+                        Decimal::from_fraction(&lower.pow(exponent), &upper.pow(exponent), 0);
+                    return Self::Decimal(decimal);
+                }
+                Self::fraction(lower.pow(exponent), upper.pow(exponent))
             }
-            Self::NotANumber => Self::Int(1),
+            _ => unreachable!(),
         }
     }
 
     pub fn int(&self) -> Self {
         match self {
-            Self::Float(f) => Self::Int(*f as i64),
-            Self::Fraction(_, _) => Self::Int(self.int_value()),
+            Self::Float(f) => match BigInt::from_f64(*f) {
+                Some(big) => Self::from_big(big),
+                None => Self::NotANumber,
+            },
+            Self::Fraction(_, _) | Self::Decimal(_) => {
+                let (upper, lower) = self.fraction_pair();
+                Self::from_big(upper.div_rem(&lower).0)
+            }
+            Self::Complex(_, _) => {
+                math_error("Complex number can not be converted to Int").unwrap_err();
+                Self::NotANumber
+            }
             _ => self.clone(),
         }
     }
     pub fn float(&self) -> Self {
         match self {
-            Self::Int(i) => Self::Float(*i as f64),
-            Self::Fraction(_, _) => Self::Float(self.float_value()),
-            _ => self.clone(),
+            Self::NotANumber => Self::NotANumber,
+            Self::Complex(_, _) => {
+                math_error("Complex number can not be converted to Float").unwrap_err();
+                Self::NotANumber
+            }
+            _ => Self::Float(self.float_value()),
         }
     }
 
-    pub fn int_value(self) -> i64 {
+    // out of range value is saturated to i64 range
+    pub fn int_value(&self) -> i64 {
         match self {
-            Self::Int(i) => i,
-            Self::Float(f) => f as i64,
+            Self::Int(i) => *i,
+            Self::Float(f) | Self::Complex(f, _) => *f as i64,
+            Self::BigInt(_) | Self::Fraction(_, _) | Self::Decimal(_) => {
+                let Self::Int(i) = self.int() else {
+                    return if self.float_value() > 0.0 {
+                        i64::MAX
+                    } else {
+                        i64::MIN
+                    };
+                };
+                i
+            }
+            Self::NotANumber => 0,
+        }
+    }
+    pub fn float_value(&self) -> f64 {
+        match self {
+            Self::Int(i) => *i as f64,
+            Self::BigInt(big) => big.to_f64(),
+            // the real part for Complex
+            Self::Float(f) | Self::Complex(f, _) => *f,
             Self::Fraction(upper, lower) => {
-                if lower > upper {
-                    0
+                // shift both sides to avoid infinity / infinity
+                const FLOAT_SAFE_BITS: u64 = 1000;
+                let larger_bits = upper.bits().max(lower.bits());
+                if larger_bits <= FLOAT_SAFE_BITS {
+                    return upper.to_f64() / lower.to_f64();
+                }
+                let factor = BigInt::from(2).pow((larger_bits - FLOAT_SAFE_BITS) as u32);
+                let upper_f = upper.div_rem(&factor).0.to_f64();
+                let lower_f = lower.div_rem(&factor).0.to_f64();
+                upper_f / lower_f
+            }
+            Self::Decimal(decimal) => decimal.to_f64(),
+            Self::NotANumber => 0_f64,
+        }
+    }
+
+    // --- --- --- --- --- ---
+    // functions with complex branches
+
+    pub fn sqrt(&self) -> Self {
+        match self {
+            Self::Complex(real, imaginary) => {
+                // principal square root
+                let modulus = real.hypot(*imaginary);
+                let root_real = ((modulus + real) / 2.0).sqrt();
+                let root_imaginary = ((modulus - real) / 2.0).sqrt().copysign(*imaginary);
+                Self::complex(root_real, root_imaginary)
+            }
+            _ => {
+                let f = self.float_value();
+                if f < 0.0 {
+                    Self::complex(0.0, (-f).sqrt())
                 } else {
-                    upper / lower
+                    Self::Float(f.sqrt())
                 }
             }
-            Self::NotANumber => 0,
         }
     }
-    pub fn float_value(self) -> f64 {
+    pub fn exp(&self) -> Self {
+        let (real, imaginary) = complex_exp(self.complex_pair());
+        Self::complex(real, imaginary)
+    }
+    pub fn ln(&self) -> Self {
+        let (real, imaginary) = complex_ln(self.complex_pair());
+        Self::complex(real, imaginary)
+    }
+    pub fn abs(&self) -> Self {
         match self {
-            Self::Int(i) => i as f64,
-            Self::Float(f) => f,
-            Self::Fraction(upper, lower) => (upper as f64) / (lower as f64),
-            Self::NotANumber => 0_f64,
+            Self::Int(_) | Self::BigInt(_) => Self::from_big(self.big_value().abs()),
+            Self::Float(f) => Self::Float(f.abs()),
+            Self::Fraction(upper, lower) => Self::Fraction(upper.abs(), lower.clone()),
+            Self::Decimal(decimal) => Self::Decimal(decimal.abs()),
+            Self::Complex(real, imaginary) => Self::Float(real.hypot(*imaginary)),
+            Self::NotANumber => Self::NotANumber,
+        }
+    }
+    // argument (phase angle) in radians
+    pub fn arg(&self) -> Self {
+        let (real, imaginary) = self.complex_pair();
+        Self::Float(imaginary.atan2(real))
+    }
+    // complex conjugate
+    pub fn conj(&self) -> Self {
+        match self {
+            Self::Complex(real, imaginary) => Self::Complex(*real, -imaginary),
+            _ => self.clone(),
+        }
+    }
+    pub fn real(&self) -> Self {
+        match self {
+            Self::Complex(real, _) => Self::Float(*real),
+            _ => self.clone(),
+        }
+    }
+    pub fn imaginary(&self) -> Self {
+        match self {
+            Self::Complex(_, imaginary) => Self::Float(*imaginary),
+            Self::NotANumber => Self::NotANumber,
+            _ => Self::Int(0),
         }
     }
 
@@ -149,39 +475,75 @@ impl Number {
         let diff_abs = diff.abs();
         return diff_abs <= EPS;
     }
+}
 
-    fn reduce(&self) -> Result<Self, ()> {
-        // this method is specially for Number::Fraction
-        let Self::Fraction(mut upper, mut lower) = self else {
-            return Err(internal_error(
-                InternalComponent::InternalFn,
-                "invalid `Number::reduce` invocation"
-            )?);
-        };
-        let gcd_result = Self::gcd(upper, lower);
-        upper /= gcd_result;
-        lower /= gcd_result;
-        return Ok(Self::Fraction(upper, lower));
-    }
-    // greatest common divisor
-    fn gcd(n1: i64, n2: i64) -> i64 {
-        if n1 == n2 {
-            return n1;
-        }
-
-        // avoid negative number
-        let larger = cmp::max(n1, n2).abs();
-        let smaller = cmp::min(n1, n2).abs();
+// --- --- --- --- --- ---
 
-        if smaller == 0 {
-            return larger;
+pub fn complex_mul((r1, i1): ComplexPair, (r2, i2): ComplexPair) -> ComplexPair {
+    (r1 * r2 - i1 * i2, r1 * i2 + i1 * r2)
+}
+pub fn complex_div((r1, i1): ComplexPair, (r2, i2): ComplexPair) -> ComplexPair {
+    let denominator = r2 * r2 + i2 * i2;
+    (
+        (r1 * r2 + i1 * i2) / denominator,
+        (i1 * r2 - r1 * i2) / denominator,
+    )
+}
+fn complex_exp((real, imaginary): ComplexPair) -> ComplexPair {
+    let modulus = real.exp();
+    if imaginary == 0.0 {
+        return (modulus, 0.0);
+    }
+    (modulus * imaginary.cos(), modulus * imaginary.sin())
+}
+fn complex_ln((real, imaginary): ComplexPair) -> ComplexPair {
+    (real.hypot(imaginary).ln(), imaginary.atan2(real))
+}
+fn complex_pow(base: ComplexPair, target: &Number) -> ComplexPair {
+    // small integer exponent is computed by repeated multiplying,
+    // to keep results like `(1i) ^ 2` exact.
+    const MAX_REPEAT_EXPONENT: i64 = 1024;
+    if let Number::Int(exponent) = target {
+        if exponent.abs() <= MAX_REPEAT_EXPONENT {
+            let mut result = (1.0, 0.0);
+            let mut factor = base;
+            let mut rest = exponent.unsigned_abs();
+            while rest > 0 {
+                if rest & 1 == 1 {
+                    result = complex_mul(result, factor);
+                }
+                factor = complex_mul(factor, factor);
+                rest >>= 1;
+            }
+            if *exponent < 0 {
+                result = complex_div((1.0, 0.0), result);
+            }
+            return result;
         }
+    }
 
-        return Self::gcd(smaller, larger % smaller);
+    if base == (0.0, 0.0) {
+        return (0.0, 0.0);
     }
-    // least common multiple
-    fn lcm(n1: i64, n2: i64) -> i64 {
-        return (n1 * n2) / Self::gcd(n1, n2);
+    // base ^ target = e ^ (target * ln(base))
+    let exponent = complex_mul(target.complex_pair(), complex_ln(base));
+    complex_exp(exponent)
+}
+
+// optimize float number output
+// example:
+// 1.0000000000 -> 1
+// 6.02e23 | 1.5e-9 keep scientific notation
+fn float_display(f: &mut fmt::Formatter<'_>, num: f64) -> fmt::Result {
+    let abs = num.abs();
+    if !num.is_finite() {
+        write!(f, "{}", num)
+    } else if abs >= 1e16 || (abs != 0.0 && abs < 1e-6) {
+        write!(f, "{:e}", num)
+    } else if num == num.floor() {
+        write!(f, "{}", num as i64)
+    } else {
+        write!(f, "{:.10}", num)
     }
 }
 
@@ -191,19 +553,28 @@ impl fmt::Display for Number {
             Self::NotANumber => write!(f, "Not a Number"),
 
             Self::Int(num) => write!(f, "{}", num),
-            Self::Float(num) => {
-                // optimize float number output
-                // example:
-                // 1.0000000000 -> 1
-                if *num == num.floor() {
-                    write!(f, "{}", *num as i64)
-                } else {
-                    write!(f, "{:.10}", num)
-                }
-            }
+            Self::BigInt(num) => write!(f, "{}", num),
+            Self::Float(num) => float_display(f, *num),
             Self::Fraction(upper, lower) => {
                 write!(f, "({} / {})", upper, lower)
             }
+            Self::Decimal(decimal) => write!(f, "{}", decimal),
+            Self::Complex(real, imaginary) => {
+                // a + bi | a - bi | bi
+                if *real != 0.0 {
+                    let sign = if imaginary.is_sign_negative() {
+                        '-'
+                    } else {
+                        '+'
+                    };
+                    float_display(f, *real)?;
+                    write!(f, " {} ", sign)?;
+                    float_display(f, imaginary.abs())?;
+                } else {
+                    float_display(f, *imaginary)?;
+                }
+                write!(f, "i")
+            }
         }
     }
 }
@@ -212,158 +583,159 @@ impl fmt::Display for Number {
 impl Add for Number {
     type Output = Self;
     fn add(self, other: Self) -> Self {
-        if self == Self::NotANumber || other == Self::NotANumber {
-            return Self::NotANumber;
-        }
-
-        if let (Self::Float(_), _) | (_, Self::Float(_)) = (self, other) {
-            let f1 = self.float_value();
-            let f2 = other.float_value();
-            return Self::Float(f1 + f2);
-        }
-
-        match self {
-            Self::Int(num1) => match other {
-                Self::Int(num2) => Self::Int(num1 + num2),
-                Self::Fraction(upper, lower) => Self::Fraction(upper + num1 * lower, lower)
-                    .reduce()
-                    .unwrap(),
-                _ => unreachable!(),
+        self.operate_with(
+            &other,
+            |i1, i2| i1.checked_add(i2),
+            |b1, b2| b1 + b2,
+            |(upper1, lower1), (upper2, lower2)| {
+                let upper = &(&upper1 * &lower2) + &(&upper2 * &lower1);
+                (upper, &lower1 * &lower2)
             },
-            Self::Fraction(upper, lower) => match other {
-                Self::Int(num2) => Self::Fraction(upper + num2 * lower, lower)
-                    .reduce()
-                    .unwrap(),
-                Self::Fraction(other_upper, other_lower) => {
-                    let lower_lcm = Self::lcm(lower, other_lower);
-                    let self_factor = lower_lcm / lower;
-                    let other_factor = lower_lcm / other_lower;
-                    let res_upper = upper * self_factor + other_upper * other_factor;
-                    Self::Fraction(res_upper, lower_lcm).reduce().unwrap()
-                }
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
-        }
+            |f1, f2| f1 + f2,
+            Some(|(r1, i1), (r2, i2)| (r1 + r2, i1 + i2)),
+        )
     }
 }
 
 impl Sub for Number {
     type Output = Self;
     fn sub(self, other: Self) -> Self {
-        if self == Self::NotANumber || other == Self::NotANumber {
-            return Self::NotANumber;
-        }
-
-        if let (Self::Float(_), _) | (_, Self::Float(_)) = (self, other) {
-            // convert num1 and num2 to float type
-            let f1 = self.float_value();
-            let f2 = other.float_value();
-            return Self::Float(f1 - f2);
-        }
-
-        match self {
-            Self::Int(num1) => match other {
-                Self::Int(num2) => Self::Int(num1 - num2),
-                Self::Fraction(upper, lower) => Self::Fraction(num1 * lower - upper, lower)
-                    .reduce()
-                    .unwrap(),
-                _ => unreachable!(),
-            },
-            Self::Fraction(upper, lower) => match other {
-                Self::Int(num2) => Self::Fraction(upper - num2 * lower, lower)
-                    .reduce()
-                    .unwrap(),
-                Self::Fraction(other_upper, other_lower) => {
-                    let lower_lcm = Self::lcm(lower, other_lower);
-                    let self_factor = lower_lcm / lower;
-                    let other_factor = lower_lcm / other_lower;
-                    let res_upper = upper * self_factor - other_upper * other_factor;
-                    Self::Fraction(res_upper, lower_lcm).reduce().unwrap()
-                }
-                _ => unreachable!(),
+        self.operate_with(
+            &other,
+            |i1, i2| i1.checked_sub(i2),
+            |b1, b2| b1 - b2,
+            |(upper1, lower1), (upper2, lower2)| {
+                let upper = &(&upper1 * &lower2) - &(&upper2 * &lower1);
+                (upper, &lower1 * &lower2)
             },
-            _ => unreachable!(),
-        }
+            |f1, f2| f1 - f2,
+            Some(|(r1, i1), (r2, i2)| (r1 - r2, i1 - i2)),
+        )
     }
 }
 
 impl Mul for Number {
     type Output = Self;
     fn mul(self, other: Self) -> Self {
-        if self == Self::NotANumber || other == Self::NotANumber {
-            return Self::NotANumber;
-        }
-
-        if let (Self::Float(_), _) | (_, Self::Float(_)) = (self, other) {
-            // convert num1 and num2 to float type
-            let f1 = self.float_value();
-            let f2 = other.float_value();
-            return Self::Float(f1 * f2);
-        }
-
-        match self {
-            Self::Int(num1) => match other {
-                Self::Int(num2) => Self::Int(num1 * num2),
-                Self::Fraction(upper, lower) => {
-                    Self::Fraction(num1 * upper, lower).reduce().unwrap()
-                }
-                _ => unreachable!(),
-            },
-            Self::Fraction(upper, lower) => match other {
-                Self::Int(num2) => Self::Fraction(upper * num2, lower).reduce().unwrap(),
-                Self::Fraction(other_upper, other_lower) => {
-                    let muled_upper = upper * other_upper;
-                    let muled_lower = lower * other_lower;
-                    Self::Fraction(muled_upper, muled_lower).reduce().unwrap()
-                }
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
-        }
+        self.operate_with(
+            &other,
+            |i1, i2| i1.checked_mul(i2),
+            |b1, b2| b1 * b2,
+            |(upper1, lower1), (upper2, lower2)| (&upper1 * &upper2, &lower1 * &lower2),
+            |f1, f2| f1 * f2,
+            Some(complex_mul),
+        )
     }
 }
 
 impl Div for Number {
     type Output = Self;
     fn div(self, other: Self) -> Self {
-        if self == Self::NotANumber || other == Self::NotANumber {
-            return Self::NotANumber;
-        }
-
         // when the divisor is ZERO
-        if other.float_value() == 0.0 {
+        if other.is_zero() {
             math_error("the divisor should not to be ZERO").unwrap_err();
             return Number::NotANumber;
         }
 
-        // when either `self` or `other` is float
-        if let (Self::Float(_), _) | (_, Self::Float(_)) = (self, other) {
-            // convert num1 and num2 to float type
-            let f1 = self.float_value();
-            let f2 = other.float_value();
+        self.operate_with(
+            &other,
+            |i1, i2| i1.checked_div(i2),
+            |b1, b2| b1.div_rem(b2).0,
+            |(upper1, lower1), (upper2, lower2)| (&upper1 * &lower2, &lower1 * &upper2),
+            |f1, f2| f1 / f2,
+            Some(complex_div),
+        )
+    }
+}
+
+// bitwise operators, available only for Int and BigInt
+fn bitwise_operate(
+    num1: Number,
+    num2: Number,
+    int_operator: fn(i64, i64) -> i64,
+    digit_operator: fn(u32, u32) -> u32,
+) -> Result<Number, ()> {
+    match (num1, num2) {
+        (Number::NotANumber, _) | (_, Number::NotANumber) => Ok(Number::NotANumber),
+        (Number::Int(i1), Number::Int(i2)) => Ok(Number::Int(int_operator(i1, i2))),
+        (num1, num2) if num1.is_integer() && num2.is_integer() => {
+            let result = num1.big_value().bitwise(&num2.big_value(), digit_operator);
+            Ok(Number::from_big(result))
+        }
+        _ => Err(math_error("bitwise operation expects Int typed number")?),
+    }
+}
+fn shift_amount(num: &Number) -> Result<u32, ()> {
+    match num {
+        Number::Int(i) if *i >= 0 && (*i as u64) <= MAX_BITS => Ok(*i as u32),
+        Number::Int(_) | Number::BigInt(_) => Err(math_error("shift amount out of range")?),
+        _ => Err(math_error("bitwise operation expects Int typed number")?),
+    }
+}
 
-            return Self::Float(f1 / f2);
+impl BitAnd for Number {
+    type Output = Result<Self, ()>;
+    fn bitand(self, other: Self) -> Self::Output {
+        bitwise_operate(self, other, |i1, i2| i1 & i2, |d1, d2| d1 & d2)
+    }
+}
+impl BitOr for Number {
+    type Output = Result<Self, ()>;
+    fn bitor(self, other: Self) -> Self::Output {
+        bitwise_operate(self, other, |i1, i2| i1 | i2, |d1, d2| d1 | d2)
+    }
+}
+impl BitXor for Number {
+    type Output = Result<Self, ()>;
+    fn bitxor(self, other: Self) -> Self::Output {
+        bitwise_operate(self, other, |i1, i2| i1 ^ i2, |d1, d2| d1 ^ d2)
+    }
+}
+impl Shl for Number {
+    type Output = Result<Self, ()>;
+    fn shl(self, other: Self) -> Self::Output {
+        if let (Self::NotANumber, _) | (_, Self::NotANumber) = (&self, &other) {
+            return Ok(Self::NotANumber);
         }
+        let shift = shift_amount(&other)?;
 
         match self {
-            Self::Int(num1) => match other {
-                Self::Int(num2) => Self::Int(num1 / num2),
-                Self::Fraction(upper, lower) => {
-                    Self::Fraction(num1 * lower, upper).reduce().unwrap()
-                }
-                _ => unreachable!(),
-            },
-            Self::Fraction(upper, lower) => match other {
-                Self::Int(num2) => Self::Fraction(upper, lower * num2).reduce().unwrap(),
-                Self::Fraction(other_upper, other_lower) => {
-                    Self::Fraction(upper * other_lower, lower * other_upper)
-                        .reduce()
-                        .unwrap()
-                }
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
+            // no bit is lost
+            Self::Int(i) if shift < 64 && (i << shift) >> shift == i => Ok(Self::Int(i << shift)),
+            Self::Int(_) | Self::BigInt(_) => {
+                let factor = BigInt::from(2).pow(shift);
+                Ok(Self::from_big(&self.big_value() * &factor))
+            }
+            _ => Err(math_error("bitwise operation expects Int typed number")?),
+        }
+    }
+}
+impl Shr for Number {
+    type Output = Result<Self, ()>;
+    fn shr(self, other: Self) -> Self::Output {
+        if let (Self::NotANumber, _) | (_, Self::NotANumber) = (&self, &other) {
+            return Ok(Self::NotANumber);
+        }
+        let shift = shift_amount(&other)?;
+
+        match self {
+            Self::Int(i) => Ok(Self::Int(i >> shift.min(63))),
+            Self::BigInt(big) => {
+                let factor = BigInt::from(2).pow(shift);
+                Ok(Self::from_big(big.div_floor(&factor)))
+            }
+            _ => Err(math_error("bitwise operation expects Int typed number")?),
+        }
+    }
+}
+impl Not for Number {
+    type Output = Result<Self, ()>;
+    fn not(self) -> Self::Output {
+        match self {
+            Self::NotANumber => Ok(Self::NotANumber),
+            Self::Int(i) => Ok(Self::Int(!i)),
+            Self::BigInt(big) => Ok(Self::from_big(big.bit_not())),
+            _ => Err(math_error("bitwise operation expects Int typed number")?),
         }
     }
 }
@@ -372,40 +744,41 @@ impl Div for Number {
 
 impl PartialOrd for Number {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        if let (Self::NotANumber, _) | (_, Self::NotANumber) = (self, other) {
-            return None;
+        match (self, other) {
+            (Self::NotANumber, _) | (_, Self::NotANumber) => None,
+            // Complex numbers are not ordered
+            (Self::Complex(_, _), _) | (_, Self::Complex(_, _)) => None,
+            (Self::Float(_), _) | (_, Self::Float(_)) => {
+                let self_f = self.float_value();
+                let other_f = other.float_value();
+                self_f.partial_cmp(&other_f)
+            }
+            (Self::Int(i1), Self::Int(i2)) => i1.partial_cmp(i2),
+            _ => {
+                // compare exactly as `upper1 * lower2` and `upper2 * lower1`
+                let (upper1, lower1) = self.fraction_pair();
+                let (upper2, lower2) = other.fraction_pair();
+                (&upper1 * &lower2).partial_cmp(&(&upper2 * &lower1))
+            }
         }
-
-        let self_f = self.float_value();
-        let other_f = other.float_value();
-        return self_f.partial_cmp(&other_f);
     }
 }
 
 impl PartialEq for Number {
     fn eq(&self, other: &Self) -> bool {
-        if let (Self::NotANumber, _) | (_, Self::NotANumber) = (self, other) {
-            return false;
-        }
-
-        if let (Self::Float(_), _) | (_, Self::Float(_)) = (self, other) {
-            let f1 = self.float_value();
-            let f2 = other.float_value();
-            return Self::float_cmp(f1, f2);
-        }
-
-        match self {
-            Self::Int(num1) => match other {
-                Self::Int(num2) => *num1 == *num2,
-                Self::Fraction(_, _) => *num1 as f64 == other.float_value(),
-                _ => unreachable!(),
-            },
-            Self::Fraction(_, _) => match other {
-                Self::Int(num2) => self.int_value() == *num2,
-                Self::Fraction(_, _) => Self::float_cmp(self.float_value(), other.float_value()),
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
+        match (self, other) {
+            (Self::NotANumber, _) | (_, Self::NotANumber) => false,
+            (Self::Complex(_, _), _) | (_, Self::Complex(_, _)) => {
+                let (r1, i1) = self.complex_pair();
+                let (r2, i2) = other.complex_pair();
+                Self::float_cmp(r1, r2) && Self::float_cmp(i1, i2)
+            }
+            (Self::Float(_), _) | (_, Self::Float(_)) => {
+                let f1 = self.float_value();
+                let f2 = other.float_value();
+                Self::float_cmp(f1, f2)
+            }
+            _ => self.partial_cmp(other) == Some(cmp::Ordering::Equal),
         }
     }
     fn ne(&self, other: &Self) -> bool {
@@ -7,6 +7,7 @@ use crate::public::env::ENV_OPTION;
 use crate::public::error::{reference_error, type_error, ReferenceType};
 use crate::public::value::array::ArrayLiteral;
 use crate::public::value::function::Function;
+use crate::public::value::set::RawSet;
 use crate::public::value::value::{Value, ValueType};
 use crate::public::value::{self, display_indent, GetAddr};
 use crate::public::Param;
@@ -19,10 +20,20 @@ use super::object::Object;
 pub struct Class {
     properties: Vec<Property>,
     method_storage: ComposeStorage<Function>,
+    instance_check: Option<InstanceCheck>,
 
     pub completer: Option<Rc<Completer>>,
 }
 
+// checks the instantiated objects of build-in classes, like: the pattern of Regex
+#[derive(Clone, Copy)]
+pub struct InstanceCheck(pub fn(&Object) -> Result<(), ()>);
+impl PartialEq for InstanceCheck {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::fn_addr_eq(self.0, other.0)
+    }
+}
+
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[derive(PartialEq, Clone)]
 pub struct Property(pub ValueType, pub String);
@@ -62,9 +73,14 @@ impl Class {
         return Self {
             properties,
             method_storage,
+            instance_check: None,
             completer,
         };
     }
+    pub fn with_instance_check(mut self, check: fn(&Object) -> Result<(), ()>) -> Self {
+        self.instance_check = Some(InstanceCheck(check));
+        return self;
+    }
 
     pub fn get_method(&self, method_name: &str) -> Result<Function, ()> {
         let result_method = self.method_storage.getter(method_name);
@@ -83,6 +99,11 @@ impl Class {
             let current_prop = &properties[index];
 
             let current_value = match values.pop_front() {
+                // Arrays are converted for Set typed properties
+                Some(Value::Array(arr)) if current_prop.type__() == ValueType::Set => {
+                    let elements: Vec<Value> = arr.borrow().iter().cloned().collect();
+                    Value::from(RawSet::from_values(elements)?)
+                }
                 Some(val) => {
                     // check instantiation param type
                     if !val.check_type(current_prop.type__()) {
@@ -101,7 +122,12 @@ impl Class {
             index += 1;
         }
 
-        return Ok(Object::new(temp_list, Some(class_self)));
+        let check = class_self.instance_check;
+        let object = Object::new(temp_list, Some(class_self));
+        if let Some(InstanceCheck(check)) = check {
+            check(&object)?;
+        }
+        return Ok(object);
     }
 
     pub fn display_methods(f: &mut fmt::Formatter<'_>, cls: &Class, level: usize) -> fmt::Result {
@@ -4,6 +4,9 @@ use std::rc::Rc;
 
 use crate::public::env::ENV_OPTION;
 use crate::public::error::{assignment_error, reference_error, ReferenceType};
+use crate::public::std::modules::matrix::MatrixModule;
+use crate::public::std::modules::poly::PolyModule;
+use crate::public::std::modules::time::TimeModule;
 use crate::public::value::oop::class::Class;
 use crate::public::value::{self, ComplexStructure, GetAddr};
 use crate::utils::completer::Completer;
@@ -74,6 +77,24 @@ impl Object {
         }
     }
 
+    // stored properties, the methods of prototype are excluded
+    pub fn properties(&self) -> ListStorage<Value> {
+        let ComposeStorage {
+            storage_pattern,
+            data_list,
+            data_map,
+        } = self.get_store();
+
+        match storage_pattern {
+            DataStoragePattern::List => data_list.clone().unwrap(),
+            DataStoragePattern::Map => {
+                let mut list: ListStorage<Value> = data_map.clone().unwrap().into_iter().collect();
+                list.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+                list
+            }
+        }
+    }
+
     pub fn get(&self, prop_name: &str) -> Result<Value, ()> {
         let store = self.get_store();
         let target_value_result = store.getter(prop_name);
@@ -134,6 +155,21 @@ pub struct BuildInObject {
 impl ComplexStructure for Object {
     fn display(f: &mut fmt::Formatter<'_>, obj: &Rc<RefCell<Self>>, level: usize) -> fmt::Result {
         let obj_ref = obj.as_ref().borrow();
+        if MatrixModule::is_matrix_object(&obj_ref) {
+            if let Some(result) = MatrixModule::display(f, &obj_ref, level) {
+                return result;
+            }
+        }
+        if PolyModule::is_poly_object(&obj_ref) {
+            if let Some(result) = PolyModule::display(f, &obj_ref) {
+                return result;
+            }
+        }
+        if TimeModule::is_time_object(&obj_ref) {
+            if let Some(result) = TimeModule::display(f, &obj_ref) {
+                return result;
+            }
+        }
         let store = obj_ref.get_store();
         let ComposeStorage {
             storage_pattern,
@@ -0,0 +1,355 @@
+use std::fmt;
+
+use crate::public::error::math_error;
+
+use super::decimal::Decimal;
+use super::number::Number;
+use super::symbols::Symbols;
+use super::value::Value;
+
+// exponents of the SI base dimensions:
+// [length, mass, time, electric current, temperature, amount of substance, luminous intensity]
+pub type Dimension = [i8; 7];
+
+const DIMENSIONLESS: Dimension = [0; 7];
+
+// (symbol, factor to the coherent SI unit, dimension, is SI prefix available)
+const UNITS: [(&str, &str, Dimension, bool); 33] = [
+    // base units
+    ("m", "1", [1, 0, 0, 0, 0, 0, 0], true),
+    ("g", "1e-3", [0, 1, 0, 0, 0, 0, 0], true),
+    ("s", "1", [0, 0, 1, 0, 0, 0, 0], true),
+    ("A", "1", [0, 0, 0, 1, 0, 0, 0], true),
+    ("K", "1", [0, 0, 0, 0, 1, 0, 0], true),
+    ("mol", "1", [0, 0, 0, 0, 0, 1, 0], true),
+    ("cd", "1", [0, 0, 0, 0, 0, 0, 1], true),
+    // derived units
+    ("N", "1", [1, 1, -2, 0, 0, 0, 0], true),
+    ("J", "1", [2, 1, -2, 0, 0, 0, 0], true),
+    ("W", "1", [2, 1, -3, 0, 0, 0, 0], true),
+    ("Pa", "1", [-1, 1, -2, 0, 0, 0, 0], true),
+    ("Hz", "1", [0, 0, -1, 0, 0, 0, 0], true),
+    ("C", "1", [0, 0, 1, 1, 0, 0, 0], true),
+    ("V", "1", [2, 1, -3, -1, 0, 0, 0], true),
+    ("ohm", "1", [2, 1, -3, -2, 0, 0, 0], true),
+    ("L", "1e-3", [3, 0, 0, 0, 0, 0, 0], true),
+    ("Wh", "3600", [2, 1, -2, 0, 0, 0, 0], true),
+    ("eV", "1.602176634e-19", [2, 1, -2, 0, 0, 0, 0], true),
+    // time
+    ("min", "60", [0, 0, 1, 0, 0, 0, 0], false),
+    ("h", "3600", [0, 0, 1, 0, 0, 0, 0], false),
+    ("day", "86400", [0, 0, 1, 0, 0, 0, 0], false),
+    ("week", "604800", [0, 0, 1, 0, 0, 0, 0], false),
+    // length
+    ("inch", "0.0254", [1, 0, 0, 0, 0, 0, 0], false),
+    ("ft", "0.3048", [1, 0, 0, 0, 0, 0, 0], false),
+    ("yd", "0.9144", [1, 0, 0, 0, 0, 0, 0], false),
+    ("mi", "1609.344", [1, 0, 0, 0, 0, 0, 0], false),
+    // mass
+    ("t", "1000", [0, 1, 0, 0, 0, 0, 0], false),
+    ("lb", "0.45359237", [0, 1, 0, 0, 0, 0, 0], false),
+    ("oz", "0.028349523125", [0, 1, 0, 0, 0, 0, 0], false),
+    // others
+    ("cal", "4.184", [2, 1, -2, 0, 0, 0, 0], true),
+    ("bar", "1e5", [-1, 1, -2, 0, 0, 0, 0], false),
+    ("atm", "101325", [-1, 1, -2, 0, 0, 0, 0], false),
+    ("ha", "1e4", [2, 0, 0, 0, 0, 0, 0], false),
+];
+
+const PREFIXES: [(&str, &str); 9] = [
+    ("T", "1e12"),
+    ("G", "1e9"),
+    ("M", "1e6"),
+    ("k", "1e3"),
+    ("c", "1e-2"),
+    ("m", "1e-3"),
+    ("u", "1e-6"),
+    ("n", "1e-9"),
+    ("p", "1e-12"),
+];
+
+// exact rational number from the decimal string in the tables
+fn exact_factor(source: &str) -> Number {
+    let (upper, lower) = Decimal::parse(source).unwrap().fraction_pair();
+    Number::fraction(upper, lower).simplify()
+}
+
+// find the unit symbol, like: `m` | `km` | `min`
+fn lookup(symbol: &str) -> Option<(Number, Dimension)> {
+    for (unit_symbol, factor, dimension, _) in UNITS {
+        if symbol == unit_symbol {
+            return Some((exact_factor(factor), dimension));
+        }
+    }
+    for (prefix, prefix_factor) in PREFIXES {
+        let Some(rest) = symbol.strip_prefix(prefix) else {
+            continue;
+        };
+        for (unit_symbol, factor, dimension, is_prefixable) in UNITS {
+            if is_prefixable && rest == unit_symbol {
+                let factor = exact_factor(prefix_factor) * exact_factor(factor);
+                return Some((factor.simplify(), dimension));
+            }
+        }
+    }
+    return None;
+}
+
+// --- --- --- --- --- ---
+
+// product of units, like: `km/h` | `kg*m/s^2`
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(PartialEq, Clone, Default)]
+pub struct CompoundUnit {
+    // (unit symbol, power), the power is never ZERO
+    terms: Vec<(String, i8)>,
+}
+
+impl CompoundUnit {
+    pub fn is_unit(symbol: &str) -> bool {
+        lookup(symbol).is_some()
+    }
+
+    // the symbol should be checked by `is_unit`
+    pub fn push(&mut self, symbol: &str, power: i8) {
+        match self.terms.iter_mut().find(|(term, _)| term == symbol) {
+            Some((_, current)) => *current = current.saturating_add(power),
+            None => self.terms.push((symbol.to_string(), power)),
+        }
+        self.terms.retain(|(_, power)| *power != 0);
+    }
+
+    fn combine(&self, other: &Self, sign: i8) -> Self {
+        let mut result = self.clone();
+        for (symbol, power) in &other.terms {
+            result.push(symbol, power.saturating_mul(sign));
+        }
+        result
+    }
+
+    fn factor(&self) -> Number {
+        let mut result = Number::Int(1);
+        for (symbol, power) in &self.terms {
+            let (factor, _) = lookup(symbol).unwrap();
+            let powered = Number::pow(factor, Number::Int(power.unsigned_abs() as i64));
+            result = if *power > 0 {
+                (result * powered).simplify()
+            } else {
+                Number::exact_div(result, powered)
+            };
+        }
+        result
+    }
+
+    fn dimension(&self) -> Dimension {
+        let mut result = DIMENSIONLESS;
+        for (symbol, power) in &self.terms {
+            let (_, dimension) = lookup(symbol).unwrap();
+            for (target, base) in result.iter_mut().zip(dimension) {
+                *target = target.saturating_add(base.saturating_mul(*power));
+            }
+        }
+        result
+    }
+}
+
+impl fmt::Display for CompoundUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn term_display(symbol: &str, power: i8) -> String {
+            match power.unsigned_abs() {
+                1 => symbol.to_string(),
+                power => format!("{}^{}", symbol, power),
+            }
+        }
+
+        let numerators: Vec<String> = self
+            .terms
+            .iter()
+            .filter(|(_, power)| *power > 0)
+            .map(|(symbol, power)| term_display(symbol, *power))
+            .collect();
+        let denominators: Vec<String> = self
+            .terms
+            .iter()
+            .filter(|(_, power)| *power < 0)
+            .map(|(symbol, power)| term_display(symbol, *power))
+            .collect();
+
+        if numerators.is_empty() {
+            write!(f, "1")?;
+        } else {
+            write!(f, "{}", numerators.join("*"))?;
+        }
+        for denominator in denominators {
+            write!(f, "/{}", denominator)?;
+        }
+        Ok(())
+    }
+}
+
+// --- --- --- --- --- ---
+
+// physical quantity, like: `3 km` | `9.8 m/s^2`
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Clone)]
+pub struct Quantity {
+    // magnitude in the `unit`
+    pub value: Number,
+    pub unit: CompoundUnit,
+}
+
+impl Quantity {
+    pub fn new(value: Number, unit: CompoundUnit) -> Self {
+        Self { value, unit }
+    }
+
+    // regard Number as the dimensionless Quantity
+    fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Quantity(quantity) => quantity.clone(),
+            Value::Number(num) => Self::new(num.clone(), CompoundUnit::default()),
+            _ => unreachable!(),
+        }
+    }
+    // Number is used if the unit is dimensionless, like: `km/m`
+    fn into_value(self) -> Value {
+        if self.unit.dimension() == DIMENSIONLESS {
+            let value = (self.value * self.unit.factor()).simplify();
+            return Value::Number(value);
+        }
+        Value::Quantity(Self::new(self.value.simplify(), self.unit))
+    }
+
+    // magnitude in the target unit, with the same dimension
+    fn value_in(&self, unit: &CompoundUnit) -> Number {
+        let ratio = Number::exact_div(self.unit.factor(), unit.factor());
+        (self.value.clone() * ratio).simplify()
+    }
+
+    fn check_dimension(&self, other: &Self, operator: Symbols) -> Result<(), ()> {
+        if self.unit.dimension() != other.unit.dimension() {
+            let msg = format!(
+                "incompatible units `{}` and `{}` for {}",
+                self.unit, other.unit, operator
+            );
+            return Err(math_error(&msg)?);
+        }
+        Ok(())
+    }
+
+    fn pow(self, exponent: Number) -> Result<Self, ()> {
+        let (numerator, denominator) = match &exponent {
+            Number::Int(i) => (*i, 1),
+            Number::Fraction(upper, lower) => match (upper.to_i64(), lower.to_i64()) {
+                (Some(upper), Some(lower)) => (upper, lower),
+                _ => (0, 0),
+            },
+            _ => (0, 0),
+        };
+
+        let mut unit = CompoundUnit::default();
+        for (symbol, power) in &self.unit.terms {
+            let powered = (*power as i64).checked_mul(numerator);
+            let new_power = powered
+                .filter(|powered| denominator != 0 && powered % denominator == 0)
+                .and_then(|powered| i8::try_from(powered / denominator).ok());
+            let Some(new_power) = new_power else {
+                let msg = format!(
+                    "the unit `{}` can not be raised to power {}",
+                    self.unit, exponent
+                );
+                return Err(math_error(&msg)?);
+            };
+            unit.push(symbol, new_power);
+        }
+        Ok(Self::new(Number::pow(self.value, exponent), unit))
+    }
+
+    pub fn negate(&self) -> Self {
+        Self::new(Number::Int(0) - self.value.clone(), self.unit.clone())
+    }
+
+    // operate Quantity with Quantity or Number
+    pub fn operate(val1: &Value, val2: &Value, operator: Symbols) -> Result<Value, ()> {
+        let quantity1 = Self::from_value(val1);
+        let quantity2 = Self::from_value(val2);
+
+        let result = match operator {
+            Symbols::Plus | Symbols::Minus | Symbols::Mod => {
+                quantity1.check_dimension(&quantity2, operator)?;
+                let other = quantity2.value_in(&quantity1.unit);
+                let value = match operator {
+                    Symbols::Plus => quantity1.value + other,
+                    Symbols::Minus => quantity1.value - other,
+                    Symbols::Mod => Number::modulo(quantity1.value, other),
+                    _ => unreachable!(),
+                };
+                Self::new(value, quantity1.unit).into_value()
+            }
+            Symbols::FloorDivide => {
+                quantity1.check_dimension(&quantity2, operator)?;
+                let other = quantity2.value_in(&quantity1.unit);
+                Value::Number(Number::floor_div(quantity1.value, other))
+            }
+            Symbols::Multiply => {
+                let unit = quantity1.unit.combine(&quantity2.unit, 1);
+                Self::new(quantity1.value * quantity2.value, unit).into_value()
+            }
+            Symbols::Divide => {
+                let unit = quantity1.unit.combine(&quantity2.unit, -1);
+                Self::new(Number::exact_div(quantity1.value, quantity2.value), unit).into_value()
+            }
+            Symbols::Power => {
+                let Value::Number(exponent) = val2 else {
+                    return Err(math_error("the exponent should be a dimensionless Number")?);
+                };
+                quantity1.pow(exponent.clone())?.into_value()
+            }
+            Symbols::Convert => {
+                let Value::Quantity(target) = val2 else {
+                    return Err(math_error("the conversion target should be a unit")?);
+                };
+                quantity1.check_dimension(&quantity2, operator)?;
+                let value = quantity1.value_in(&target.unit);
+                Value::Quantity(Self::new(value, target.unit.clone()))
+            }
+            Symbols::CompareEqual => Value::from(quantity1 == quantity2),
+            Symbols::NotEqual => Value::from(quantity1 != quantity2),
+            Symbols::LessThan
+            | Symbols::MoreThan
+            | Symbols::LessThanEqual
+            | Symbols::MoreThanEqual => {
+                quantity1.check_dimension(&quantity2, operator)?;
+                let num1 = quantity1.value;
+                let num2 = quantity2.value_in(&quantity1.unit);
+                let result = match operator {
+                    Symbols::LessThan => num1 < num2,
+                    Symbols::MoreThan => num1 > num2,
+                    Symbols::LessThanEqual => num1 <= num2,
+                    Symbols::MoreThanEqual => num1 >= num2,
+                    _ => unreachable!(),
+                };
+                Value::from(result)
+            }
+            Symbols::AndSign => Value::from(val1.get_bool() && val2.get_bool()),
+            Symbols::OrSign => Value::from(val1.get_bool() || val2.get_bool()),
+            _ => {
+                let msg = format!("the operation {} is not available for Quantity", operator);
+                return Err(math_error(&msg)?);
+            }
+        };
+        return Ok(result);
+    }
+}
+
+impl PartialEq for Quantity {
+    fn eq(&self, other: &Self) -> bool {
+        self.unit.dimension() == other.unit.dimension() && self.value == other.value_in(&self.unit)
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.unit)
+    }
+}
@@ -0,0 +1,231 @@
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+
+use crossterm::style::Stylize;
+
+use super::big_int::BigInt;
+use super::number::Number;
+use super::value::{Value, ValueType};
+use super::{display_indent, Addr, ComplexStructure, GetAddr};
+use crate::public::error::{math_error, type_error};
+
+// hashing key of the Set elements.
+// Numbers are the same element only if their exact values are equal,
+// so `1`, `1.0` and `(2 / 2)` are the same, while the Float `0.1` and `fraction(1, 10)` are not.
+// it is stricter than `==`, which compares Floats with a tolerance,
+// since the tolerance is not transitive and can not be hashed.
+// Strings are keyed by content and Uniques by identity.
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub enum SetKey {
+    Boolean(bool),
+    // exact reduced (upper, lower) pair
+    Number(BigInt, BigInt),
+    // `true` for the positive infinity
+    Infinity(bool),
+    // bits of the real and imaginary part
+    Complex(u64, u64),
+    String(String),
+    Unique(Addr),
+}
+
+impl SetKey {
+    pub fn from_value(value: &Value) -> Result<Self, ()> {
+        let key = match value {
+            Value::Boolean(bool_val) => Self::Boolean(*bool_val),
+            Value::Number(num) => match (num.exact_fraction(), num) {
+                (Some((upper, lower)), _) => Self::Number(upper, lower),
+                (None, Number::Float(f)) if f.is_infinite() => Self::Infinity(*f > 0.0),
+                (None, Number::Complex(real, imaginary))
+                    if !real.is_nan() && !imaginary.is_nan() =>
+                {
+                    // `-0.0` and `0.0` are the same
+                    Self::Complex((real + 0.0).to_bits(), (imaginary + 0.0).to_bits())
+                }
+                (None, _) => {
+                    let msg = format!("{} can not be a Set element", num);
+                    return Err(math_error(&msg)?);
+                }
+            },
+            Value::String(str) => Self::String(str.borrow().clone()),
+            Value::Unique(uni) => Self::Unique(uni.get_addr()),
+            _ => {
+                return Err(type_error(
+                    Some("Set element"),
+                    vec![
+                        ValueType::Boolean,
+                        ValueType::Number,
+                        ValueType::String,
+                        ValueType::Unique,
+                    ],
+                    value.get_type(),
+                )?)
+            }
+        };
+        Ok(key)
+    }
+}
+
+// --- --- --- --- --- ---
+
+pub struct RawSet {
+    // elements in insertion order,
+    // removed elements are `None` until compacted.
+    elements: Vec<Option<(SetKey, Value)>>,
+    indices: HashMap<SetKey, usize>,
+}
+
+impl RawSet {
+    pub fn new() -> Self {
+        return Self {
+            elements: vec![],
+            indices: HashMap::new(),
+        };
+    }
+
+    pub fn from_values<T: IntoIterator<Item = Value>>(values: T) -> Result<Self, ()> {
+        let mut set = Self::new();
+        for value in values {
+            set.insert(value)?;
+        }
+        return Ok(set);
+    }
+
+    fn push_entry(&mut self, key: SetKey, value: Value) {
+        self.indices.insert(key.clone(), self.elements.len());
+        self.elements.push(Some((key, value)));
+    }
+    fn entries(&self) -> impl Iterator<Item = &(SetKey, Value)> {
+        return self.elements.iter().flatten();
+    }
+    fn has_key(&self, key: &SetKey) -> bool {
+        return self.indices.contains_key(key);
+    }
+
+    // returns `false` if the element already exists
+    pub fn insert(&mut self, value: Value) -> Result<bool, ()> {
+        let key = SetKey::from_value(&value)?;
+        if self.has_key(&key) {
+            return Ok(false);
+        }
+        // Strings are copied, the later modification does not affect the Set
+        let value = match value {
+            Value::String(str) => Value::from(str.borrow().clone()),
+            _ => value,
+        };
+        self.push_entry(key, value);
+        return Ok(true);
+    }
+    // returns `false` if the element does not exist
+    pub fn remove(&mut self, value: &Value) -> Result<bool, ()> {
+        let key = SetKey::from_value(value)?;
+        let Some(index) = self.indices.remove(&key) else {
+            return Ok(false);
+        };
+        self.elements[index] = None;
+
+        // compact when more than half are removed
+        if self.elements.len() > self.indices.len() * 2 {
+            let entries: Vec<(SetKey, Value)> = self.elements.drain(..).flatten().collect();
+            self.indices.clear();
+            for (key, value) in entries {
+                self.push_entry(key, value);
+            }
+        }
+        return Ok(true);
+    }
+    pub fn has(&self, value: &Value) -> Result<bool, ()> {
+        let key = SetKey::from_value(value)?;
+        return Ok(self.has_key(&key));
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+        self.indices.clear();
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        return self.indices.len();
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &Value> {
+        return self.entries().map(|(_, value)| value);
+    }
+
+    pub fn join(&self, div: &str) -> String {
+        let strings: Vec<String> = self.iter().map(|v| v.to_raw_string()).collect();
+        return strings.join(div);
+    }
+
+    // --- --- --- --- --- ---
+
+    // elements of `self` which satisfy the `predicate`, in insertion order
+    fn select(&self, predicate: impl Fn(&SetKey) -> bool) -> Self {
+        let mut result = Self::new();
+        for (key, value) in self.entries() {
+            if predicate(key) {
+                result.push_entry(key.clone(), value.clone());
+            }
+        }
+        return result;
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.select(|_| true);
+        for (key, value) in other.entries() {
+            if !result.has_key(key) {
+                result.push_entry(key.clone(), value.clone());
+            }
+        }
+        return result;
+    }
+    pub fn intersection(&self, other: &Self) -> Self {
+        return self.select(|key| other.has_key(key));
+    }
+    pub fn difference(&self, other: &Self) -> Self {
+        return self.select(|key| !other.has_key(key));
+    }
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut result = self.difference(other);
+        for (key, value) in other.entries() {
+            if !self.has_key(key) {
+                result.push_entry(key.clone(), value.clone());
+            }
+        }
+        return result;
+    }
+}
+
+impl PartialEq for RawSet {
+    // Sets with the same elements are equal, regardless of the order
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.entries().all(|(key, _)| other.has_key(key))
+    }
+}
+
+impl ComplexStructure for RawSet {
+    fn display(f: &mut fmt::Formatter<'_>, set: &Rc<RefCell<Self>>, level: usize) -> fmt::Result {
+        const LINE_COUNT: usize = 5;
+
+        write!(f, "{{")?;
+        for (index, element) in set.borrow().iter().enumerate() {
+            // print indent
+            if index % LINE_COUNT == 0 {
+                write!(f, "\r\n")?;
+                write!(f, "{}", display_indent(level))?;
+            }
+            // print element
+            Self::item_display(f, element, level + 1)?;
+            // comma symbol print
+            write!(f, "{}", ", ".dim())?;
+        }
+
+        write!(f, "\r\n")?;
+        write!(f, "{}}}", display_indent(level - 1))
+    }
+
+    fn deep_clone(set: &Rc<RefCell<Self>>) -> Value {
+        let mut new_set = Self::new();
+        for (key, value) in set.borrow().entries() {
+            new_set.push_entry(key.clone(), Self::item_clone(value));
+        }
+        return Value::from(new_set);
+    }
+}
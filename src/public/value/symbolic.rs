@@ -0,0 +1,644 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::public::compile_time::ast::ast_enum::{ASTNode, ASTVec};
+use crate::public::compile_time::ast::types::{
+    ExpressionNode, InvocationNode, LazyExpressionNode, ObjectReadingNode, VariableNode,
+};
+use crate::public::error::math_error;
+
+use super::number::Number;
+use super::symbols::Symbols;
+
+// Symbolic layer for lazy-expressions:
+// the postfix elements are converted into a term tree,
+// which can be differentiated, simplified and printed in infix form.
+
+#[derive(PartialEq, Clone)]
+pub struct CallTerm {
+    caller: ASTNode,
+    // the inserted object param for object methods, like: `Math` in `Math.sin(x)`
+    self_param: Option<ExpressionNode>,
+    args: Vec<Term>,
+}
+
+#[derive(PartialEq, Clone)]
+pub enum Term {
+    Number(Number),
+    Variable(String),
+    Unary(Symbols, Box<Term>),
+    Binary(Symbols, Box<Term>, Box<Term>),
+    Call(Box<CallTerm>),
+    // the ASTNode which is not symbolically resolved
+    Other(ASTNode),
+}
+
+const MAX_SIMPLIFY_PASS: usize = 16;
+
+fn int(i: i64) -> Term {
+    Term::Number(Number::Int(i))
+}
+fn binary(symbol: Symbols, left: Term, right: Term) -> Term {
+    Term::Binary(symbol, left.into(), right.into())
+}
+fn negate(term: Term) -> Term {
+    Term::Unary(Symbols::Negate, term.into())
+}
+
+// the call with `Math` module function, like: `Math.cos(x)`
+fn math_call(name: &str, arg: Term) -> Term {
+    let math_node = ASTNode::Variable(
+        VariableNode {
+            name: String::from("Math"),
+        }
+        .into(),
+    );
+    let caller = ASTNode::ObjectReading(
+        ObjectReadingNode {
+            obj_node: math_node.clone(),
+            property: String::from(name),
+        }
+        .into(),
+    );
+    let call = CallTerm {
+        caller,
+        self_param: Some(ExpressionNode {
+            elements: vec![math_node],
+        }),
+        args: vec![arg],
+    };
+    Term::Call(call.into())
+}
+
+// fold two numeric operands,
+// the division which is truncated in computing is kept.
+fn fold(symbol: Symbols, num1: &Number, num2: &Number) -> Option<Number> {
+    let (num1, num2) = (num1.clone(), num2.clone());
+    let result = match symbol {
+        Symbols::Plus => num1 + num2,
+        Symbols::Minus => num1 - num2,
+        Symbols::Multiply => num1 * num2,
+        Symbols::Divide => {
+            if num2.is_zero() {
+                return None;
+            }
+            if num1.is_integer() && num2.is_integer() {
+                let quotient = Number::exact_div(num1, num2);
+                if !quotient.is_integer() {
+                    return None;
+                }
+                quotient
+            } else {
+                num1 / num2
+            }
+        }
+        Symbols::Power => {
+            if num1.is_zero() && num2.float_value() < 0.0 {
+                return None;
+            }
+            Number::pow(num1, num2)
+        }
+        _ => return None,
+    };
+    match result {
+        Number::NotANumber => None,
+        _ => Some(result.simplify()),
+    }
+}
+
+impl CallTerm {
+    fn name(&self) -> String {
+        render_node(&self.caller)
+    }
+    // function name for `Math.xxx` calls
+    fn math_function(&self) -> Option<&str> {
+        let ASTNode::ObjectReading(reading_node) = &self.caller else {
+            return None;
+        };
+        match &reading_node.obj_node {
+            ASTNode::Variable(var_node) if var_node.name == "Math" => Some(&reading_node.property),
+            _ => None,
+        }
+    }
+}
+
+impl Term {
+    // build term tree from postfix expression elements,
+    // None is returned for invalid sequences.
+    pub fn from_elements(elements: &ASTVec) -> Option<Self> {
+        let mut stack = Vec::<Term>::new();
+        for node in elements {
+            let term = match node {
+                ASTNode::NumberLiteral(num) => Term::Number(num.clone()),
+                ASTNode::Variable(var_node) => Term::Variable(var_node.name.clone()),
+                ASTNode::Expression(expr_node) => Term::from_elements(&expr_node.elements)?,
+                ASTNode::Invocation(invoke_node) => Term::from_invocation(invoke_node)?,
+                ASTNode::SymbolLiteral(symbol) if Symbols::is_unary_symbol(*symbol) => {
+                    Term::Unary(*symbol, stack.pop()?.into())
+                }
+                ASTNode::SymbolLiteral(symbol) => {
+                    let right = stack.pop()?;
+                    let left = stack.pop()?;
+                    binary(*symbol, left, right)
+                }
+                _ => Term::Other(node.clone()),
+            };
+            stack.push(term);
+        }
+
+        if stack.len() == 1 {
+            stack.pop()
+        } else {
+            None
+        }
+    }
+    pub fn from_lazy_expr(node: &ASTNode) -> Option<Self> {
+        match node {
+            ASTNode::Expression(expr_node) => Term::from_elements(&expr_node.elements),
+            _ => None,
+        }
+    }
+    fn from_invocation(node: &InvocationNode) -> Option<Self> {
+        let mut params = node.params.iter();
+        let self_param = match node.caller {
+            ASTNode::ObjectReading(_) => Some(params.next()?.clone()),
+            _ => None,
+        };
+        let args = params
+            .map(|param| Term::from_elements(&param.elements))
+            .collect::<Option<Vec<Term>>>()?;
+
+        let call = CallTerm {
+            caller: node.caller.clone(),
+            self_param,
+            args,
+        };
+        Some(Term::Call(call.into()))
+    }
+
+    pub fn to_lazy_expr(&self) -> ASTNode {
+        let expr_node = ExpressionNode {
+            elements: self.to_elements(),
+        };
+        ASTNode::Expression(expr_node.into())
+    }
+    pub fn to_elements(&self) -> ASTVec {
+        let mut elements = ASTVec::new();
+        self.push_elements(&mut elements);
+        elements
+    }
+    fn push_elements(&self, elements: &mut ASTVec) {
+        match self {
+            Term::Number(num) => elements.push(ASTNode::NumberLiteral(num.clone())),
+            Term::Variable(name) => elements.push(ASTNode::Variable(
+                VariableNode { name: name.clone() }.into(),
+            )),
+            Term::Unary(symbol, operand) => {
+                operand.push_elements(elements);
+                elements.push(ASTNode::SymbolLiteral(*symbol));
+            }
+            Term::Binary(symbol, left, right) => {
+                left.push_elements(elements);
+                right.push_elements(elements);
+                elements.push(ASTNode::SymbolLiteral(*symbol));
+            }
+            Term::Call(call) => {
+                let mut params: Vec<ExpressionNode> = call.self_param.iter().cloned().collect();
+                for arg in &call.args {
+                    params.push(ExpressionNode {
+                        elements: arg.to_elements(),
+                    });
+                }
+                let invoke_node = InvocationNode {
+                    caller: call.caller.clone(),
+                    params,
+                };
+                elements.push(ASTNode::Invocation(invoke_node.into()));
+            }
+            Term::Other(node) => elements.push(node.clone()),
+        }
+    }
+
+    fn is_number(&self, target: i64) -> bool {
+        matches!(self, Term::Number(num) if *num == Number::Int(target))
+    }
+    fn contains(&self, var_name: &str) -> bool {
+        match self {
+            Term::Number(_) => false,
+            Term::Variable(name) => name == var_name,
+            Term::Unary(_, operand) => operand.contains(var_name),
+            Term::Binary(_, left, right) => left.contains(var_name) || right.contains(var_name),
+            Term::Call(call) => call.args.iter().any(|arg| arg.contains(var_name)),
+            Term::Other(_) => false,
+        }
+    }
+
+    // --- --- --- --- --- ---
+
+    pub fn substitute(&self, bindings: &HashMap<String, Term>) -> Self {
+        match self {
+            Term::Variable(name) => match bindings.get(name) {
+                Some(term) => term.clone(),
+                None => self.clone(),
+            },
+            Term::Unary(symbol, operand) => {
+                Term::Unary(*symbol, operand.substitute(bindings).into())
+            }
+            Term::Binary(symbol, left, right) => binary(
+                *symbol,
+                left.substitute(bindings),
+                right.substitute(bindings),
+            ),
+            Term::Call(call) => {
+                let mut new_call = call.as_ref().clone();
+                new_call.args = call
+                    .args
+                    .iter()
+                    .map(|arg| arg.substitute(bindings))
+                    .collect();
+                Term::Call(new_call.into())
+            }
+            Term::Number(_) | Term::Other(_) => self.clone(),
+        }
+    }
+
+    pub fn simplify(&self) -> Self {
+        let mut current = self.clone();
+        for _ in 0..MAX_SIMPLIFY_PASS {
+            let next = current.simplify_once();
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        current
+    }
+    fn simplify_once(&self) -> Self {
+        match self {
+            Term::Unary(symbol, operand) => {
+                let operand = operand.simplify_once();
+                match (symbol, operand) {
+                    (Symbols::Negate, Term::Number(num)) => Term::Number(Number::Int(0) - num),
+                    (Symbols::Negate, Term::Unary(Symbols::Negate, inner)) => *inner,
+                    (_, operand) => Term::Unary(*symbol, operand.into()),
+                }
+            }
+            Term::Binary(symbol, left, right) => {
+                let left = left.simplify_once();
+                let right = right.simplify_once();
+                Self::simplify_binary(*symbol, left, right)
+            }
+            Term::Call(call) => {
+                let mut new_call = call.as_ref().clone();
+                new_call.args = call.args.iter().map(|arg| arg.simplify_once()).collect();
+                Term::Call(new_call.into())
+            }
+            _ => self.clone(),
+        }
+    }
+    fn simplify_binary(symbol: Symbols, left: Term, right: Term) -> Self {
+        // constant folding
+        if let (Term::Number(num1), Term::Number(num2)) = (&left, &right) {
+            if let Some(result) = fold(symbol, num1, num2) {
+                return Term::Number(result);
+            }
+        }
+
+        match symbol {
+            Symbols::Plus if left.is_number(0) => right,
+            Symbols::Plus | Symbols::Minus if right.is_number(0) => left,
+            Symbols::Minus if left.is_number(0) => negate(right),
+            Symbols::Minus if left == right => int(0),
+            Symbols::Plus if left == right => binary(Symbols::Multiply, int(2), left),
+            Symbols::Plus => match right {
+                // `a + -b` -> `a - b`
+                Term::Unary(Symbols::Negate, operand) => binary(Symbols::Minus, left, *operand),
+                _ => binary(symbol, left, right),
+            },
+            Symbols::Minus => match right {
+                // `a - -b` -> `a + b`
+                Term::Unary(Symbols::Negate, operand) => binary(Symbols::Plus, left, *operand),
+                _ => binary(symbol, left, right),
+            },
+
+            Symbols::Multiply if left.is_number(0) || right.is_number(0) => int(0),
+            Symbols::Multiply if left.is_number(1) => right,
+            Symbols::Multiply if right.is_number(1) => left,
+            Symbols::Multiply if left.is_number(-1) => negate(right),
+            Symbols::Multiply if right.is_number(-1) => negate(left),
+            Symbols::Multiply => match (left, right) {
+                // numeric factor goes first: `x * 2` -> `2 * x`
+                (left, Term::Number(num)) if !matches!(left, Term::Number(_)) => {
+                    binary(symbol, Term::Number(num), left)
+                }
+                // combine numeric factors: `2 * (3 * x)` -> `6 * x`
+                (Term::Number(num1), Term::Binary(Symbols::Multiply, inner_left, inner_right))
+                    if matches!(*inner_left, Term::Number(_)) =>
+                {
+                    let Term::Number(num2) = *inner_left else {
+                        unreachable!()
+                    };
+                    binary(
+                        symbol,
+                        binary(symbol, Term::Number(num1), Term::Number(num2)),
+                        *inner_right,
+                    )
+                }
+                (left, right) if left == right => binary(Symbols::Power, left, int(2)),
+                (left, right) => binary(symbol, left, right),
+            },
+
+            Symbols::Divide if right.is_number(1) => left,
+            Symbols::Divide if left.is_number(0) => int(0),
+            Symbols::Power if right.is_number(0) => int(1),
+            Symbols::Power if right.is_number(1) || left.is_number(1) => left,
+            _ => binary(symbol, left, right),
+        }
+    }
+
+    // --- --- --- --- --- ---
+
+    pub fn diff(&self, var_name: &str) -> Result<Self, ()> {
+        if !self.contains(var_name) {
+            if let Term::Other(node) = self {
+                // the unresolved node may depends on the variable
+                if !matches!(
+                    node,
+                    ASTNode::QuantityLiteral(_)
+                        | ASTNode::StringLiteral(_)
+                        | ASTNode::ObjectReading(_)
+                ) {
+                    let msg = format!("cannot differentiate `{}`", render_node(node));
+                    return Err(math_error(&msg)?);
+                }
+            }
+            return Ok(int(0));
+        }
+
+        let result = match self {
+            Term::Variable(_) => int(1),
+            Term::Unary(Symbols::Negate, operand) => negate(operand.diff(var_name)?),
+            Term::Binary(symbol, left, right) => {
+                let (u, v) = (left.as_ref().clone(), right.as_ref().clone());
+                match symbol {
+                    Symbols::Plus | Symbols::Minus => {
+                        binary(*symbol, u.diff(var_name)?, v.diff(var_name)?)
+                    }
+                    Symbols::Multiply => binary(
+                        Symbols::Plus,
+                        binary(Symbols::Multiply, u.diff(var_name)?, v.clone()),
+                        binary(Symbols::Multiply, u, v.diff(var_name)?),
+                    ),
+                    Symbols::Divide => match &v {
+                        // `(u / c)'` -> `u' * (1 / c)`
+                        Term::Number(num) if !num.is_zero() => binary(
+                            Symbols::Multiply,
+                            Term::Number(Number::exact_div(Number::Int(1), num.clone())),
+                            u.diff(var_name)?,
+                        ),
+                        // `(u / v)'` -> `(u' * v - u * v') / v ^ 2`
+                        _ => binary(
+                            Symbols::Divide,
+                            binary(
+                                Symbols::Minus,
+                                binary(Symbols::Multiply, u.diff(var_name)?, v.clone()),
+                                binary(Symbols::Multiply, u, v.diff(var_name)?),
+                            ),
+                            binary(Symbols::Power, v, int(2)),
+                        ),
+                    },
+                    Symbols::Power => Self::diff_power(u, v, var_name)?,
+                    _ => {
+                        let msg = format!("cannot differentiate the operation {}", symbol);
+                        return Err(math_error(&msg)?);
+                    }
+                }
+            }
+            Term::Call(call) => Self::diff_call(call, var_name)?,
+            _ => {
+                let msg = format!("cannot differentiate `{}`", self);
+                return Err(math_error(&msg)?);
+            }
+        };
+        Ok(result.simplify())
+    }
+    fn diff_power(base: Term, exponent: Term, var_name: &str) -> Result<Self, ()> {
+        let result = if !exponent.contains(var_name) {
+            // `(u ^ n)'` -> `n * u ^ (n - 1) * u'`
+            binary(
+                Symbols::Multiply,
+                binary(
+                    Symbols::Multiply,
+                    exponent.clone(),
+                    binary(
+                        Symbols::Power,
+                        base.clone(),
+                        binary(Symbols::Minus, exponent, int(1)),
+                    ),
+                ),
+                base.diff(var_name)?,
+            )
+        } else if let Term::Number(num) = &base {
+            // `(c ^ v)'` -> `c ^ v * ln(c) * v'`
+            binary(
+                Symbols::Multiply,
+                binary(
+                    Symbols::Multiply,
+                    binary(Symbols::Power, base.clone(), exponent.clone()),
+                    Term::Number(num.ln()),
+                ),
+                exponent.diff(var_name)?,
+            )
+        } else {
+            // `(u ^ v)'` -> `u ^ v * (v' * ln(u) + v * u' / u)`
+            binary(
+                Symbols::Multiply,
+                binary(Symbols::Power, base.clone(), exponent.clone()),
+                binary(
+                    Symbols::Plus,
+                    binary(
+                        Symbols::Multiply,
+                        exponent.diff(var_name)?,
+                        math_call("ln", base.clone()),
+                    ),
+                    binary(
+                        Symbols::Divide,
+                        binary(Symbols::Multiply, exponent, base.diff(var_name)?),
+                        base,
+                    ),
+                ),
+            )
+        };
+        Ok(result)
+    }
+    fn diff_call(call: &CallTerm, var_name: &str) -> Result<Self, ()> {
+        let (Some(name), [arg]) = (call.math_function(), call.args.as_slice()) else {
+            let msg = format!("cannot differentiate the function `{}`", call.name());
+            return Err(math_error(&msg)?);
+        };
+        let arg = arg.clone();
+
+        // derivative of the outer function, chain rule is applied after
+        let outer = match name {
+            "sin" => math_call("cos", arg.clone()),
+            "cos" => negate(math_call("sin", arg.clone())),
+            "tan" => binary(
+                Symbols::Divide,
+                int(1),
+                binary(Symbols::Power, math_call("cos", arg.clone()), int(2)),
+            ),
+            "asin" | "acos" => {
+                let root = math_call(
+                    "sqrt",
+                    binary(
+                        Symbols::Minus,
+                        int(1),
+                        binary(Symbols::Power, arg.clone(), int(2)),
+                    ),
+                );
+                let result = binary(Symbols::Divide, int(1), root);
+                if name == "acos" {
+                    negate(result)
+                } else {
+                    result
+                }
+            }
+            "atan" => binary(
+                Symbols::Divide,
+                int(1),
+                binary(
+                    Symbols::Plus,
+                    int(1),
+                    binary(Symbols::Power, arg.clone(), int(2)),
+                ),
+            ),
+            "sinh" => math_call("cosh", arg.clone()),
+            "cosh" => math_call("sinh", arg.clone()),
+            "exp" => math_call("exp", arg.clone()),
+            "ln" => binary(Symbols::Divide, int(1), arg.clone()),
+            "sqrt" => binary(
+                Symbols::Divide,
+                int(1),
+                binary(Symbols::Multiply, int(2), math_call("sqrt", arg.clone())),
+            ),
+            _ => {
+                let msg = format!("cannot differentiate the function `{}`", call.name());
+                return Err(math_error(&msg)?);
+            }
+        };
+        Ok(binary(Symbols::Multiply, outer, arg.diff(var_name)?))
+    }
+
+    // --- --- --- --- --- ---
+
+    // binding power for infix printing
+    fn precedence(&self) -> i8 {
+        match self {
+            Term::Number(Number::Complex(_, _)) => 6,
+            Term::Number(num) if num.float_value() < 0.0 => 8,
+            Term::Unary(_, _) => 8,
+            Term::Binary(symbol, _, _) => match symbol {
+                Symbols::Power => 9,
+                Symbols::Multiply | Symbols::Divide | Symbols::Mod | Symbols::FloorDivide => 7,
+                Symbols::Plus | Symbols::Minus => 6,
+                Symbols::LeftShift | Symbols::RightShift => 5,
+                Symbols::BitAnd => 4,
+                Symbols::BitXor => 3,
+                Symbols::BitOr => 2,
+                _ if Symbols::is_compare_symbol(*symbol) => 1,
+                _ => 0,
+            },
+            _ => 10,
+        }
+    }
+}
+
+// infix form of other ASTNodes
+fn render_node(node: &ASTNode) -> String {
+    match node {
+        ASTNode::NumberLiteral(num) => num.to_string(),
+        ASTNode::QuantityLiteral(quantity) => quantity.to_string(),
+        ASTNode::StringLiteral(str) => format!("\"{}\"", str),
+        ASTNode::Variable(var_node) => var_node.name.clone(),
+        ASTNode::ObjectReading(reading_node) => {
+            format!(
+                "{}.{}",
+                render_node(&reading_node.obj_node),
+                reading_node.property
+            )
+        }
+        ASTNode::ElementReading(reading_node) => format!(
+            "{}[{}]",
+            render_node(&reading_node.target_node),
+            render_expression(&reading_node.index_node)
+        ),
+        ASTNode::ArrayLiteral(arr_node) => {
+            let elements: Vec<String> = arr_node.elements.iter().map(render_expression).collect();
+            format!("[{}]", elements.join(", "))
+        }
+        ASTNode::Expression(expr_node) => render_expression(expr_node),
+        ASTNode::LazyExpression(lazy_node) => lazy_expr_display(lazy_node),
+        _ => String::from("..."),
+    }
+}
+fn render_expression(expr_node: &ExpressionNode) -> String {
+    match Term::from_elements(&expr_node.elements) {
+        Some(term) => term.to_string(),
+        None => String::from("..."),
+    }
+}
+
+// infix form of lazy-expression, like: `{x ^ 2 + 1}` or `{x, y => x + y}`
+pub fn lazy_expr_display(lazy_node: &LazyExpressionNode) -> String {
+    let Some(term) = Term::from_lazy_expr(&lazy_node.sub_sequence) else {
+        return String::from("<Lazy-Expression>");
+    };
+    if lazy_node.params.is_empty() {
+        format!("{{{}}}", term)
+    } else {
+        format!("{{{} => {}}}", lazy_node.params.join(", "), term)
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Number(num) => write!(f, "{}", num),
+            Term::Variable(name) => write!(f, "{}", name),
+            Term::Unary(symbol, operand) => {
+                write!(f, "{}", symbol.operator())?;
+                if operand.precedence() <= self.precedence() {
+                    write!(f, "({})", operand)
+                } else {
+                    write!(f, "{}", operand)
+                }
+            }
+            Term::Binary(symbol, left, right) => {
+                let precedence = self.precedence();
+                // `^` is right-associative
+                let is_power = *symbol == Symbols::Power;
+                let left_paren =
+                    left.precedence() < precedence || (is_power && left.precedence() <= precedence);
+                let right_paren = right.precedence() < precedence
+                    || (!is_power && right.precedence() == precedence);
+
+                if left_paren {
+                    write!(f, "({})", left)?;
+                } else {
+                    write!(f, "{}", left)?;
+                }
+                write!(f, " {} ", symbol.operator())?;
+                if right_paren {
+                    write!(f, "({})", right)
+                } else {
+                    write!(f, "{}", right)
+                }
+            }
+            Term::Call(call) => {
+                let args: Vec<String> = call.args.iter().map(|arg| arg.to_string()).collect();
+                write!(f, "{}({})", call.name(), args.join(", "))
+            }
+            Term::Other(node) => write!(f, "{}", render_node(node)),
+        }
+    }
+}
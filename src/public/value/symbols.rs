@@ -22,11 +22,22 @@ pub enum Symbols {
     AndSign,
     OrSign,
 
+    FloorDivide,
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitNot,
+    LeftShift,
+    RightShift,
+    Negate,
+    Convert,
+
     Equal,
     PlusEqual,
     MinusEqual,
     MultiplyEqual,
     DivideEqual,
+    ModEqual,
     PowerEqual,
 
     ObjectReading,
@@ -52,6 +63,7 @@ impl Symbols {
             Self::Minus => Self::MinusEqual,
             Self::Multiply => Self::MultiplyEqual,
             Self::Divide => Self::DivideEqual,
+            Self::Mod => Self::ModEqual,
             Self::Power => Self::PowerEqual,
             Self::LessThan => Self::LessThanEqual,
             Self::MoreThan => Self::MoreThanEqual,
@@ -70,16 +82,45 @@ impl Symbols {
             Self::MinusEqual => Self::Minus,
             Self::MultiplyEqual => Self::Multiply,
             Self::DivideEqual => Self::Divide,
+            Self::ModEqual => Self::Mod,
             Self::PowerEqual => Self::Power,
             _ => self,
         }
     }
 
+    // word formed operators
+    pub fn from_word(word: &str) -> Option<Self> {
+        match word {
+            "and" => Some(Self::AndSign),
+            "or" => Some(Self::OrSign),
+            "not" => Some(Self::Not),
+            "band" => Some(Self::BitAnd),
+            "bor" => Some(Self::BitOr),
+            "bxor" => Some(Self::BitXor),
+            "to" | "in" => Some(Self::Convert),
+            _ => None,
+        }
+    }
+
+    pub fn is_unary_symbol(symbol: Self) -> bool {
+        return symbol == Self::Not || symbol == Self::BitNot || symbol == Self::Negate;
+    }
+
+    pub fn is_compare_symbol(symbol: Self) -> bool {
+        return symbol == Self::LessThan
+            || symbol == Self::MoreThan
+            || symbol == Self::LessThanEqual
+            || symbol == Self::MoreThanEqual
+            || symbol == Self::CompareEqual
+            || symbol == Self::NotEqual;
+    }
+
     pub fn is_basic_symbol(symbol: Self) -> bool {
         return symbol == Self::Plus
             || symbol == Self::Minus
             || symbol == Self::Multiply
             || symbol == Self::Divide
+            || symbol == Self::Mod
             || symbol == Self::Power
             || symbol == Self::Not
             || symbol == Self::LessThan
@@ -87,12 +128,55 @@ impl Symbols {
             || symbol == Self::Equal;
     }
 
+    // the operator text in source code
+    pub fn operator(&self) -> &'static str {
+        match self {
+            Self::Plus => "+",
+            Self::Minus | Self::Negate => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Mod => "%",
+            Self::Power => "^",
+
+            Self::Not => "!",
+            Self::AndSign => "&",
+            Self::OrSign => "|",
+
+            Self::FloorDivide => "//",
+            Self::BitAnd => "band",
+            Self::BitOr => "bor",
+            Self::BitXor => "bxor",
+            Self::BitNot => "~",
+            Self::LeftShift => "<<",
+            Self::RightShift => ">>",
+            Self::Convert => "to",
+
+            Self::LessThan => "<",
+            Self::MoreThan => ">",
+            Self::Equal => "=",
+            Self::LessThanEqual => "<=",
+            Self::MoreThanEqual => ">=",
+            Self::NotEqual => "!=",
+            Self::CompareEqual => "==",
+
+            Self::PlusEqual => "+=",
+            Self::MinusEqual => "-=",
+            Self::MultiplyEqual => "*=",
+            Self::DivideEqual => "/=",
+            Self::ModEqual => "%=",
+            Self::PowerEqual => "^=",
+
+            Self::ObjectReading => ".",
+        }
+    }
+
     pub fn is_equal_symbol(symbol: Self) -> bool {
         return symbol == Self::Equal
             || symbol == Self::PlusEqual
             || symbol == Self::MinusEqual
             || symbol == Self::MultiplyEqual
             || symbol == Self::DivideEqual
+            || symbol == Self::ModEqual
             || symbol == Self::PowerEqual;
     }
 }
@@ -107,6 +191,7 @@ impl From<char> for Symbols {
             '%' => Self::Mod,
             '^' => Self::Power,
             '!' => Self::Not,
+            '~' => Self::BitNot,
             '&' => Self::AndSign,
             '|' => Self::OrSign,
             '<' => Self::LessThan,
@@ -131,6 +216,16 @@ impl fmt::Display for Symbols {
             Self::AndSign => write!(f, "And"),
             Self::OrSign => write!(f, "Or"),
 
+            Self::FloorDivide => write!(f, "FloorDivide"),
+            Self::BitAnd => write!(f, "BitAnd"),
+            Self::BitOr => write!(f, "BitOr"),
+            Self::BitXor => write!(f, "BitXor"),
+            Self::BitNot => write!(f, "BitNot"),
+            Self::LeftShift => write!(f, "LeftShift"),
+            Self::RightShift => write!(f, "RightShift"),
+            Self::Negate => write!(f, "Negate"),
+            Self::Convert => write!(f, "Convert"),
+
             Self::LessThan => write!(f, "LessThan"),
             Self::MoreThan => write!(f, "MoreThan"),
             Self::Equal => write!(f, "Equal"),
@@ -143,6 +238,7 @@ impl fmt::Display for Symbols {
             Self::MinusEqual => write!(f, "MinusEqual"),
             Self::MultiplyEqual => write!(f, "MultiplyEqual"),
             Self::DivideEqual => write!(f, "DivideEqual"),
+            Self::ModEqual => write!(f, "ModEqual"),
             Self::PowerEqual => write!(f, "PowerEqual"),
 
             Self::ObjectReading => write!(f, "ObjectReading"),
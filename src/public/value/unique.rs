@@ -30,7 +30,8 @@ impl From<String> for Unique {
 
 impl GetAddr for Unique {
     fn get_addr(&self) -> super::Addr {
-        let ptr = self as *const Unique;
+        // clones of the same unique share the identifier
+        let ptr = Rc::as_ptr(&self.0);
         return ptr as super::Addr;
     }
 }
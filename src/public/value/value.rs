@@ -6,15 +6,18 @@ use std::rc::Rc;
 use crossterm::style::Stylize;
 
 use crate::public::env::ENV_OPTION;
-use crate::public::error::{internal_error, InternalComponent};
+use crate::public::error::{internal_error, math_error, InternalComponent};
 
-use super::super::compile_time::ast::ast_enum::ASTNode;
+use super::super::compile_time::ast::types::LazyExpressionNode;
 use super::array::{ArrayLiteral, RawArray};
 use super::function::{BuildInFunction, Function, UserDefinedFunction};
 use super::map::RawMap;
 use super::number::Number;
 use super::oop::class::Class;
 use super::oop::object::Object;
+use super::quantity::Quantity;
+use super::set::RawSet;
+use super::symbolic::lazy_expr_display;
 use super::unique::Unique;
 use super::{into_rc_refcell, ComplexStructure, GetAddr};
 
@@ -25,11 +28,13 @@ pub enum ValueType {
 
     Boolean,
     Number,
+    Quantity,
     Unique,
 
     String,
     Array,
     Map,
+    Set,
     LazyExpression,
 
     Function,
@@ -59,6 +64,11 @@ impl ValueType {
                     ("number", ValueType::Number),
                     ("Number", ValueType::Number),
                     // --- --- --- --- --- ---
+                    ("qty", ValueType::Quantity),
+                    ("Qty", ValueType::Quantity),
+                    ("quantity", ValueType::Quantity),
+                    ("Quantity", ValueType::Quantity),
+                    // --- --- --- --- --- ---
                     ("uni", ValueType::Unique),
                     ("Uni", ValueType::Unique),
                     ("unique", ValueType::Unique),
@@ -77,6 +87,9 @@ impl ValueType {
                     ("map", ValueType::Map),
                     ("Map", ValueType::Map),
                     // --- --- --- --- --- ---
+                    ("set", ValueType::Set),
+                    ("Set", ValueType::Set),
+                    // --- --- --- --- --- ---
                     ("lExpr", ValueType::LazyExpression),
                     ("LazyExpr", ValueType::LazyExpression),
                     // --- --- --- --- --- ---
@@ -117,10 +130,12 @@ impl fmt::Display for ValueType {
             ValueType::Void => write!(f, "Void"),
             ValueType::Boolean => write!(f, "Boolean"),
             ValueType::Number => write!(f, "Number"),
+            ValueType::Quantity => write!(f, "Quantity"),
             ValueType::Unique => write!(f, "Unique"),
             ValueType::String => write!(f, "String"),
             ValueType::Array => write!(f, "Array"),
             ValueType::Map => write!(f, "Map"),
+            ValueType::Set => write!(f, "Set"),
             ValueType::LazyExpression => write!(f, "LazyExpression"),
             ValueType::Function => write!(f, "Function"),
             ValueType::Class => write!(f, "Class"),
@@ -146,11 +161,13 @@ pub enum Value {
 
     Boolean(bool),
     Number(Number),
+    Quantity(Quantity),
     Unique(Unique),
     String(Rc<RefCell<String>>),
     Array(Rc<RefCell<RawArray>>),
     Map(Rc<RefCell<RawMap>>),
-    LazyExpression(Rc<RefCell<ASTNode>>),
+    Set(Rc<RefCell<RawSet>>),
+    LazyExpression(Rc<RefCell<LazyExpressionNode>>),
 
     Function(Function),
     Class(Rc<Class>),
@@ -186,6 +203,9 @@ impl Value {
                 "invalid `Value::get_i64` invocation"
             )?)
         };
+        if let Number::Complex(_, _) = num {
+            return Err(math_error("real number is expected, found Complex number")?);
+        }
         return Ok(num.int_value());
     }
     pub fn get_f64(&self) -> Result<f64, ()> {
@@ -196,15 +216,20 @@ impl Value {
                 "invalid `Value::get_f64` invocation"
             )?)
         };
+        if let Number::Complex(_, _) = num {
+            return Err(math_error("real number is expected, found Complex number")?);
+        }
         return Ok(num.float_value());
     }
     pub fn get_bool(&self) -> bool {
         match self {
             Self::Boolean(bool_val) => *bool_val,
             Self::Number(num) => *num != Number::Int(0),
+            Self::Quantity(quantity) => !quantity.value.is_zero(),
             Self::String(str) => str.as_ref().borrow().len() > 0,
             Self::Array(arr) => arr.as_ref().borrow().len() > 0,
             Self::Map(map) => map.as_ref().borrow().len() > 0,
+            Self::Set(set) => set.as_ref().borrow().len() > 0,
 
             Self::Void(_) => false,
             Self::LazyExpression(_)
@@ -232,13 +257,15 @@ impl Value {
             Self::Void(_) => self.to_string(),
             Self::Boolean(bool_val) => bool_val.to_string(),
             Self::Number(num) => num.to_string(),
+            Self::Quantity(quantity) => quantity.to_string(),
             Self::Unique(uni) => uni.get_identi().to_string(),
             Self::String(str) => str.as_ref().borrow().clone(),
             Self::Function(func) => func.to_string(),
             Self::Array(arr) => arr.as_ref().borrow().join(", "),
+            Self::Set(set) => set.as_ref().borrow().join(", "),
 
             Self::Map(_) => String::from("<Map>"),
-            Self::LazyExpression(_) => String::from("<Lazy-Expression>"),
+            Self::LazyExpression(l_expr) => lazy_expr_display(&l_expr.borrow()),
             Self::Class(_) => String::from("<Class>"),
             Self::Object(_) => String::from("<Object>"),
         }
@@ -255,6 +282,7 @@ impl Value {
             // can be directly cloned.
             Self::Boolean(_)
             | Self::Number(_)
+            | Self::Quantity(_)
             // Unique, Function and Class can not be modified,
             // can just clone their Rc.
             | Self::Unique(_)
@@ -272,6 +300,8 @@ impl Value {
                 RawMap::deep_clone(map),
             Self::Array(arr) =>
                 RawArray::deep_clone(arr),
+            Self::Set(set) =>
+                RawSet::deep_clone(set),
             Self::Object(obj) =>
                 Object::deep_clone(obj),
 
@@ -294,10 +324,12 @@ impl Value {
 
             Self::Boolean(_) => ValueType::Boolean,
             Self::Number(_) => ValueType::Number,
+            Self::Quantity(_) => ValueType::Quantity,
             Self::Unique(_) => ValueType::Unique,
             Self::String(_) => ValueType::String,
             Self::Array(_) => ValueType::Array,
             Self::Map(_) => ValueType::Map,
+            Self::Set(_) => ValueType::Set,
             Self::LazyExpression(_) => ValueType::LazyExpression,
 
             Self::Function(_) => ValueType::Function,
@@ -327,6 +359,7 @@ impl fmt::Display for Value {
             Self::String(str) => write!(f, "{}", str.as_ref().borrow()),
             Self::Array(arr) => RawArray::display(f, arr, 1),
             Self::Map(map) => RawMap::display(f, map, 1),
+            Self::Set(set) => RawSet::display(f, set, 1),
             Self::Object(obj) => Object::display(f, obj, 1),
             Self::Class(cls) => write!(f, "{}", cls),
 
@@ -337,7 +370,12 @@ impl fmt::Display for Value {
                             write!(f, "{}", bool_val.to_string().dark_yellow())
                         }
                         Self::Number(num) => write!(f, "{}", num.to_string().yellow()),
-                        Self::LazyExpression(_) => write!(f, "{}", "<Lazy-Expression>".cyan()),
+                        Self::Quantity(quantity) => {
+                            write!(f, "{}", quantity.to_string().yellow())
+                        }
+                        Self::LazyExpression(l_expr) => {
+                            write!(f, "{}", lazy_expr_display(&l_expr.borrow()).cyan())
+                        }
                         Self::Function(func) => write!(f, "{}", func.to_string().cyan()),
                         _ => unreachable!(),
                     }
@@ -345,7 +383,10 @@ impl fmt::Display for Value {
                     match self {
                         Self::Boolean(bool_val) => write!(f, "{}", bool_val),
                         Self::Number(num) => write!(f, "{}", num),
-                        Self::LazyExpression(_) => write!(f, "{}", "<Lazy-Expression>"),
+                        Self::Quantity(quantity) => write!(f, "{}", quantity),
+                        Self::LazyExpression(l_expr) => {
+                            write!(f, "{}", lazy_expr_display(&l_expr.borrow()))
+                        }
                         Self::Function(func) => write!(f, "{}", func),
                         _ => unreachable!(),
                     }
@@ -379,12 +420,14 @@ impl PartialEq for Value {
             (Self::Void(sub1), Self::Void(sub2)) => sub1 == sub2,
             (Self::Boolean(bool_val1), Self::Boolean(bool_val2)) => *bool_val1 == *bool_val2,
             (Self::Number(num1), Self::Number(num2)) => *num1 == *num2,
+            (Self::Quantity(quantity1), Self::Quantity(quantity2)) => *quantity1 == *quantity2,
             (Self::String(str_ref1), Self::String(str_ref2)) => {
                 let str1 = str_ref1.as_ref().borrow();
                 let temp = str_ref2.as_ref().borrow();
                 let str2 = temp.as_str();
                 str1.eq(str2)
             }
+            (Self::Set(set1), Self::Set(set2)) => *set1.borrow() == *set2.borrow(),
             (Self::LazyExpression(_), Self::LazyExpression(_))
             | (Self::Unique(_), Self::Unique(_))
             | (Self::Array(_), Self::Array(_))
@@ -439,8 +482,13 @@ impl From<RawMap> for Value {
         Self::Map(into_rc_refcell(value))
     }
 }
-impl From<ASTNode> for Value {
-    fn from(value: ASTNode) -> Self {
+impl From<RawSet> for Value {
+    fn from(value: RawSet) -> Self {
+        Self::Set(into_rc_refcell(value))
+    }
+}
+impl From<LazyExpressionNode> for Value {
+    fn from(value: LazyExpressionNode) -> Self {
         Self::LazyExpression(into_rc_refcell(value))
     }
 }
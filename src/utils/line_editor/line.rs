@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
 use crate::public::env::ENV_OPTION;
+use crate::utils::unicode::{char_width, str_width};
 use crossterm::style::Stylize;
 
 use super::tokenizer::{tokenize, TokenVec};
@@ -65,19 +66,33 @@ impl Line {
         self.content.push_str(str);
         self.refresh();
     }
-    pub fn pop(&mut self) {
-        self.content.pop();
-        self.refresh();
+    // insert / remove, the index is character index
+    fn byte_index(&self, index: usize) -> usize {
+        match self.content.char_indices().nth(index) {
+            Some((byte_index, _)) => byte_index,
+            None => self.content.len(),
+        }
     }
-
-    // insert / remove
     pub fn insert(&mut self, index: usize, ch: char) {
-        self.content.insert(index, ch);
+        let byte_index = self.byte_index(index);
+        self.content.insert(byte_index, ch);
         self.refresh();
     }
-    pub fn remove(&mut self, index: usize) {
-        self.content.remove(index);
+    // remove the character before the display column, return its width
+    pub fn remove_before(&mut self, column: usize) -> usize {
+        let boundaries = self.boundaries();
+        let Some(position) = boundaries.iter().position(|(_, col)| *col >= column) else {
+            return 0;
+        };
+        if position == 0 {
+            return 0;
+        }
+        let (start_index, start_column) = boundaries[position - 1];
+        let (end_index, end_column) = boundaries[position];
+        let byte_range = self.byte_index(start_index)..self.byte_index(end_index);
+        self.content.replace_range(byte_range, "");
         self.refresh();
+        end_column - start_column
     }
 
     // --- --- --- --- --- ---
@@ -100,7 +115,59 @@ impl Line {
         self.refresh();
     }
 
-    pub fn len(&self) -> usize {
-        self.content.len()
+    // the display width, wide characters take two columns
+    pub fn width(&self) -> usize {
+        str_width(&self.content)
+    }
+
+    // (character index, display column) of the character boundaries,
+    // zero width characters (like combining accents) belong to the previous one.
+    fn boundaries(&self) -> Vec<(usize, usize)> {
+        let mut boundaries = Vec::<(usize, usize)>::new();
+        let mut column = 0;
+        let mut count = 0;
+        for (index, ch) in self.content.chars().enumerate() {
+            let width = char_width(ch);
+            if width > 0 || index == 0 {
+                boundaries.push((index, column));
+            }
+            column += width;
+            count += 1;
+        }
+        boundaries.push((count, column));
+        boundaries
+    }
+
+    // character index at the display column
+    pub fn char_index(&self, column: usize) -> usize {
+        let boundaries = self.boundaries();
+        match boundaries.iter().find(|(_, col)| *col >= column) {
+            Some((index, _)) => *index,
+            None => boundaries.last().unwrap().0,
+        }
+    }
+    // the first character boundary column not less than the column
+    pub fn align_column(&self, column: usize) -> usize {
+        match self.boundaries().iter().find(|(_, col)| *col >= column) {
+            Some((_, col)) => *col,
+            None => column,
+        }
+    }
+    // width of the character before / after the display column
+    pub fn width_before(&self, column: usize) -> usize {
+        let boundaries = self.boundaries();
+        match boundaries.iter().position(|(_, col)| *col >= column) {
+            Some(position) if position > 0 => boundaries[position].1 - boundaries[position - 1].1,
+            _ => 0,
+        }
+    }
+    pub fn width_after(&self, column: usize) -> usize {
+        let boundaries = self.boundaries();
+        match boundaries.iter().position(|(_, col)| *col >= column) {
+            Some(position) if position + 1 < boundaries.len() => {
+                boundaries[position + 1].1 - boundaries[position].1
+            }
+            _ => 0,
+        }
     }
 }
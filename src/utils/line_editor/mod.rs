@@ -27,6 +27,7 @@ use candidate::Candidate;
 use analyzer::analyze;
 use tokenizer::{TextType, Token};
 
+use super::unicode::{char_width, str_width};
 use super::{cursor::Cursor, print_line, terminal::Terminal};
 
 pub struct LineEditor {
@@ -83,19 +84,30 @@ impl LineEditor {
         Terminal::clear_after_cursor()
     }
 
-    fn back_operate(&mut self) -> io::Result<()> {
-        if self.overflow_left == 0 {
-            Cursor::left(1)?;
+    // the display column of the cursor in the line content
+    fn line_column(&self) -> io::Result<usize> {
+        Ok(Cursor::pos_col()? - self.prompt.len() + self.overflow_left)
+    }
+    fn move_cursor_to_column(&self, column: usize) -> io::Result<()> {
+        Cursor::move_to_col(self.prompt.len() + column - self.overflow_left)
+    }
+    // the overflowed width when the line end is visible,
+    // the visible area always starts at a whole character.
+    fn end_overflow(&self) -> usize {
+        let line_width = self.current_line.width();
+        if line_width > self.visible_area_width {
+            let overflow = line_width - self.visible_area_width;
+            self.current_line.align_column(overflow)
+        } else {
+            0
         }
+    }
 
-        let line = &mut self.current_line;
+    fn back_operate(&mut self) -> io::Result<()> {
         if self.is_at.line_end {
-            line.pop();
-            if line.len() > self.visible_area_width {
-                self.overflow_left = line.len() - self.visible_area_width;
-            } else {
-                self.overflow_left = 0;
-            };
+            self.current_line.remove_before(self.current_line.width());
+            self.overflow_left = self.end_overflow();
+            self.move_cursor_to_column(self.current_line.width())?;
         } else {
             self.remove_edit()?;
         }
@@ -119,7 +131,7 @@ impl LineEditor {
         // virtual line left & right end
         self.is_at.line_start = self.is_at.left_end && (self.overflow_left == 0);
         self.is_at.line_end = ((cursor_pos - prompt_len)
-            == (self.current_line.len() - self.overflow_left))
+            == (self.current_line.width() - self.overflow_left))
             || (self.is_at.right_end && self.overflow_right == 0);
 
         return Ok(());
@@ -133,17 +145,14 @@ impl LineEditor {
             // temporarily push hint token
             self.current_line.tokens.push(hint_token);
 
-            let hint_width = hint_text.len();
-            let content_width = self.current_line.len() + hint_width;
+            let hint_width = str_width(hint_text);
+            let content_width = self.current_line.width() + hint_width;
 
             if content_width > self.visible_area_width {
+                let column = self.line_column()?;
                 let offset = content_width - self.visible_area_width;
-                let cursor_move = offset - self.overflow_left;
-
-                if cursor_move > 0 {
-                    Cursor::left(cursor_move)?;
-                }
-                self.overflow_left = offset;
+                self.overflow_left = self.current_line.align_column(offset);
+                self.move_cursor_to_column(column)?;
             }
 
             self.render_with_fixed_pos()?;
@@ -165,15 +174,16 @@ impl LineEditor {
     }
     fn hide_hint(&mut self) -> io::Result<()> {
         if let Some(hint_text) = self.candidate.current_hint() {
-            let hint_width = hint_text.chars().count();
+            let hint_width = str_width(hint_text);
             let overflow = self.overflow_left;
 
             if overflow > 0 {
                 // min(self.overflow_left, hint_width)
                 let offset = std::cmp::min(overflow, hint_width);
 
-                self.overflow_left -= offset;
-                Cursor::right(offset)?;
+                let column = self.line_column()?;
+                self.overflow_left = self.current_line.align_column(overflow - offset);
+                self.move_cursor_to_column(column)?;
             }
             self.candidate.clear();
             self.render_with_fixed_pos()?;
@@ -191,8 +201,9 @@ impl LineEditor {
             token: &Token,
             range: Range<usize>,
         ) {
+            let text = token.visible_text(range);
             if unsafe { ENV_OPTION.support_ansi } {
-                let mut colored = token.colored(range);
+                let mut colored = token.colored(&text);
                 // if is history, line text will be darken
                 if is_history {
                     colored = colored.dim();
@@ -200,7 +211,7 @@ impl LineEditor {
 
                 buffer.extend(colored.to_string().chars());
             } else {
-                *buffer += &token.content;
+                *buffer += &text;
             }
         }
 
@@ -216,13 +227,14 @@ impl LineEditor {
                 break;
             }
 
+            let token_width = token.width();
             if offset > 0 {
-                if offset >= token.len() {
+                if offset >= token_width {
                     // token is out of visible area
-                    offset -= token.len();
+                    offset -= token_width;
                 } else {
                     // token has part in unvisible area
-                    let actual_print_len = token.len() - offset;
+                    let actual_print_len = token_width - offset;
 
                     // when a token is going to be overflow left side and right side
                     if actual_print_len > remain_space {
@@ -236,14 +248,14 @@ impl LineEditor {
                         break;
                     }
 
-                    remain_space -= token.len() - offset;
-                    buffer_extend_colored(&mut buffer, is_history, token, offset..token.len());
+                    remain_space -= token_width - offset;
+                    buffer_extend_colored(&mut buffer, is_history, token, offset..token_width);
                     offset = 0;
                 }
             } else {
-                if remain_space >= token.len() {
-                    remain_space -= token.len();
-                    buffer_extend_colored(&mut buffer, is_history, token, 0..token.len());
+                if remain_space >= token_width {
+                    remain_space -= token_width;
+                    buffer_extend_colored(&mut buffer, is_history, token, 0..token_width);
                 } else {
                     buffer_extend_colored(&mut buffer, is_history, token, 0..remain_space);
                     remain_space = 0;
@@ -259,48 +271,54 @@ impl LineEditor {
 
     // --- --- --- --- --- ---
 
-    fn scroll_left(&mut self) {
-        if self.overflow_left > 0 {
-            self.overflow_left -= 1;
-            self.overflow_right += 1;
-        }
+    // scroll by the width of the character beside the cursor
+    fn scroll_left(&mut self) -> io::Result<()> {
+        let column = self.line_column()?;
+        let width = self.current_line.width_before(column);
+        let width = width.min(self.overflow_left);
+        self.overflow_left -= width;
+        self.overflow_right += width;
+        self.move_cursor_to_column(column - width)
     }
-    fn scroll_right(&mut self) {
-        if self.overflow_right > 0 {
-            self.overflow_right -= 1;
-            self.overflow_left += 1;
-        }
+    fn scroll_right(&mut self) -> io::Result<()> {
+        let column = self.line_column()?;
+        let width = self.current_line.width_after(column);
+        let width = width.min(self.overflow_right);
+        let overflow = self.current_line.align_column(self.overflow_left + width);
+        self.overflow_right -= width;
+        self.overflow_left = overflow;
+        self.move_cursor_to_column(column + width)
     }
 
     // --- --- --- --- --- ---
 
     fn insert_edit(&mut self, ch: char) -> io::Result<()> {
-        let insert_pos = Cursor::pos_col()? - self.prompt.len() + self.overflow_left;
+        let column = self.line_column()?;
+        let insert_pos = self.current_line.char_index(column);
         self.current_line.insert(insert_pos, ch);
 
-        if self.current_line.len() - 1 >= self.visible_area_width {
-            self.overflow_left += 1;
-        } else {
-            Cursor::right(1)?;
+        let inserted_width = char_width(ch);
+        if self.current_line.width() - inserted_width >= self.visible_area_width {
+            let overflow = self.overflow_left + inserted_width;
+            self.overflow_left = self.current_line.align_column(overflow);
         }
-        return Ok(());
+        self.move_cursor_to_column(column + inserted_width)
     }
     fn remove_edit(&mut self) -> io::Result<()> {
-        let cursor_pos = Cursor::pos_col()?;
-        if cursor_pos == 0 {
+        let column = self.line_column()?;
+        let removed_width = self.current_line.remove_before(column);
+        if removed_width == 0 {
             return Ok(());
         }
 
-        let mut remove_pos = cursor_pos - self.prompt.len() + self.overflow_left;
         if self.overflow_left > 0 {
-            remove_pos -= 1;
-            self.overflow_left -= 1;
+            // keep the cursor and scroll the content
+            let overflow = self.overflow_left.saturating_sub(removed_width);
+            self.overflow_left = self.current_line.align_column(overflow);
         } else if self.overflow_right > 0 {
-            self.overflow_right -= 1;
+            self.overflow_right = self.overflow_right.saturating_sub(removed_width);
         }
-
-        self.current_line.remove(remove_pos);
-        return Ok(());
+        self.move_cursor_to_column(column - removed_width)
     }
 
     // --- --- --- --- --- ---
@@ -310,10 +328,10 @@ impl LineEditor {
             return Ok(());
         };
 
-        let hint_width = hint_text.chars().count();
         self.current_line.push_str(hint_text);
         self.candidate.clear();
-        Cursor::right(hint_width)?;
+        self.overflow_left = self.end_overflow();
+        self.move_cursor_to_column(self.current_line.width())?;
         self.render_with_fixed_pos()
     }
 
@@ -355,9 +373,10 @@ impl LineEditor {
 
                 KeyCode::Tab => {
                     if let Some(new_content) = self.history.get_current() {
-                        if new_content.len() > self.visible_area_width {
+                        let content_width = str_width(&new_content);
+                        if content_width > self.visible_area_width {
                             self.overflow_left = 0;
-                            self.overflow_right = new_content.len() - self.visible_area_width;
+                            self.overflow_right = content_width - self.visible_area_width;
                         }
                         self.history.reset_index();
                         self.current_line.reset_with(new_content);
@@ -379,10 +398,11 @@ impl LineEditor {
                         }
 
                         if self.is_at.left_end {
-                            self.scroll_left();
+                            self.scroll_left()?;
                         } else {
                             self.hide_hint()?;
-                            Cursor::left(1)?;
+                            let column = self.line_column()?;
+                            Cursor::left(self.current_line.width_before(column))?;
                             continue; // skip rerender
                         }
                     }
@@ -393,9 +413,10 @@ impl LineEditor {
                         }
 
                         if self.is_at.right_end {
-                            self.scroll_right();
+                            self.scroll_right()?;
                         } else {
-                            Cursor::right(1)?;
+                            let column = self.line_column()?;
+                            Cursor::right(self.current_line.width_after(column))?;
                             continue; // skip rerender
                         }
                     }
@@ -427,26 +448,14 @@ impl LineEditor {
                     }
 
                     KeyCode::Char(ch) => {
-                        if !ch.is_ascii() {
-                            // avoid Non-ASCII character
-                            print_line("");
-                            break Signal::NonASCII;
-                        }
-
                         if self.is_at.line_end {
+                            // the cursor is kept before the paired character
+                            let column = self.current_line.width() + char_width(ch);
                             self.current_line.push(ch);
 
                             self.hide_hint()?;
-                            self.refresh()?;
-                            if !self.is_at.right_end {
-                                Cursor::right(1)?;
-                            }
-                            if self.current_line.len() > self.visible_area_width {
-                                self.overflow_left =
-                                    self.current_line.len() - self.visible_area_width;
-                            } else {
-                                self.overflow_left = 0;
-                            };
+                            self.overflow_left = self.end_overflow();
+                            self.move_cursor_to_column(column)?;
                         } else {
                             self.insert_edit(ch)?;
                         }
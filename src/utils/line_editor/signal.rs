@@ -2,5 +2,4 @@
 pub enum Signal {
     NewLine(String),
     Interrupt,
-    NonASCII,
 }
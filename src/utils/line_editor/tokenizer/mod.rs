@@ -1,17 +1,54 @@
 mod token;
 
-use crate::{public::compile_time::keywords::Keyword, utils::ascii::is_identi_ascii};
+use std::str::Chars;
+
+use crate::public::compile_time::keywords::Keyword;
+use crate::public::value::symbols::Symbols;
+use crate::utils::unicode::{is_identi_char, is_identi_continue};
 
 pub use token::{TextType, Token, TokenType, TokenVec};
 
+// take string literal from `chars` and push it into `value`,
+// the opening quote is already in `value`.
+fn string_resolver(chars: &mut Chars, quote: char, is_raw: bool, value: &mut String) {
+    let mut rest = chars.clone();
+    let is_triple = rest.next() == Some(quote) && rest.next() == Some(quote);
+    if is_triple {
+        chars.nth(1);
+        value.extend([quote, quote]);
+    }
+
+    let mut is_escape_char = false;
+    while let Some(ch) = chars.next() {
+        value.push(ch);
+
+        // switch escape character state
+        if is_escape_char {
+            is_escape_char = false;
+            continue;
+        } else if ch == '\\' && !is_raw {
+            is_escape_char = true;
+            continue;
+        }
+
+        if ch == quote {
+            if !is_triple {
+                break;
+            }
+            let mut rest = chars.clone();
+            if rest.next() == Some(quote) && rest.next() == Some(quote) {
+                chars.nth(1);
+                value.extend([quote, quote]);
+                break;
+            }
+        }
+    }
+}
+
 pub fn tokenize(source: &str) -> TokenVec {
-    // is used for check is number minus OR
-    // check whether is in annotation state.
+    // is used for check whether is in annotation state.
     let mut last_type = TokenType::Unknown;
 
-    // use to control if number is minus
-    let mut is_num_minus = false;
-
     let mut tokens = TokenVec::new();
     let mut comment = String::new();
 
@@ -37,13 +74,16 @@ pub fn tokenize(source: &str) -> TokenVec {
             last_type = TokenType::Number;
 
             let mut value = String::from(ch);
-            if is_num_minus {
-                is_num_minus = false;
-                value.insert(0, '-');
-            }
 
+            // number literals like:
+            // `1_000` | `1.5e-3` | `0xFF` | `0b1010` | `0o755`
             while let Some(ch) = chars.next() {
-                if ch.is_ascii_digit() || ch == '.' {
+                let is_exponent_sign = (ch == '+' || ch == '-')
+                    && !value.starts_with("0x")
+                    && !value.starts_with("0X")
+                    && value.ends_with(['e', 'E']);
+
+                if ch.is_ascii_alphanumeric() || ch == '_' || ch == '.' || is_exponent_sign {
                     value.push(ch);
                 } else {
                     cached_ch = ch;
@@ -57,11 +97,11 @@ pub fn tokenize(source: &str) -> TokenVec {
         }
 
         // Identifier
-        if is_identi_ascii(ch) {
+        if is_identi_char(ch) {
             let mut value = String::from(ch);
 
             while let Some(ch) = chars.next() {
-                if is_identi_ascii(ch) || ch.is_ascii_digit() {
+                if is_identi_continue(ch) {
                     value.push(ch)
                 } else {
                     cached_ch = ch;
@@ -69,12 +109,28 @@ pub fn tokenize(source: &str) -> TokenVec {
                 }
             }
 
+            // raw and interpolated string literal: `r"..."` | `f"..."`
+            if (value == "r" || value == "f") && matches!(cached_ch, '\'' | '\"') {
+                let quote = cached_ch;
+                let is_raw = value == "r";
+                cached_ch = '\0';
+                value.push(quote);
+                string_resolver(&mut chars, quote, is_raw, &mut value);
+
+                last_type = TokenType::String;
+                tokens.push(Token::new(TextType::StringLiteral, value));
+                continue;
+            }
+
             if last_type == TokenType::Annotation {
                 // Type annotation
                 tokens.push(Token::new(TextType::Annotation, value));
             } else {
                 let option_keyword = Keyword::is_keyword(&value);
-                let is_keyword = option_keyword.is_some() || value.eq("true") || value.eq("false");
+                let is_keyword = option_keyword.is_some()
+                    || Symbols::from_word(&value).is_some()
+                    || value.eq("true")
+                    || value.eq("false");
 
                 if is_keyword {
                     last_type = TokenType::Keyword;
@@ -96,7 +152,7 @@ pub fn tokenize(source: &str) -> TokenVec {
                 tokens.push(Token::new(TextType::Paren, String::from(ch)));
             }
             // Computing symbols
-            '+' | '-' | '*' | '/' | '%' | '^' | '!' | '<' | '>' | '=' | '.' | '&' | '|' => {
+            '+' | '-' | '*' | '/' | '%' | '^' | '!' | '~' | '<' | '>' | '=' | '.' | '&' | '|' => {
                 last_type = TokenType::Symbol;
                 tokens.push(Token::new(TextType::Symbol, String::from(ch)));
             }
@@ -104,23 +160,7 @@ pub fn tokenize(source: &str) -> TokenVec {
             '\'' | '\"' => {
                 // String token resolve
                 let mut value = String::from(ch);
-                let mut is_escape_char = false;
-
-                while let Some(ch) = chars.next() {
-                    if !is_escape_char && (ch == '\'' || ch == '\"') {
-                        value.push(ch);
-                        break;
-                    }
-
-                    // switch escape character state
-                    if is_escape_char {
-                        is_escape_char = false;
-                    } else if ch == '\\' {
-                        is_escape_char = true;
-                    }
-
-                    value.push(ch);
-                }
+                string_resolver(&mut chars, ch, false, &mut value);
 
                 last_type = TokenType::String;
                 let current_token = Token::new(TextType::StringLiteral, value);
@@ -2,6 +2,8 @@ use std::ops::Range;
 
 use crossterm::style::{StyledContent, Stylize};
 
+use crate::utils::unicode::{char_width, str_width};
+
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum TokenType {
     Unknown,
@@ -50,13 +52,34 @@ impl Token {
     pub fn new(type__: TextType, content: String) -> Self {
         Self { type__, content }
     }
-    pub fn len(&self) -> usize {
-        self.content.chars().count()
+    // the display width, wide characters take two columns
+    pub fn width(&self) -> usize {
+        str_width(&self.content)
     }
 
-    pub fn colored(&self, range: Range<usize>) -> StyledContent<&str> {
-        let text = &self.content[range];
+    // the text inside the display column range,
+    // the wide character which is cut by the range is replaced with spaces.
+    pub fn visible_text(&self, range: Range<usize>) -> String {
+        let mut text = String::new();
+        let mut column = 0;
+        for ch in self.content.chars() {
+            let ch_range = column..column + char_width(ch);
+            column = ch_range.end;
+
+            if ch_range.end <= range.start || ch_range.start >= range.end {
+                continue;
+            }
+            if ch_range.start >= range.start && ch_range.end <= range.end {
+                text.push(ch);
+            } else {
+                let cut_width = ch_range.end.min(range.end) - ch_range.start.max(range.start);
+                text.push_str(&" ".repeat(cut_width));
+            }
+        }
+        text
+    }
 
+    pub fn colored<'a>(&self, text: &'a str) -> StyledContent<&'a str> {
         match self.type__ {
             TextType::Unknown => text.white().on_dark_red(),
 
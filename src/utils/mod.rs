@@ -1,9 +1,9 @@
 mod cursor;
 mod terminal;
 
-pub mod ascii;
 pub mod completer;
 pub mod line_editor;
+pub mod unicode;
 
 use std::{
     fmt::Display,
@@ -0,0 +1,60 @@
+// combining marks, like: combining accents | variation selectors
+fn is_combining_mark(ch: char) -> bool {
+    matches!(ch as u32,
+        0x0300..=0x036F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F
+    )
+}
+
+// zero width characters, like: combining marks | zero width space | zero width joiner
+fn is_zero_width(ch: char) -> bool {
+    is_combining_mark(ch) || matches!(ch as u32, 0x200B..=0x200F)
+}
+
+// East Asian wide characters, like: CJK | Hangul | fullwidth forms | emoji
+fn is_wide(ch: char) -> bool {
+    matches!(ch as u32,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD
+    )
+}
+
+// [a-z A-Z _] and Unicode letters, like: `标签` | `größe`
+pub fn is_identi_char(ch: char) -> bool {
+    ch == '_' || ch.is_alphabetic() || is_combining_mark(ch)
+}
+// the characters after the first one of an identifier,
+// zero width non-joiner and joiner are kept inside a word only (UAX #31), like: `می‌خواهم`
+pub fn is_identi_continue(ch: char) -> bool {
+    is_identi_char(ch) || ch.is_ascii_digit() || ch == '\u{200C}' || ch == '\u{200D}'
+}
+
+// count of terminal columns the character takes
+pub fn char_width(ch: char) -> usize {
+    if ch.is_control() || is_zero_width(ch) {
+        0
+    } else if is_wide(ch) {
+        2
+    } else {
+        1
+    }
+}
+pub fn str_width(str: &str) -> usize {
+    str.chars().map(char_width).sum()
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::{run, run_error};
+
+const IMPORTS: &str = "import Basic\nimport Array\n";
+
+#[test]
+fn default_sort() {
+    let source = format!(
+        "{}{}",
+        IMPORTS,
+        "a = new Array([3, fraction(1, 2), 2.5, -1])
+a.sort()
+out string(a.v)
+b = new Array([\"b\", \"a\", \"c\"])
+b.sort()
+out string(b.v)
+",
+    );
+    assert_eq!(
+        run("array_default_sort", &source),
+        ["-1, (1 / 2), 2.5000000000, 3", "a, b, c"]
+    );
+}
+
+#[test]
+fn sort_errors() {
+    let cases = [
+        (
+            "[1, \"a\"]",
+            "TypeError  for \"sorting\": expected Number, found String.",
+        ),
+        (
+            "[\"a\", 2]",
+            "TypeError  for \"sorting\": expected String, found Number.",
+        ),
+        (
+            "[true, false]",
+            "TypeError  for \"sorting\": expected Number/String, found Boolean.",
+        ),
+        ("[1i, 2]", "MathError : can not compare 1i with 2."),
+    ];
+    for (index, (elements, expected)) in cases.iter().enumerate() {
+        let source = format!("{}a = new Array({})\na.sort()\n", IMPORTS, elements);
+        let message = run_error(&format!("array_sort_error_{}", index), &source);
+        assert_eq!(message, *expected, "{}", elements);
+    }
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::{assert_module_outputs, run};
+
+const MODULES: &[&str] = &["Math"];
+
+#[test]
+fn calculus_results() {
+    assert_module_outputs(
+        "calculus",
+        MODULES,
+        &[
+            ("Math.diff({x => x ^ 3}, 2)", "12.0000000000"),
+            (
+                "Math.integrate({x => Math.sin(x)}, 0, 3.141592653589793)",
+                "2.0000000000",
+            ),
+            ("Math.integrate({x => Math.sqrt(x)}, 0, 1)", "0.6666666667"),
+            ("Math.root({x => x ^ 2 - 2}, 0, 2)", "1.4142135624"),
+            ("Math.minimize({x => (x - 1) ^ 2}, -3, 3)", "1.0000000000"),
+            ("Math.sum({x => x}, 1, 10)", "55"),
+            ("Math.sum({x => x ^ 2}, -2, 2)", "10"),
+        ],
+    );
+}
+
+#[test]
+fn divergent_integrals() {
+    // the sample is not a number, or the integral does not converge,
+    // the error stops the script.
+    let cases = [
+        ("{x => 1 / x}, 0, 1", "the function is not a number at 0."),
+        (
+            "{x => 1 / (x - 0.3)}, 0, 1",
+            "the integral does not converge.",
+        ),
+    ];
+    for (index, (args, expected)) in cases.iter().enumerate() {
+        let source = format!("import Math\nout Math.integrate({})\nout 1\n", args);
+        let outputs = run(&format!("integrate_error_{}", index), &source);
+        assert!(
+            outputs.iter().any(|line| line.ends_with(expected)),
+            "`{}`: {:?}",
+            args,
+            outputs
+        );
+        assert!(!outputs.contains(&String::from("1")), "{:?}", outputs);
+    }
+}
@@ -0,0 +1,70 @@
+// helpers shared by the script tests
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+// run the source as a script file and collect the output lines
+pub fn run(name: &str, source: &str) -> Vec<String> {
+    let path = env::temp_dir().join(format!("swamp_{}_{}.swamp", name, std::process::id()));
+    fs::write(&path, source).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_SwampReconstructed"))
+        .arg(&path)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    stdout
+        .lines()
+        .map(|line| line.trim_end_matches('\r').to_string())
+        .collect()
+}
+
+pub fn assert_outputs(name: &str, cases: &[(&str, &str)]) {
+    assert_module_outputs(name, &[], cases);
+}
+
+// the std modules are imported before the cases
+pub fn assert_module_outputs(name: &str, modules: &[&str], cases: &[(&str, &str)]) {
+    let imports: String = modules
+        .iter()
+        .map(|module| format!("import {}\n", module))
+        .collect();
+    let source: String = cases
+        .iter()
+        .map(|(expr, _)| format!("out {}\n", expr))
+        .collect();
+    let outputs = run(name, &(imports + &source));
+
+    for (index, (expr, expected)) in cases.iter().enumerate() {
+        assert_eq!(
+            outputs.get(index).map(String::as_str),
+            Some(*expected),
+            "`{}`",
+            expr
+        );
+    }
+}
+
+// the first error message, without ANSI codes
+pub fn run_error(name: &str, source: &str) -> String {
+    let outputs = run(name, source);
+    let Some(line) = outputs.iter().find(|line| line.contains("Error")) else {
+        panic!("no error in the outputs: {:?}", outputs);
+    };
+
+    let mut message = String::new();
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            // skip until the end of the escape sequence
+            chars.by_ref().find(|ch| *ch == 'm');
+            continue;
+        }
+        message.push(ch);
+    }
+    message.trim().to_string()
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::{assert_module_outputs, assert_outputs, run_error};
+
+#[test]
+fn arithmetic() {
+    assert_outputs(
+        "complex_arithmetic",
+        &[
+            ("(1 + 2i) + (3 - 1i)", "4 + 1i"),
+            ("(1 + 2i) * (3 - 1i)", "5 + 5i"),
+            ("(3 + 4i) / (1 - 2i)", "-1 + 2i"),
+            ("(1 + 1i) / 1i", "1 - 1i"),
+            ("1i ^ 2", "-1"),
+            ("1i * 1i == -1", "true"),
+            ("(1 + 2i) != (1 + 2i)", "false"),
+        ],
+    );
+}
+
+#[test]
+fn display() {
+    // `i` alone is a name, so the imaginary unit is written as `1i`
+    assert_outputs(
+        "complex_display",
+        &[
+            ("3 + 4i", "3 + 4i"),
+            ("1i", "1i"),
+            ("-1i", "-1i"),
+            ("2.5i", "2.5000000000i"),
+            ("3 - 4i", "3 - 4i"),
+            ("(1 + 1i) - 1i", "1"),
+        ],
+    );
+}
+
+#[test]
+fn branch_cuts() {
+    assert_module_outputs(
+        "complex_branch_cuts",
+        &["Math"],
+        &[
+            ("Math.sqrt(-4)", "2i"),
+            ("Math.sqrt(0 - 4i)", "1.4142135624 - 1.4142135624i"),
+            ("Math.ln(-1)", "3.1415926536i"),
+            ("Math.arg(-1)", "3.1415926536"),
+            ("Math.abs(3 + 4i)", "5"),
+            ("Math.conj(3 + 4i)", "3 - 4i"),
+        ],
+    );
+}
+
+#[test]
+fn unordered() {
+    for (index, source) in [
+        "out 1i < 2i",
+        "out 1i > 2i",
+        "out 1 <= 1i",
+        "out 0 < 1 < 1i",
+    ]
+    .iter()
+    .enumerate()
+    {
+        let message = run_error(&format!("complex_unordered_{}", index), source);
+        assert_eq!(
+            message, "MathError : complex numbers are unordered.",
+            "`{}`",
+            source
+        );
+    }
+}
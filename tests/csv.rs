@@ -0,0 +1,96 @@
+mod common;
+
+use common::{assert_module_outputs, run, run_error};
+
+const MODULES: &[&str] = &["Basic", "CSV", "JSON"];
+
+#[test]
+fn parse_field_numbers() {
+    assert_module_outputs(
+        "csv_numbers",
+        MODULES,
+        &[
+            ("JSON.stringify(CSV.parse(\"1,-2,+3,-0\"))", "[[1,-2,3,0]]"),
+            (
+                "JSON.stringify(CSV.parse(\"0.5,-0.5,1.5e3\"))",
+                "[[0.5,-0.5,1500.0]]",
+            ),
+            (
+                "JSON.stringify(CSV.parse(\"12345678901234567890,-12345678901234567890\"))",
+                "[[12345678901234567890,-12345678901234567890]]",
+            ),
+            // leading zeros are kept as text
+            (
+                "JSON.stringify(CSV.parse(\"02134,007.5,00,0\"))",
+                "[[\"02134\",\"007.5\",\"00\",0]]",
+            ),
+            (
+                "JSON.stringify(CSV.parse(\"-,1e,inf\"))",
+                "[[\"-\",\"1e\",\"inf\"]]",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn parse_records() {
+    assert_module_outputs(
+        "csv_records",
+        MODULES,
+        &[
+            // blank lines are skipped, CRLF is a line break
+            (
+                r#"JSON.stringify(CSV.parse("a,b\n1,2\r\n\n3,4\n"))"#,
+                r#"[["a","b"],[1,2],[3,4]]"#,
+            ),
+            // quoted fields contain delimiters, escaped quotes and newlines
+            (
+                r#"JSON.stringify(CSV.parse("\"x,y\",\"say \"\"hi\"\"\"\n\"1\n2\",\"3\""))"#,
+                r#"[["x,y","say \"hi\""],["1\n2","3"]]"#,
+            ),
+            (
+                r#"JSON.stringify(CSV.parse("name,zip\nann,02134\nbob,10001", true))"#,
+                r#"[{"name":"ann","zip":"02134"},{"name":"bob","zip":10001}]"#,
+            ),
+            (
+                r#"JSON.stringify(CSV.parse("a;b\n1;2", false, ";"))"#,
+                r#"[["a","b"],[1,2]]"#,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn stringify_rows() {
+    let outputs = run(
+        "csv_stringify",
+        r#"import CSV
+out CSV.stringify([["a,b", "say \"hi\"", 1], [2.5, true]])
+"#,
+    );
+    // each row ends with a line break, so the output ends with an empty line
+    assert_eq!(outputs, [r#""a,b","say ""hi""",1"#, "2.5,true", ""]);
+}
+
+#[test]
+fn parse_errors() {
+    let cases = [
+        (
+            r#"CSV.parse("\"a\"b,c")"#,
+            "invalid CSV at line 1: unexpected character after closing quote.",
+        ),
+        (
+            r#"CSV.parse("a\n\"b,c")"#,
+            "invalid CSV at line 2: unclosed quoted field.",
+        ),
+        (
+            r#"CSV.parse("a", false, ";;")"#,
+            "expected 1 character except quote and newline, found 2.",
+        ),
+    ];
+    for (index, (code, expected)) in cases.iter().enumerate() {
+        let source = format!("import CSV\n{}\n", code);
+        let message = run_error(&format!("csv_error_{}", index), &source);
+        assert!(message.ends_with(expected), "`{}`: {}", code, message);
+    }
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::{assert_module_outputs, run_error};
+
+const MODULES: &[&str] = &["Basic", "JSON"];
+
+#[test]
+fn parse_numbers() {
+    assert_module_outputs(
+        "json_numbers",
+        MODULES,
+        &[
+            ("JSON.parse(\"-5\")", "-5"),
+            ("JSON.parse(\"-0\")", "0"),
+            ("string(JSON.parse(\"[1, -2]\"))", "1, -2"),
+            (
+                "JSON.parse(\"123456789012345678901\")",
+                "123456789012345678901",
+            ),
+            (
+                "JSON.parse(\"-123456789012345678901\")",
+                "-123456789012345678901",
+            ),
+            ("JSON.parse(\"1.5\") == 1.5", "true"),
+            ("JSON.parse(\"2e3\") == 2000", "true"),
+            ("JSON.parse(\"-2.5E-1\") == -0.25", "true"),
+        ],
+    );
+}
+
+#[test]
+fn parse_strings() {
+    assert_module_outputs(
+        "json_strings",
+        MODULES,
+        &[
+            (r#"JSON.parse("\"a\\nb\"") == "a\nb""#, "true"),
+            (r#"JSON.parse("\"\\u0041\"")"#, "A"),
+            (r#"JSON.parse("\"\\ud83d\\ude00\"")"#, "😀"),
+        ],
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let cases = [
+        ("-", "invalid JSON at index 1: invalid number."),
+        (
+            "01",
+            "invalid JSON at index 1: unexpected trailing characters.",
+        ),
+        ("1.", "invalid JSON at index 2: invalid number."),
+        ("1e+", "invalid JSON at index 3: invalid number."),
+        (r#"\"\\ud83d\""#, "unpaired surrogate."),
+        (r#"\"\\ude00\""#, "unpaired surrogate."),
+        ("[1, 2", "expected ',' or ']'."),
+    ];
+    for (index, (text, expected)) in cases.iter().enumerate() {
+        let source = format!("import JSON\nout JSON.parse(\"{}\")\n", text);
+        let message = run_error(&format!("json_error_{}", index), &source);
+        assert!(message.ends_with(expected), "`{}`: {}", text, message);
+    }
+}
+
+#[test]
+fn stringify_round_trip() {
+    assert_module_outputs(
+        "json_stringify",
+        MODULES,
+        &[
+            (
+                "JSON.stringify(JSON.parse(\"[-1, 2.5, true, null]\"))",
+                "[-1,2.5,true,null]",
+            ),
+            ("JSON.stringify(\"a\\\"b\")", "\"a\\\"b\""),
+        ],
+    );
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use common::{run, run_error};
+
+#[test]
+fn positional_and_named_arguments() {
+    let source = "import JSON
+f = {x, y => x - y}
+out f(5, 3)
+out f({x: 5; y: 3})
+out f({y: 3; x: 5})
+a = 10
+sum = {a + b}
+out sum({a: 1; b: 2})
+# a single param keeps the Map as its value
+g = {m => m}
+out JSON.stringify(g({x: 1}))
+";
+    assert_eq!(
+        run("lazy_arguments", source),
+        ["2", "2", "2", "3", "{\"x\":1}"]
+    );
+}
+
+#[test]
+fn named_argument_errors() {
+    let message = run_error(
+        "lazy_missing_binding",
+        "f = {x, y => x + y}\nout f({x: 2})\n",
+    );
+    assert_eq!(message, "ReferenceError : property `y` is not defined.");
+    let message = run_error(
+        "lazy_argument_count",
+        "f = {x, y => x + y}\nout f(1, 2, 3)\n",
+    );
+    assert_eq!(
+        message,
+        "RangeError  for \"lazy-expression invocation\": expected 2, found 3."
+    );
+}
@@ -0,0 +1,170 @@
+mod common;
+
+use common::{assert_outputs, run, run_error};
+
+#[test]
+fn arithmetic_precedence() {
+    assert_outputs(
+        "arithmetic",
+        &[
+            ("1 + 2 * 3", "7"),
+            ("(1 + 2) * 3", "9"),
+            ("10 - 2 - 3", "5"),
+            ("12 / 2 / 3", "2"),
+            ("2 ^ 2 * 3", "12"),
+            ("7 % 4 + 1", "4"),
+            ("7 // 2", "3"),
+            ("(0 - 7) // 2", "-4"),
+        ],
+    );
+}
+
+#[test]
+fn power_is_right_associative() {
+    assert_outputs(
+        "power",
+        &[
+            ("2 ^ 3 ^ 2", "512"),
+            ("(2 ^ 3) ^ 2", "64"),
+            ("2 ^ 2 ^ 0", "2"),
+        ],
+    );
+}
+
+#[test]
+fn unary_operators() {
+    assert_outputs(
+        "unary",
+        &[
+            ("-7", "-7"),
+            ("-2 ^ 2", "-4"),
+            ("(-2) ^ 2", "4"),
+            ("2 ^ -1 * 4", "2"),
+            ("5 - -3", "8"),
+            ("2 * -3", "-6"),
+            ("+5", "5"),
+            ("- -5", "5"),
+            ("-(1 + 2)", "-3"),
+            ("!0", "true"),
+            ("not 1", "false"),
+            ("~5", "-6"),
+            ("~-1", "0"),
+        ],
+    );
+
+    let outputs = run(
+        "unary_variable",
+        "x = 3\nout -x\nout -x * 2 + 1\nout -(x + 1)\n",
+    );
+    assert_eq!(outputs, vec!["-3", "-5", "-4"]);
+}
+
+#[test]
+fn bitwise_operators() {
+    assert_outputs(
+        "bitwise",
+        &[
+            ("6 band 3", "2"),
+            ("6 bor 1", "7"),
+            ("6 bxor 3", "5"),
+            ("1 << 4", "16"),
+            ("256 >> 2", "64"),
+            ("1 + 6 band 3", "3"),
+            ("1 bor 2 band 3", "3"),
+            ("(2 ^ 70 + 5) band 7", "5"),
+            ("(2 ^ 70) bor 1", "1180591620717411303425"),
+            ("(0 - 2 ^ 70) band -1", "-1180591620717411303424"),
+            ("(0 - 2 ^ 64) bxor (2 ^ 64 - 1)", "-1"),
+            ("~(2 ^ 70)", "-1180591620717411303425"),
+        ],
+    );
+}
+
+#[test]
+fn bitwise_words_as_names() {
+    // `band`, `bor` and `bxor` are operators only after an operand
+    let outputs = run(
+        "bitwise_names",
+        "band = 6\nbor = band bor 1\nout bor\nout band bxor 3\n",
+    );
+    assert_eq!(outputs, vec!["7", "5"]);
+}
+
+#[test]
+fn doubled_symbols_are_adjacent() {
+    // `/ /`, `< <` and `> >` are two separate symbols
+    for (index, source) in ["out 8 / / 2", "out 1 < < 2", "out 8 > > 2"]
+        .iter()
+        .enumerate()
+    {
+        let message = run_error(&format!("separated_{}", index), source);
+        assert!(
+            message.starts_with("SyntaxError"),
+            "`{}`: {}",
+            source,
+            message
+        );
+    }
+}
+
+#[test]
+fn logical_operators() {
+    assert_outputs(
+        "logical",
+        &[
+            ("1 < 2 & 2 < 3", "true"),
+            ("1 > 2 | 2 < 3", "true"),
+            ("true and false", "false"),
+            ("false or true", "true"),
+            ("0 != 0 & 10 / 0 > 1", "false"),
+        ],
+    );
+}
+
+#[test]
+fn chained_comparisons() {
+    assert_outputs(
+        "chained",
+        &[
+            ("0 < 3 <= 10", "true"),
+            ("0 < 30 <= 10", "false"),
+            ("1 < 2 < 3 < 4", "true"),
+            ("1 < 2 > 3", "false"),
+            ("3 > 2 > 1", "true"),
+            ("1 == 1 != 2", "true"),
+        ],
+    );
+
+    // the right-hand part is skipped once the chain is false
+    let source = "count = 0
+f = fn() { glo count = count + 1; brk 5 }
+out 3 < 2 < f()
+out count
+out 1 < 2 < f()
+out count
+";
+    assert_eq!(run("chained_skip", source), vec!["false", "0", "true", "1"]);
+}
+
+#[test]
+fn compound_assignment() {
+    let source = "a = 7
+a %= 3
+out a
+a -= -2
+out a
+a ^= 3
+out a
+";
+    assert_eq!(run("compound", source), vec!["1", "3", "27"]);
+}
+
+#[test]
+fn conversion_words_as_names() {
+    // `to` and `in` convert only when a unit follows
+    let outputs = run(
+        "conversion_names",
+        "import Math\nfrom = 1\nto = 4\nout Math.sum({x => x}, from, to)\nin = 2\nout in * to\nx = 3 km to m\nout x\nout x in km\n",
+    );
+    assert_eq!(outputs, vec!["10", "8", "3000 m", "3 km"]);
+}
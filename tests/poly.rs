@@ -0,0 +1,32 @@
+mod common;
+
+use common::{run, run_error};
+
+#[test]
+fn power() {
+    let source = "import Poly
+p = new Poly([1, 1])
+out p ^ 3
+out p ^ 0
+z = new Poly([0])
+out z ^ 0
+out z ^ 5
+c = new Poly([2])
+out c ^ 10
+";
+    assert_eq!(
+        run("poly_power", source),
+        ["x^3 + 3x^2 + 3x + 1", "1", "1", "0", "1024"]
+    );
+}
+
+#[test]
+fn power_degree_limit() {
+    let source = "import Poly\nout new Poly([1, 0]) ^ 1000000000\n";
+    let message = run_error("poly_power_limit", source);
+    assert!(
+        message.ends_with("expected at most 1000, found 1000000000."),
+        "{}",
+        message
+    );
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::{run, run_error};
+
+const IMPORTS: &str = "import Basic\nimport Regex\nimport JSON\n";
+
+// run the lines after the imports
+fn run_lines(name: &str, lines: &str) -> Vec<String> {
+    run(name, &format!("{}{}", IMPORTS, lines))
+}
+
+#[test]
+fn matching() {
+    let outputs = run_lines(
+        "regex_matching",
+        r#"r = new Regex("(\\w+)@(?<host>\\w+)\\.com")
+out r.is_match("mail: ab@cd.com")
+out r.is_match("mail: ab@cd.org")
+out JSON.stringify(r.find("mail: ab@cd.com"))
+out string(r.captures("x ab@cd.com"))
+out JSON.stringify(r.named_captures("x ab@cd.com"))
+d = new Regex("(\\d)")
+out string(d.find_all("a1b22c"))
+out d.replace("a1b2", "<$1>")
+out d.replace("a1b2", "<$1>", 1)
+c = new Regex(",\\s*")
+out string(c.split("a, b,c"))
+i = new Regex("(?i)^abc$")
+out i.is_match("ABC")
+"#,
+    );
+    assert_eq!(
+        outputs,
+        [
+            "true",
+            "false",
+            r#"{"end":15,"start":6,"text":"ab@cd.com"}"#,
+            "ab@cd.com, ab, cd",
+            r#"{"host":"cd"}"#,
+            "1, 2, 2",
+            "a<1>b<2>",
+            "a<1>b2",
+            "a, b, c",
+            "true",
+        ]
+    );
+}
+
+#[test]
+fn back_references() {
+    let outputs = run_lines(
+        "regex_back_references",
+        r#"b = new Regex("^(a+)b\\1$")
+out b.is_match("aabaa")
+out b.is_match("aaba")
+n = new Regex("(?<q>[\"'])x\\k<q>")
+out n.is_match("'x'")
+out n.is_match("'x\"")
+"#,
+    );
+    assert_eq!(outputs, ["true", "false", "true", "false"]);
+}
+
+#[test]
+fn step_limit() {
+    let source = format!(
+        "{}{}",
+        IMPORTS,
+        r#"s = new Regex("(a*)*\\1b")
+out s.is_match("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
+"#,
+    );
+    let message = run_error("regex_step_limit", &source);
+    assert!(message.contains("regex backtracking steps"), "{}", message);
+}
+
+#[test]
+fn pattern_errors() {
+    // the pattern is checked when the Regex is created
+    let cases = [
+        (r#"(a"#, "at index 2: unclosed group."),
+        (r#"a)"#, "at index 1: unmatched ')'."),
+        (r#"[a"#, "at index 2: unclosed character class."),
+        (r#"*a"#, "at index 0: nothing to repeat."),
+        (
+            r#"(a)\\2"#,
+            "at index 5: back-reference to undefined group.",
+        ),
+        // nested repetitions multiply the program size
+        (r#"((a{999}){999}){999}"#, "at index 20: pattern too large."),
+    ];
+    for (index, (pattern, expected)) in cases.iter().enumerate() {
+        let source = format!("{}r = new Regex(\"{}\")\nout 1\n", IMPORTS, pattern);
+        let message = run_error(&format!("regex_error_{}", index), &source);
+        assert!(message.ends_with(expected), "`{}`: {}", pattern, message);
+    }
+}
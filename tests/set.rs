@@ -0,0 +1,73 @@
+mod common;
+
+use common::run;
+
+const IMPORTS: &str = "import Basic\nimport Set\nimport JSON\n";
+
+#[test]
+fn membership_by_exact_value() {
+    // Set elements are the same only if their exact values are equal
+    let source = format!(
+        "{}{}",
+        IMPORTS,
+        "s = new Set([0.3, 1, 0.5, decimal(\"0.1\")])
+out s.has(1.0)
+out s.has(fraction(2, 2))
+out s.has(fraction(1, 2))
+out s.has(fraction(1, 10))
+out s.has(0.1)
+u = new Set([1, 1.0, fraction(2, 2), 0.3, 0.1 + 0.2])
+out u.len()
+",
+    );
+    let outputs = run("set_membership", &source);
+    assert_eq!(outputs, ["true", "true", "true", "true", "false", "3"]);
+}
+
+#[test]
+fn membership_near_tolerance() {
+    // `==` compares Floats with a tolerance, while Set membership does not
+    let source = format!(
+        "{}{}",
+        IMPORTS,
+        "s = new Set([0.3])
+y = 0.3 + 0.0000000000000004
+out y == 0.3
+out s.has(y)
+z = 0.3 + 0.0000000000000001
+out z == 0.3
+out s.has(z)
+out s.has(0.1 + 0.2)
+out s.has(0.3)
+",
+    );
+    let outputs = run("set_near_tolerance", &source);
+    assert_eq!(
+        outputs,
+        ["false", "false", "true", "false", "false", "true"]
+    );
+}
+
+#[test]
+fn not_a_number_element() {
+    // the division warns before the NaN is inserted
+    let source = format!("{}s = new Set([])\nn = 0 / 0\ns.insert(n)\n", IMPORTS);
+    let outputs = run("set_nan", &source);
+    assert!(
+        outputs
+            .iter()
+            .any(|line| line.contains("Not a Number can not be a Set element.")),
+        "{:?}",
+        outputs
+    );
+}
+
+#[test]
+fn stringify_as_array() {
+    let source = format!(
+        "{}{}",
+        IMPORTS, "s = new Set([1, \"a\", true])\nout JSON.stringify(s.v)\n",
+    );
+    let outputs = run("set_stringify", &source);
+    assert_eq!(outputs, ["[1,\"a\",true]"]);
+}
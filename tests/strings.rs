@@ -0,0 +1,21 @@
+mod common;
+
+use common::{run, run_error};
+
+#[test]
+fn multi_line_string() {
+    let source = "s = \"\"\"a\nb\"\"\"\nout s\n";
+    assert_eq!(run("multi_line_string", source), ["a", "b"]);
+}
+
+#[test]
+fn unclosed_multi_line_string() {
+    let source = "out 1\ns = \"\"\"a\nb\n\nout 2\n";
+    let message = run_error("unclosed_multi_line_string", source);
+    assert_eq!(message, "SyntaxError : unclosed multi-line string literal.");
+    let outputs = run("unclosed_multi_line_string_line", source);
+    assert_eq!(
+        outputs.last().map(String::as_str),
+        Some("Error occured at line 2.")
+    );
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use common::{assert_module_outputs, run_error};
+
+const MODULES: &[&str] = &["Time"];
+
+#[test]
+fn leap_day() {
+    assert_module_outputs(
+        "time_leap_day",
+        MODULES,
+        &[
+            (
+                "Time.parse(\"2024-02-29\", \"%Y-%m-%d\").iso()",
+                "2024-02-29T00:00:00Z",
+            ),
+            ("Time.parse(\"2024-02-29\").iso()", "2024-02-29T00:00:00Z"),
+            ("Time.date(2000, 2, 29).yearday()", "60"),
+            (
+                "Time.date(2024, 2, 28) + Time.days(1) == Time.date(2024, 2, 29)",
+                "true",
+            ),
+            (
+                "Time.date(2024, 3, 1) - Time.date(2024, 2, 28) == Time.days(2)",
+                "true",
+            ),
+        ],
+    );
+
+    // the date does not exist, it is not a syntax problem of the text
+    for (index, pattern) in ["", ", \"%Y-%m-%d\""].iter().enumerate() {
+        let source = format!("import Time\nTime.parse(\"2023-02-29\"{})\n", pattern);
+        let message = run_error(&format!("time_not_leap_{}", index), &source);
+        assert_eq!(
+            message,
+            "MathError : invalid time `2023-02-29`: invalid day `29`."
+        );
+    }
+    let message = run_error(
+        "time_not_leap_date",
+        "import Time\nTime.date(1900, 2, 29)\n",
+    );
+    assert_eq!(message, "MathError : invalid day `29`.");
+}
+
+#[test]
+fn offsets() {
+    assert_module_outputs(
+        "time_offsets",
+        MODULES,
+        &[
+            (
+                "Time.parse(\"2024-02-29T12:00:00+08:00\").to_offset(\"-05:00\").iso()",
+                "2024-02-28T23:00:00-05:00",
+            ),
+            // the same instant in different offsets
+            (
+                "Time.parse(\"2024-02-29T12:00:00+08:00\") == Time.parse(\"2024-02-29T04:00:00Z\")",
+                "true",
+            ),
+            (
+                "Time.parse(\"2024-02-29T12:00:00+08:00\").to_offset(-300) == Time.parse(\"2024-02-29T12:00:00+08:00\")",
+                "true",
+            ),
+            (
+                "Time.parse(\"2024-02-29T12:00:00+08:00\") < Time.parse(\"2024-02-29T12:00:00+07:00\")",
+                "true",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn negative_timestamps() {
+    assert_module_outputs(
+        "time_negative_timestamps",
+        MODULES,
+        &[
+            ("Time.from_timestamp(-86400).iso()", "1969-12-31T00:00:00Z"),
+            (
+                "Time.from_timestamp(-1.5).iso()",
+                "1969-12-31T23:59:58.500Z",
+            ),
+            ("Time.from_timestamp(-86400).timestamp()", "-86400"),
+            (
+                "Time.from_timestamp(-62135596800).iso()",
+                "0001-01-01T00:00:00Z",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn year_range() {
+    let cases = [
+        (
+            "Time.date(10000, 1, 1)",
+            "MathError : invalid year `10000`.",
+        ),
+        ("Time.date(0, 1, 1)", "MathError : invalid year `0`."),
+        (
+            "Time.from_timestamp(-62135596801)",
+            "MathError : DateTime out of range, the year should be in 1..=9999.",
+        ),
+        (
+            "Time.date(9999, 12, 31) + Time.days(1)",
+            "MathError : DateTime out of range, the year should be in 1..=9999.",
+        ),
+    ];
+    for (index, (code, expected)) in cases.iter().enumerate() {
+        let source = format!("import Time\nout {}\n", code);
+        let message = run_error(&format!("time_range_{}", index), &source);
+        assert_eq!(message, *expected, "`{}`", code);
+    }
+}
+
+#[test]
+fn format_and_parse() {
+    assert_module_outputs(
+        "time_format",
+        MODULES,
+        &[
+            (
+                "Time.date(2024, 3, 1, 8, 5).format(\"%d/%m/%Y %I:%M %p\")",
+                "01/03/2024 08:05 AM",
+            ),
+            (
+                "Time.parse(\"01/03/2024 08:05 PM\", \"%d/%m/%Y %I:%M %p\").iso()",
+                "2024-03-01T20:05:00Z",
+            ),
+            (
+                "Time.parse(\"2024-060\", \"%Y-%j\").iso()",
+                "2024-02-29T00:00:00Z",
+            ),
+        ],
+    );
+    let message = run_error(
+        "time_parse_syntax",
+        "import Time\nTime.parse(\"2024/02/29\", \"%Y-%m-%d\")\n",
+    );
+    assert!(
+        message.starts_with("SyntaxError : invalid time text"),
+        "{}",
+        message
+    );
+}
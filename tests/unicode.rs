@@ -0,0 +1,56 @@
+mod common;
+
+use common::{run, run_error};
+
+#[test]
+fn non_ascii_identifiers() {
+    let source = "größe = 2\n标签 = 3\nout größe + 标签\ncafé = 1\nout café\n";
+    assert_eq!(run("unicode_identifiers", source), ["5", "1"]);
+}
+
+#[test]
+fn joiners_inside_identifiers() {
+    // zero width non-joiner and joiner are kept after another identifier character
+    let source = "ab\u{200C}c = 1\nab\u{200D}c = 2\nout ab\u{200C}c\nout ab\u{200D}c\n";
+    assert_eq!(run("unicode_joiners", source), ["1", "2"]);
+}
+
+#[test]
+fn invisible_characters_outside_identifiers() {
+    let cases = [
+        ("x\u{200B}y = 1", '\u{200B}'),
+        ("\u{200C}x = 1", '\u{200C}'),
+        ("\u{200D}x = 1", '\u{200D}'),
+        ("x\u{200E} = 1", '\u{200E}'),
+        ("x\u{200F} = 1", '\u{200F}'),
+    ];
+    for (index, (source, ch)) in cases.iter().enumerate() {
+        let message = run_error(&format!("unicode_invisible_{}", index), source);
+        assert!(
+            message.starts_with(&format!("SyntaxError : unknown character '{}'", ch)),
+            "{:?}: {}",
+            source,
+            message
+        );
+    }
+}
+
+#[test]
+fn multibyte_strings() {
+    // lengths and indices count characters, not bytes
+    let source = "import Basic
+import String
+s = \"hé标😀x\"
+out len(s)
+out s[1]
+out s[3]
+t = new String(s)
+out t.substring(1, 3)
+out t.substring(-2)
+out t.reverse()
+";
+    assert_eq!(
+        run("unicode_strings", source),
+        ["5", "é", "😀", "é标", "😀x", "x😀标éh"]
+    );
+}